@@ -0,0 +1,102 @@
+//! Password hashing and access tokens.
+//!
+//! Passwords are stored as argon2 hashes in PHC string format. Session and API tokens are
+//! random and handed to the client once; only their SHA-256 digests are stored, which is
+//! enough for high-entropy secrets and lets a lookup be a plain indexed query.
+//!
+//! Fever clients authenticate with an `api_key` that is the MD5 of `username:password`,
+//! so that key is derived from a separate Fever password and stored the same way as tokens.
+
+use argon2::Argon2;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use md5::Md5;
+use rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha256};
+
+/// Shortest password accepted for an account.
+pub const MIN_PASSWORD_LEN: usize = 8;
+
+/// Hash a password for storage.
+pub fn hash_password(password: &str) -> Result<String, argon2::password_hash::Error> {
+    let salt = SaltString::generate(&mut OsRng);
+    Ok(Argon2::default()
+        .hash_password(password.as_bytes(), &salt)?
+        .to_string())
+}
+
+/// Check a password against a stored hash. Malformed hashes never match.
+pub fn verify_password(password: &str, hash: &str) -> bool {
+    PasswordHash::new(hash)
+        .map(|parsed| {
+            Argon2::default()
+                .verify_password(password.as_bytes(), &parsed)
+                .is_ok()
+        })
+        .unwrap_or(false)
+}
+
+/// A new random token, as 64 hex characters.
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// The digest a token is stored and looked up by.
+pub fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// The `api_key` a Fever client sends for this username and password.
+pub fn fever_api_key(username: &str, password: &str) -> String {
+    hex::encode(Md5::digest(format!("{}:{}", username, password).as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn password_round_trip() {
+        let hash = hash_password("correct horse").unwrap();
+        assert!(hash.starts_with("$argon2"));
+        assert!(verify_password("correct horse", &hash));
+        assert!(!verify_password("correct horse ", &hash));
+        assert!(!verify_password("correct horse", "not a hash"));
+    }
+
+    #[test]
+    fn password_hashes_are_salted() {
+        assert_ne!(
+            hash_password("secret12").unwrap(),
+            hash_password("secret12").unwrap()
+        );
+    }
+
+    #[test]
+    fn tokens_are_random_and_hashed_stably() {
+        let token = generate_token();
+        assert_eq!(token.len(), 64);
+        assert!(token.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(token, generate_token());
+        assert_eq!(hash_token(&token), hash_token(&token));
+        assert_ne!(hash_token(&token), token);
+        assert_eq!(
+            hash_token("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn fever_api_key_is_md5_of_credentials() {
+        assert_eq!(fever_api_key("", ""), "853ae90f0351324bd73ea615e6487517");
+        assert_eq!(
+            fever_api_key("alice", "secret12"),
+            "a8337f7911082dbd52bd0945db63c343"
+        );
+        assert_ne!(
+            fever_api_key("alice", "secret12"),
+            fever_api_key("bob", "secret12")
+        );
+    }
+}
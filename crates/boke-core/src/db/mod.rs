@@ -9,9 +9,17 @@ mod postgres;
 pub use pool::DatabasePool;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use thiserror::Error;
 
-use crate::models::{Article, ArticleQuery, Feed, FeedWithMeta, Folder, NewArticle, NewFeed};
+use crate::models::{
+    ApiToken, Article, ArticleQuery, ArticleScope, ArticleSource, DailyReadCount,
+    DuplicateCandidate, Feed, FeedHealth, FeedReadStats, FeedUpdate, FeedWithMeta, Folder,
+    FolderDeleteMode, HiddenArticle, Highlight, HighlightQuery, Label, NewArticle, NewFeed,
+    NewFeedFetch, NewHighlight, NewLabel, NewSmartFolder, NewUser, NewWebhook, NewWebhookDelivery,
+    SearchHit, SmartFolder, UpdateHighlight, User, Webhook, WebhookDelivery, WebhookEvent,
+};
+use crate::search::SearchQuery;
 
 #[derive(Error, Debug)]
 pub enum DbError {
@@ -26,10 +34,26 @@ pub enum DbError {
 
     #[error("Invalid database URL: {0}")]
     InvalidUrl(String),
+
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
 }
 
 pub type DbResult<T> = Result<T, DbError>;
 
+/// The user created along with the schema. The desktop app always acts as this user, and
+/// data from before multi-user support belongs to it.
+pub const DEFAULT_USER_ID: i64 = 1;
+
+/// Fetches kept per feed; older ones are dropped as new ones are recorded.
+pub const FETCH_HISTORY_LEN: i64 = 100;
+
+/// Fetches listed in `FeedHealth::recent_fetches`.
+pub const RECENT_FETCHES: i64 = 20;
+
+/// Delivery attempts kept per webhook; older ones are dropped as new ones are recorded.
+pub const WEBHOOK_DELIVERY_HISTORY_LEN: i64 = 100;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InsertResult {
     Inserted(i64),
@@ -37,33 +61,184 @@ pub enum InsertResult {
 }
 
 #[async_trait]
+/// A handle acts as one user, [`DEFAULT_USER_ID`] unless picked with [`Database::for_user`].
+/// Feeds and their articles are shared: users subscribe to them, and fetching a feed
+/// stores its articles once for every subscriber. Everything else, from folders and feed
+/// settings to read state, belongs to the acting user, who only sees the feeds they
+/// subscribe to. The exceptions are user administration, the refresh bookkeeping on
+/// shared feeds and articles, and delivering webhooks.
 pub trait Database: Send + Sync + Clone + 'static {
+    /// The same database, acting as another user.
+    fn for_user(&self, user_id: i64) -> Self;
+    fn user_id(&self) -> i64;
+
+    // User administration
+    async fn get_users(&self) -> DbResult<Vec<User>>;
+    async fn get_user(&self, id: i64) -> DbResult<Option<User>>;
+    async fn get_user_by_name(&self, username: &str) -> DbResult<Option<User>>;
+    async fn create_user(&self, user: &NewUser, password_hash: Option<&str>) -> DbResult<User>;
+    async fn set_user_disabled(&self, id: i64, disabled: bool) -> DbResult<()>;
+    /// Delete a user and their data, along with feeds nobody else subscribes to.
+    async fn delete_user(&self, id: i64) -> DbResult<()>;
+    async fn get_password_hash(&self, id: i64) -> DbResult<Option<String>>;
+    /// Replace a user's password hash, which also ends all of their sessions.
+    async fn set_password_hash(&self, id: i64, password_hash: Option<&str>) -> DbResult<()>;
+
+    // Sessions and API tokens, stored and looked up by the digest of their secret
+    /// Start a session, dropping any that have expired.
+    async fn create_session(
+        &self,
+        user_id: i64,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> DbResult<()>;
+    /// The user an unexpired session belongs to.
+    async fn get_session_user(&self, token_hash: &str) -> DbResult<Option<User>>;
+    async fn delete_session(&self, token_hash: &str) -> DbResult<()>;
+    async fn get_api_tokens(&self) -> DbResult<Vec<ApiToken>>;
+    async fn create_api_token(&self, name: &str, token_hash: &str) -> DbResult<ApiToken>;
+    async fn delete_api_token(&self, id: i64) -> DbResult<()>;
+    /// The user a token belongs to, recording that it was used.
+    async fn get_token_user(&self, token_hash: &str) -> DbResult<Option<User>>;
+    /// Set or clear the digest of the acting user's Fever API key.
+    async fn set_fever_key_hash(&self, key_hash: Option<&str>) -> DbResult<()>;
+    async fn get_fever_user(&self, key_hash: &str) -> DbResult<Option<User>>;
+
     // Feed operations
+    /// Subscribe to a feed, adding it unless another user already subscribes to its URL.
     async fn insert_feed(&self, feed: &NewFeed) -> DbResult<i64>;
     async fn get_feed(&self, id: i64) -> DbResult<Option<Feed>>;
     async fn get_feeds(&self) -> DbResult<Vec<FeedWithMeta>>;
+    /// Unsubscribe from a feed; it is deleted with its articles once nobody subscribes.
     async fn delete_feed(&self, id: i64) -> DbResult<()>;
+    /// Change the subscription's settings. The feed's own URLs can only be changed by its
+    /// sole subscriber.
+    async fn update_feed(&self, id: i64, update: &FeedUpdate) -> DbResult<()>;
+    async fn get_feed_url(&self, id: i64) -> DbResult<Option<String>>;
+
+    // Shared feed and article data, kept up to date by refreshes
     async fn update_feed_favicon(&self, id: i64, favicon_url: &str) -> DbResult<()>;
     async fn update_feed_last_fetched(&self, id: i64) -> DbResult<()>;
-    async fn get_feed_url(&self, id: i64) -> DbResult<Option<String>>;
+    /// Store a new article unless every subscriber has hidden or deleted its guid.
+    async fn insert_article(&self, article: &NewArticle) -> DbResult<InsertResult>;
+    async fn update_article_content(&self, id: i64, content: &str) -> DbResult<()>;
 
     // Article operations
-    async fn insert_article(&self, article: &NewArticle) -> DbResult<InsertResult>;
     async fn get_article(&self, id: i64) -> DbResult<Option<Article>>;
     async fn get_articles(&self, query: &ArticleQuery) -> DbResult<Vec<Article>>;
     async fn toggle_read(&self, id: i64) -> DbResult<()>;
-    async fn mark_all_read(&self, feed_id: Option<i64>) -> DbResult<()>;
-    async fn mark_all_unread(&self, feed_id: Option<i64>) -> DbResult<()>;
+    /// Mark an article and its duplicates read or unread.
+    async fn set_read(&self, id: i64, read: bool) -> DbResult<()>;
+    async fn mark_all_read(&self, scope: ArticleScope) -> DbResult<()>;
+    async fn mark_all_unread(&self, scope: ArticleScope) -> DbResult<()>;
+    /// Count articles matching a query, ignoring paging.
+    async fn count_articles(&self, query: &ArticleQuery) -> DbResult<i64>;
+    /// Ids of the articles matching a query in query order, ignoring paging.
+    async fn get_article_ids(&self, query: &ArticleQuery) -> DbResult<Vec<i64>>;
+    /// Leave an article out of every list and keep its guid from being inserted again.
+    async fn hide_article(&self, id: i64) -> DbResult<HiddenArticle>;
+    /// Delete an article, keeping a tombstone so that refreshes skip it. The article stays
+    /// stored while other subscribers still see it.
+    async fn delete_article(&self, id: i64) -> DbResult<HiddenArticle>;
+    async fn get_hidden_articles(&self, feed_id: Option<i64>) -> DbResult<Vec<HiddenArticle>>;
+    /// Drop a tombstone; a deleted article comes back on the feed's next refresh.
+    async fn unhide_article(&self, hidden_id: i64) -> DbResult<()>;
+    /// Mark articles matching a query as read, along with their duplicates.
+    async fn mark_articles_read(&self, query: &ArticleQuery) -> DbResult<()>;
     async fn toggle_favorite(&self, id: i64) -> DbResult<()>;
+    async fn set_favorite(&self, id: i64, favorite: bool) -> DbResult<()>;
     async fn get_favorites_count(&self) -> DbResult<i64>;
-    async fn search_articles(&self, query: &str, limit: i64) -> DbResult<Vec<Article>>;
-    async fn update_article_content(&self, id: i64, content: &str) -> DbResult<()>;
+    /// Rebuild the full-text index from the stored articles.
+    async fn reindex_search(&self) -> DbResult<()>;
+    async fn search_articles(
+        &self,
+        query: &SearchQuery,
+        limit: i64,
+        offset: i64,
+    ) -> DbResult<Vec<SearchHit>>;
     async fn get_article_link(&self, id: i64) -> DbResult<Option<String>>;
+    /// Ids and guids of the articles the user sees in a feed.
+    async fn get_feed_guids(&self, feed_id: i64) -> DbResult<Vec<(i64, String)>>;
+
+    // Duplicate detection
+    async fn find_duplicate_candidates(
+        &self,
+        article: &NewArticle,
+    ) -> DbResult<Vec<DuplicateCandidate>>;
+    async fn set_duplicate_group(&self, article_id: i64, group_id: i64) -> DbResult<()>;
+    async fn get_duplicate_sources(&self, group_ids: &[i64]) -> DbResult<Vec<ArticleSource>>;
+
+    // Reading statistics
+    async fn get_reads_per_day(&self, since: DateTime<Utc>) -> DbResult<Vec<DailyReadCount>>;
+    async fn get_feed_read_stats(&self) -> DbResult<Vec<FeedReadStats>>;
+    async fn get_average_time_to_read(&self) -> DbResult<Option<f64>>;
+
+    // Feed health
+    async fn record_feed_fetch(&self, fetch: &NewFeedFetch) -> DbResult<()>;
+    /// Posting activity since `active_since` and fetch statistics for one feed; it counts
+    /// as dead when nothing was published after `dead_before`. `None` without a
+    /// subscription to it.
+    async fn get_feed_health(
+        &self,
+        feed_id: i64,
+        active_since: DateTime<Utc>,
+        dead_before: DateTime<Utc>,
+    ) -> DbResult<Option<FeedHealth>>;
 
     // Folder operations
     async fn get_folders(&self) -> DbResult<Vec<Folder>>;
-    async fn create_folder(&self, name: &str) -> DbResult<Folder>;
+    /// Create a folder after the existing ones under `parent_id`.
+    async fn create_folder(&self, name: &str, parent_id: Option<i64>) -> DbResult<Folder>;
     async fn rename_folder(&self, id: i64, name: &str) -> DbResult<()>;
-    async fn delete_folder(&self, id: i64) -> DbResult<()>;
+    async fn delete_folder(&self, id: i64, mode: FolderDeleteMode) -> DbResult<()>;
+    /// Move a folder to the end of `parent_id`'s sub-folders; callers check for cycles.
+    async fn move_folder(&self, id: i64, parent_id: Option<i64>) -> DbResult<()>;
+    /// Number the given sub-folders of `parent_id` in order; other ids are ignored.
+    async fn reorder_folders(&self, parent_id: Option<i64>, folder_ids: &[i64]) -> DbResult<()>;
+    /// Move a feed to the end of a folder's feeds.
     async fn move_feed_to_folder(&self, feed_id: i64, folder_id: Option<i64>) -> DbResult<()>;
+    /// Number the given feeds of `folder_id` in order; other ids are ignored.
+    async fn reorder_feeds(&self, folder_id: Option<i64>, feed_ids: &[i64]) -> DbResult<()>;
+
+    // Smart folder operations
+    async fn get_smart_folders(&self) -> DbResult<Vec<SmartFolder>>;
+    async fn get_smart_folder(&self, id: i64) -> DbResult<Option<SmartFolder>>;
+    async fn create_smart_folder(&self, folder: &NewSmartFolder) -> DbResult<SmartFolder>;
+    async fn update_smart_folder(&self, id: i64, folder: &NewSmartFolder) -> DbResult<()>;
+    async fn delete_smart_folder(&self, id: i64) -> DbResult<()>;
+
+    // Label operations
+    async fn get_labels(&self) -> DbResult<Vec<Label>>;
+    async fn create_label(&self, label: &NewLabel) -> DbResult<Label>;
+    async fn update_label(&self, id: i64, label: &NewLabel) -> DbResult<()>;
+    async fn delete_label(&self, id: i64) -> DbResult<()>;
+    async fn add_label_to_articles(&self, label_id: i64, article_ids: &[i64]) -> DbResult<()>;
+    async fn remove_label_from_articles(&self, label_id: i64, article_ids: &[i64]) -> DbResult<()>;
+    /// `(article_id, label_id)` pairs for the given articles.
+    async fn get_article_labels(&self, article_ids: &[i64]) -> DbResult<Vec<(i64, i64)>>;
+
+    // Highlight operations
+    async fn get_highlights(&self, query: &HighlightQuery) -> DbResult<Vec<Highlight>>;
+    async fn get_highlight(&self, id: i64) -> DbResult<Option<Highlight>>;
+    async fn create_highlight(&self, highlight: &NewHighlight) -> DbResult<Highlight>;
+    async fn update_highlight(&self, id: i64, highlight: &UpdateHighlight) -> DbResult<()>;
+    async fn delete_highlight(&self, id: i64) -> DbResult<()>;
+
+    // Webhook operations
+    async fn get_webhooks(&self) -> DbResult<Vec<Webhook>>;
+    async fn get_webhook(&self, id: i64) -> DbResult<Option<Webhook>>;
+    async fn create_webhook(&self, webhook: &NewWebhook, secret: &str) -> DbResult<Webhook>;
+    /// Change everything about a webhook but its secret.
+    async fn update_webhook(&self, id: i64, webhook: &NewWebhook) -> DbResult<()>;
+    async fn delete_webhook(&self, id: i64) -> DbResult<()>;
+    /// The latest delivery attempts of one of the user's webhooks, newest first.
+    async fn get_webhook_deliveries(
+        &self,
+        webhook_id: i64,
+        limit: i64,
+    ) -> DbResult<Vec<WebhookDelivery>>;
+    /// Every user's enabled webhooks for an event.
+    async fn get_enabled_webhooks(&self, event: WebhookEvent) -> DbResult<Vec<Webhook>>;
+    /// Log a delivery attempt, keeping the latest [`WEBHOOK_DELIVERY_HISTORY_LEN`].
+    async fn record_webhook_delivery(&self, delivery: &NewWebhookDelivery) -> DbResult<()>;
 }
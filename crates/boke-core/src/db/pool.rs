@@ -1,6 +1,14 @@
 use crate::db::{Database, DbError, DbResult, InsertResult};
-use crate::models::{Article, ArticleQuery, Feed, FeedWithMeta, Folder, NewArticle, NewFeed};
+use crate::models::{
+    ApiToken, Article, ArticleQuery, ArticleScope, ArticleSource, DailyReadCount,
+    DuplicateCandidate, Feed, FeedHealth, FeedReadStats, FeedUpdate, FeedWithMeta, Folder,
+    FolderDeleteMode, HiddenArticle, Highlight, HighlightQuery, Label, NewArticle, NewFeed,
+    NewFeedFetch, NewHighlight, NewLabel, NewSmartFolder, NewUser, NewWebhook, NewWebhookDelivery,
+    SearchHit, SmartFolder, UpdateHighlight, User, Webhook, WebhookDelivery, WebhookEvent,
+};
+use crate::search::SearchQuery;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 #[cfg(feature = "sqlite")]
 use super::sqlite::SqliteDatabase;
@@ -55,6 +63,182 @@ impl DatabasePool {
 
 #[async_trait]
 impl Database for DatabasePool {
+    fn for_user(&self, user_id: i64) -> Self {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => Self::Sqlite(db.for_user(user_id)),
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => Self::Postgres(db.for_user(user_id)),
+        }
+    }
+
+    fn user_id(&self) -> i64 {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.user_id(),
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.user_id(),
+        }
+    }
+
+    async fn get_users(&self) -> DbResult<Vec<User>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.get_users().await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.get_users().await,
+        }
+    }
+
+    async fn get_user(&self, id: i64) -> DbResult<Option<User>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.get_user(id).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.get_user(id).await,
+        }
+    }
+
+    async fn get_user_by_name(&self, username: &str) -> DbResult<Option<User>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.get_user_by_name(username).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.get_user_by_name(username).await,
+        }
+    }
+
+    async fn create_user(&self, user: &NewUser, password_hash: Option<&str>) -> DbResult<User> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.create_user(user, password_hash).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.create_user(user, password_hash).await,
+        }
+    }
+
+    async fn set_user_disabled(&self, id: i64, disabled: bool) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.set_user_disabled(id, disabled).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.set_user_disabled(id, disabled).await,
+        }
+    }
+
+    async fn delete_user(&self, id: i64) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.delete_user(id).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.delete_user(id).await,
+        }
+    }
+
+    async fn get_password_hash(&self, id: i64) -> DbResult<Option<String>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.get_password_hash(id).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.get_password_hash(id).await,
+        }
+    }
+
+    async fn set_password_hash(&self, id: i64, password_hash: Option<&str>) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.set_password_hash(id, password_hash).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.set_password_hash(id, password_hash).await,
+        }
+    }
+
+    async fn create_session(
+        &self,
+        user_id: i64,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.create_session(user_id, token_hash, expires_at).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.create_session(user_id, token_hash, expires_at).await,
+        }
+    }
+
+    async fn get_session_user(&self, token_hash: &str) -> DbResult<Option<User>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.get_session_user(token_hash).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.get_session_user(token_hash).await,
+        }
+    }
+
+    async fn delete_session(&self, token_hash: &str) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.delete_session(token_hash).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.delete_session(token_hash).await,
+        }
+    }
+
+    async fn get_api_tokens(&self) -> DbResult<Vec<ApiToken>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.get_api_tokens().await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.get_api_tokens().await,
+        }
+    }
+
+    async fn create_api_token(&self, name: &str, token_hash: &str) -> DbResult<ApiToken> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.create_api_token(name, token_hash).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.create_api_token(name, token_hash).await,
+        }
+    }
+
+    async fn delete_api_token(&self, id: i64) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.delete_api_token(id).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.delete_api_token(id).await,
+        }
+    }
+
+    async fn get_token_user(&self, token_hash: &str) -> DbResult<Option<User>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.get_token_user(token_hash).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.get_token_user(token_hash).await,
+        }
+    }
+
+    async fn set_fever_key_hash(&self, key_hash: Option<&str>) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.set_fever_key_hash(key_hash).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.set_fever_key_hash(key_hash).await,
+        }
+    }
+
+    async fn get_fever_user(&self, key_hash: &str) -> DbResult<Option<User>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.get_fever_user(key_hash).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.get_fever_user(key_hash).await,
+        }
+    }
+
     async fn insert_feed(&self, feed: &NewFeed) -> DbResult<i64> {
         match self {
             #[cfg(feature = "sqlite")]
@@ -91,6 +275,15 @@ impl Database for DatabasePool {
         }
     }
 
+    async fn update_feed(&self, id: i64, update: &FeedUpdate) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.update_feed(id, update).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.update_feed(id, update).await,
+        }
+    }
+
     async fn update_feed_favicon(&self, id: i64, favicon_url: &str) -> DbResult<()> {
         match self {
             #[cfg(feature = "sqlite")]
@@ -154,21 +347,93 @@ impl Database for DatabasePool {
         }
     }
 
-    async fn mark_all_read(&self, feed_id: Option<i64>) -> DbResult<()> {
+    async fn set_read(&self, id: i64, read: bool) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.set_read(id, read).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.set_read(id, read).await,
+        }
+    }
+
+    async fn mark_all_read(&self, scope: ArticleScope) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.mark_all_read(scope).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.mark_all_read(scope).await,
+        }
+    }
+
+    async fn mark_all_unread(&self, scope: ArticleScope) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.mark_all_unread(scope).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.mark_all_unread(scope).await,
+        }
+    }
+
+    async fn count_articles(&self, query: &ArticleQuery) -> DbResult<i64> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.count_articles(query).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.count_articles(query).await,
+        }
+    }
+
+    async fn get_article_ids(&self, query: &ArticleQuery) -> DbResult<Vec<i64>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.get_article_ids(query).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.get_article_ids(query).await,
+        }
+    }
+
+    async fn hide_article(&self, id: i64) -> DbResult<HiddenArticle> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.hide_article(id).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.hide_article(id).await,
+        }
+    }
+
+    async fn delete_article(&self, id: i64) -> DbResult<HiddenArticle> {
         match self {
             #[cfg(feature = "sqlite")]
-            Self::Sqlite(db) => db.mark_all_read(feed_id).await,
+            Self::Sqlite(db) => db.delete_article(id).await,
             #[cfg(feature = "postgres")]
-            Self::Postgres(db) => db.mark_all_read(feed_id).await,
+            Self::Postgres(db) => db.delete_article(id).await,
         }
     }
 
-    async fn mark_all_unread(&self, feed_id: Option<i64>) -> DbResult<()> {
+    async fn get_hidden_articles(&self, feed_id: Option<i64>) -> DbResult<Vec<HiddenArticle>> {
         match self {
             #[cfg(feature = "sqlite")]
-            Self::Sqlite(db) => db.mark_all_unread(feed_id).await,
+            Self::Sqlite(db) => db.get_hidden_articles(feed_id).await,
             #[cfg(feature = "postgres")]
-            Self::Postgres(db) => db.mark_all_unread(feed_id).await,
+            Self::Postgres(db) => db.get_hidden_articles(feed_id).await,
+        }
+    }
+
+    async fn unhide_article(&self, hidden_id: i64) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.unhide_article(hidden_id).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.unhide_article(hidden_id).await,
+        }
+    }
+
+    async fn mark_articles_read(&self, query: &ArticleQuery) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.mark_articles_read(query).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.mark_articles_read(query).await,
         }
     }
 
@@ -181,6 +446,15 @@ impl Database for DatabasePool {
         }
     }
 
+    async fn set_favorite(&self, id: i64, favorite: bool) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.set_favorite(id, favorite).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.set_favorite(id, favorite).await,
+        }
+    }
+
     async fn get_favorites_count(&self) -> DbResult<i64> {
         match self {
             #[cfg(feature = "sqlite")]
@@ -190,12 +464,26 @@ impl Database for DatabasePool {
         }
     }
 
-    async fn search_articles(&self, query: &str, limit: i64) -> DbResult<Vec<Article>> {
+    async fn reindex_search(&self) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.reindex_search().await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.reindex_search().await,
+        }
+    }
+
+    async fn search_articles(
+        &self,
+        query: &SearchQuery,
+        limit: i64,
+        offset: i64,
+    ) -> DbResult<Vec<SearchHit>> {
         match self {
             #[cfg(feature = "sqlite")]
-            Self::Sqlite(db) => db.search_articles(query, limit).await,
+            Self::Sqlite(db) => db.search_articles(query, limit, offset).await,
             #[cfg(feature = "postgres")]
-            Self::Postgres(db) => db.search_articles(query, limit).await,
+            Self::Postgres(db) => db.search_articles(query, limit, offset).await,
         }
     }
 
@@ -217,6 +505,95 @@ impl Database for DatabasePool {
         }
     }
 
+    async fn get_feed_guids(&self, feed_id: i64) -> DbResult<Vec<(i64, String)>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.get_feed_guids(feed_id).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.get_feed_guids(feed_id).await,
+        }
+    }
+
+    async fn find_duplicate_candidates(
+        &self,
+        article: &NewArticle,
+    ) -> DbResult<Vec<DuplicateCandidate>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.find_duplicate_candidates(article).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.find_duplicate_candidates(article).await,
+        }
+    }
+
+    async fn set_duplicate_group(&self, article_id: i64, group_id: i64) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.set_duplicate_group(article_id, group_id).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.set_duplicate_group(article_id, group_id).await,
+        }
+    }
+
+    async fn get_duplicate_sources(&self, group_ids: &[i64]) -> DbResult<Vec<ArticleSource>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.get_duplicate_sources(group_ids).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.get_duplicate_sources(group_ids).await,
+        }
+    }
+
+    async fn get_reads_per_day(&self, since: DateTime<Utc>) -> DbResult<Vec<DailyReadCount>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.get_reads_per_day(since).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.get_reads_per_day(since).await,
+        }
+    }
+
+    async fn get_feed_read_stats(&self) -> DbResult<Vec<FeedReadStats>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.get_feed_read_stats().await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.get_feed_read_stats().await,
+        }
+    }
+
+    async fn get_average_time_to_read(&self) -> DbResult<Option<f64>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.get_average_time_to_read().await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.get_average_time_to_read().await,
+        }
+    }
+
+    async fn record_feed_fetch(&self, fetch: &NewFeedFetch) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.record_feed_fetch(fetch).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.record_feed_fetch(fetch).await,
+        }
+    }
+
+    async fn get_feed_health(
+        &self,
+        feed_id: i64,
+        active_since: DateTime<Utc>,
+        dead_before: DateTime<Utc>,
+    ) -> DbResult<Option<FeedHealth>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.get_feed_health(feed_id, active_since, dead_before).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.get_feed_health(feed_id, active_since, dead_before).await,
+        }
+    }
+
     async fn get_folders(&self) -> DbResult<Vec<Folder>> {
         match self {
             #[cfg(feature = "sqlite")]
@@ -226,12 +603,12 @@ impl Database for DatabasePool {
         }
     }
 
-    async fn create_folder(&self, name: &str) -> DbResult<Folder> {
+    async fn create_folder(&self, name: &str, parent_id: Option<i64>) -> DbResult<Folder> {
         match self {
             #[cfg(feature = "sqlite")]
-            Self::Sqlite(db) => db.create_folder(name).await,
+            Self::Sqlite(db) => db.create_folder(name, parent_id).await,
             #[cfg(feature = "postgres")]
-            Self::Postgres(db) => db.create_folder(name).await,
+            Self::Postgres(db) => db.create_folder(name, parent_id).await,
         }
     }
 
@@ -244,12 +621,30 @@ impl Database for DatabasePool {
         }
     }
 
-    async fn delete_folder(&self, id: i64) -> DbResult<()> {
+    async fn delete_folder(&self, id: i64, mode: FolderDeleteMode) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.delete_folder(id, mode).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.delete_folder(id, mode).await,
+        }
+    }
+
+    async fn move_folder(&self, id: i64, parent_id: Option<i64>) -> DbResult<()> {
         match self {
             #[cfg(feature = "sqlite")]
-            Self::Sqlite(db) => db.delete_folder(id).await,
+            Self::Sqlite(db) => db.move_folder(id, parent_id).await,
             #[cfg(feature = "postgres")]
-            Self::Postgres(db) => db.delete_folder(id).await,
+            Self::Postgres(db) => db.move_folder(id, parent_id).await,
+        }
+    }
+
+    async fn reorder_folders(&self, parent_id: Option<i64>, folder_ids: &[i64]) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.reorder_folders(parent_id, folder_ids).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.reorder_folders(parent_id, folder_ids).await,
         }
     }
 
@@ -261,4 +656,242 @@ impl Database for DatabasePool {
             Self::Postgres(db) => db.move_feed_to_folder(feed_id, folder_id).await,
         }
     }
+
+    async fn reorder_feeds(&self, folder_id: Option<i64>, feed_ids: &[i64]) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.reorder_feeds(folder_id, feed_ids).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.reorder_feeds(folder_id, feed_ids).await,
+        }
+    }
+
+    async fn get_smart_folders(&self) -> DbResult<Vec<SmartFolder>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.get_smart_folders().await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.get_smart_folders().await,
+        }
+    }
+
+    async fn get_smart_folder(&self, id: i64) -> DbResult<Option<SmartFolder>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.get_smart_folder(id).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.get_smart_folder(id).await,
+        }
+    }
+
+    async fn create_smart_folder(&self, folder: &NewSmartFolder) -> DbResult<SmartFolder> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.create_smart_folder(folder).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.create_smart_folder(folder).await,
+        }
+    }
+
+    async fn update_smart_folder(&self, id: i64, folder: &NewSmartFolder) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.update_smart_folder(id, folder).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.update_smart_folder(id, folder).await,
+        }
+    }
+
+    async fn delete_smart_folder(&self, id: i64) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.delete_smart_folder(id).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.delete_smart_folder(id).await,
+        }
+    }
+
+    async fn get_labels(&self) -> DbResult<Vec<Label>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.get_labels().await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.get_labels().await,
+        }
+    }
+
+    async fn create_label(&self, label: &NewLabel) -> DbResult<Label> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.create_label(label).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.create_label(label).await,
+        }
+    }
+
+    async fn update_label(&self, id: i64, label: &NewLabel) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.update_label(id, label).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.update_label(id, label).await,
+        }
+    }
+
+    async fn delete_label(&self, id: i64) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.delete_label(id).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.delete_label(id).await,
+        }
+    }
+
+    async fn add_label_to_articles(&self, label_id: i64, article_ids: &[i64]) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.add_label_to_articles(label_id, article_ids).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.add_label_to_articles(label_id, article_ids).await,
+        }
+    }
+
+    async fn remove_label_from_articles(&self, label_id: i64, article_ids: &[i64]) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.remove_label_from_articles(label_id, article_ids).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.remove_label_from_articles(label_id, article_ids).await,
+        }
+    }
+
+    async fn get_article_labels(&self, article_ids: &[i64]) -> DbResult<Vec<(i64, i64)>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.get_article_labels(article_ids).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.get_article_labels(article_ids).await,
+        }
+    }
+
+    async fn get_highlights(&self, query: &HighlightQuery) -> DbResult<Vec<Highlight>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.get_highlights(query).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.get_highlights(query).await,
+        }
+    }
+
+    async fn get_highlight(&self, id: i64) -> DbResult<Option<Highlight>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.get_highlight(id).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.get_highlight(id).await,
+        }
+    }
+
+    async fn create_highlight(&self, highlight: &NewHighlight) -> DbResult<Highlight> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.create_highlight(highlight).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.create_highlight(highlight).await,
+        }
+    }
+
+    async fn update_highlight(&self, id: i64, highlight: &UpdateHighlight) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.update_highlight(id, highlight).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.update_highlight(id, highlight).await,
+        }
+    }
+
+    async fn delete_highlight(&self, id: i64) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.delete_highlight(id).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.delete_highlight(id).await,
+        }
+    }
+
+    async fn get_webhooks(&self) -> DbResult<Vec<Webhook>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.get_webhooks().await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.get_webhooks().await,
+        }
+    }
+
+    async fn get_webhook(&self, id: i64) -> DbResult<Option<Webhook>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.get_webhook(id).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.get_webhook(id).await,
+        }
+    }
+
+    async fn create_webhook(&self, webhook: &NewWebhook, secret: &str) -> DbResult<Webhook> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.create_webhook(webhook, secret).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.create_webhook(webhook, secret).await,
+        }
+    }
+
+    async fn update_webhook(&self, id: i64, webhook: &NewWebhook) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.update_webhook(id, webhook).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.update_webhook(id, webhook).await,
+        }
+    }
+
+    async fn delete_webhook(&self, id: i64) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.delete_webhook(id).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.delete_webhook(id).await,
+        }
+    }
+
+    async fn get_webhook_deliveries(
+        &self,
+        webhook_id: i64,
+        limit: i64,
+    ) -> DbResult<Vec<WebhookDelivery>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.get_webhook_deliveries(webhook_id, limit).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.get_webhook_deliveries(webhook_id, limit).await,
+        }
+    }
+
+    async fn get_enabled_webhooks(&self, event: WebhookEvent) -> DbResult<Vec<Webhook>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.get_enabled_webhooks(event).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.get_enabled_webhooks(event).await,
+        }
+    }
+
+    async fn record_webhook_delivery(&self, delivery: &NewWebhookDelivery) -> DbResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => db.record_webhook_delivery(delivery).await,
+            #[cfg(feature = "postgres")]
+            Self::Postgres(db) => db.record_webhook_delivery(delivery).await,
+        }
+    }
 }
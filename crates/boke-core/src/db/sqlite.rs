@@ -1,14 +1,31 @@
 use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
 use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
-use sqlx::{QueryBuilder, Sqlite, SqlitePool};
+use sqlx::{Connection, QueryBuilder, Sqlite, SqlitePool};
+use std::collections::HashSet;
 use std::str::FromStr;
 
-use super::{Database, DbResult, InsertResult};
-use crate::models::{Article, ArticleQuery, Feed, FeedWithMeta, Folder, NewArticle, NewFeed};
+use super::{
+    DEFAULT_USER_ID, Database, DbError, DbResult, FETCH_HISTORY_LEN, InsertResult, RECENT_FETCHES,
+    WEBHOOK_DELIVERY_HISTORY_LEN,
+};
+use crate::dedup::{self, DUPLICATE_WINDOW, MAX_TITLE_CANDIDATES};
+use crate::models::{
+    ApiToken, Article, ArticleQuery, ArticleScope, ArticleSort, ArticleSource, DailyReadCount,
+    DuplicateCandidate, Feed, FeedFetch, FeedHealth, FeedReadStats, FeedUpdate, FeedWithMeta,
+    Folder, FolderDeleteMode, FolderId, HiddenArticle, Highlight, HighlightQuery, Label,
+    NewArticle, NewFeed, NewFeedFetch, NewHighlight, NewLabel, NewSmartFolder, NewUser, NewWebhook,
+    NewWebhookDelivery, SearchHit, SmartFolder, UpdateHighlight, User, Webhook, WebhookDelivery,
+    WebhookEvent,
+};
+use crate::search::{
+    FilterField, SearchFilter, SearchQuery, fts5_quote, highlight_snippet, like_contains,
+};
 
 #[derive(Clone)]
 pub struct SqliteDatabase {
     pool: SqlitePool,
+    user_id: i64,
 }
 
 impl SqliteDatabase {
@@ -26,25 +43,246 @@ impl SqliteDatabase {
         // Initialize schema
         Self::init_schema(&pool).await?;
 
-        Ok(Self { pool })
+        Ok(Self {
+            pool,
+            user_id: DEFAULT_USER_ID,
+        })
     }
 
     async fn init_schema(pool: &SqlitePool) -> DbResult<()> {
+        let before_users =
+            Self::table_exists(pool, "feeds").await? && !Self::table_exists(pool, "users").await?;
+
         sqlx::query(SCHEMA).execute(pool).await?;
+        Self::migrate_columns(pool, COLUMN_MIGRATIONS).await?;
+        if before_users {
+            Self::migrate_columns(pool, PRE_USERS_COLUMN_MIGRATIONS).await?;
+            Self::rebuild_tables(pool, RENAME_PRE_USERS_TABLES, MIGRATE_TO_USERS).await?;
+        } else if Self::folder_names_unique_per_user(pool).await? {
+            Self::rebuild_tables(
+                pool,
+                RENAME_UNIQUE_NAME_FOLDERS,
+                MIGRATE_UNIQUE_NAME_FOLDERS,
+            )
+            .await?;
+        }
+        sqlx::query(POST_MIGRATION_SCHEMA).execute(pool).await?;
+        Self::migrate_search_index(pool).await?;
+        Ok(())
+    }
+
+    async fn table_exists(pool: &SqlitePool, name: &str) -> DbResult<bool> {
+        let count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?",
+        )
+        .bind(name)
+        .fetch_one(pool)
+        .await?;
+        Ok(count > 0)
+    }
+
+    /// Whether folders still carry the per-user unique name constraint, which kept two
+    /// parents from having sub-folders of the same name.
+    async fn folder_names_unique_per_user(pool: &SqlitePool) -> DbResult<bool> {
+        let count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'folders' AND sql LIKE '%UNIQUE(user_id, name)%'",
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(count > 0)
+    }
+
+    /// Change the constraints of existing tables: `rename` moves them aside, they are
+    /// recreated from `SCHEMA`, and `refill` copies the data over and drops the old ones.
+    ///
+    /// That needs foreign keys off, and the references in other tables left pointing at
+    /// the original names.
+    async fn rebuild_tables(pool: &SqlitePool, rename: &str, refill: &str) -> DbResult<()> {
+        let mut conn = pool.acquire().await?;
+        sqlx::query("PRAGMA foreign_keys = OFF")
+            .execute(&mut *conn)
+            .await?;
+        sqlx::query("PRAGMA legacy_alter_table = ON")
+            .execute(&mut *conn)
+            .await?;
+
+        let mut tx = conn.begin().await?;
+        sqlx::query(rename).execute(&mut *tx).await?;
+        sqlx::query(SCHEMA).execute(&mut *tx).await?;
+        sqlx::query(refill).execute(&mut *tx).await?;
+        tx.commit().await?;
+
+        sqlx::query("PRAGMA legacy_alter_table = OFF")
+            .execute(&mut *conn)
+            .await?;
+        sqlx::query("PRAGMA foreign_keys = ON")
+            .execute(&mut *conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Recreate and rebuild the full-text index when its definition has changed.
+    async fn migrate_search_index(pool: &SqlitePool) -> DbResult<()> {
+        let version = sqlx::query_scalar::<_, String>(
+            "SELECT value FROM settings WHERE key = 'search_index_version'",
+        )
+        .fetch_optional(pool)
+        .await?;
+        if version.as_deref() == Some(SEARCH_INDEX_VERSION) {
+            return Ok(());
+        }
+
+        let mut tx = pool.begin().await?;
+        sqlx::query(FTS_SCHEMA).execute(&mut *tx).await?;
+        sqlx::query(
+            "INSERT INTO settings (key, value) VALUES ('search_index_version', ?) ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        )
+        .bind(SEARCH_INDEX_VERSION)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Add columns introduced after the initial schema to existing databases.
+    async fn migrate_columns(pool: &SqlitePool, migrations: &[(&str, &str, &str)]) -> DbResult<()> {
+        for (table, column, definition) in migrations {
+            let exists = sqlx::query_scalar::<_, i64>(
+                "SELECT COUNT(*) FROM pragma_table_info(?) WHERE name = ?",
+            )
+            .bind(table)
+            .bind(column)
+            .fetch_one(pool)
+            .await?
+                > 0;
+
+            if !exists {
+                sqlx::query(&format!(
+                    "ALTER TABLE {} ADD COLUMN {} {}",
+                    table, column, definition
+                ))
+                .execute(pool)
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn insert_categories(&self, article_id: i64, categories: &[String]) -> DbResult<()> {
+        if categories.is_empty() {
+            return Ok(());
+        }
+
+        let mut qb: QueryBuilder<Sqlite> =
+            QueryBuilder::new("INSERT OR IGNORE INTO article_categories (article_id, name) ");
+        qb.push_values(categories, |mut row, name| {
+            row.push_bind(article_id).push_bind(name);
+        });
+        qb.build().execute(&self.pool).await?;
         Ok(())
     }
 }
 
+/// Articles of the acting user's feeds with their read state; bind the user id next.
+const ARTICLE_SELECT: &str = r#"
+    SELECT
+        a.id, a.feed_id, a.guid, a.title, a.link, a.author, a.summary, a.content,
+        a.image_url, a.enclosure_url, a.enclosure_type, a.published_at,
+        COALESCE(st.is_read, 0) as is_read, COALESCE(st.is_favorite, 0) as is_favorite,
+        a.created_at, st.read_at, st.favorited_at, a.dedup_group_id,
+        COALESCE(s.custom_title, f.title) as feed_title, f.favicon_url as feed_favicon_url
+    FROM articles a
+    JOIN feeds f ON a.feed_id = f.id
+    JOIN subscriptions s ON s.feed_id = a.feed_id
+    LEFT JOIN article_states st ON st.article_id = a.id AND st.user_id = s.user_id
+    WHERE s.user_id = "#;
+
+/// The joins behind `ARTICLE_SELECT`, for counting or updating articles; bind the user id
+/// next.
+const ARTICLE_FROM: &str = r#"
+    FROM articles a
+    JOIN feeds f ON a.feed_id = f.id
+    JOIN subscriptions s ON s.feed_id = a.feed_id
+    LEFT JOIN article_states st ON st.article_id = a.id AND st.user_id = s.user_id
+    WHERE s.user_id = "#;
+
+/// Excludes articles the user has a tombstone for; appended to queries over `articles a`
+/// and `subscriptions s`.
+const NOT_HIDDEN: &str = " AND NOT EXISTS (SELECT 1 FROM article_tombstones t WHERE t.user_id = s.user_id AND t.feed_id = a.feed_id AND t.guid = a.guid)";
+
+/// Record a tombstone for the bound user and article, returning it.
+const INSERT_TOMBSTONE: &str = r#"
+    INSERT INTO article_tombstones (user_id, feed_id, guid, title, article_id)
+    SELECT s.user_id, a.feed_id, a.guid, a.title, a.id
+    FROM articles a
+    JOIN subscriptions s ON s.feed_id = a.feed_id
+    WHERE s.user_id = ? AND a.id = ?
+    ON CONFLICT (user_id, feed_id, guid) DO UPDATE SET article_id = excluded.article_id
+    RETURNING id, feed_id, guid, title, article_id, hidden_at
+"#;
+
+/// Delete feeds that lost their last subscriber, with their articles.
+const DELETE_UNSUBSCRIBED_FEEDS: &str =
+    "DELETE FROM feeds WHERE NOT EXISTS (SELECT 1 FROM subscriptions s WHERE s.feed_id = feeds.id)";
+
+/// Highlights of the acting user; bind the user id next.
+const HIGHLIGHT_SELECT: &str = r#"
+    SELECT
+        h.id, h.article_id, a.title as article_title, h.quote, h.prefix, h.suffix,
+        h.start_offset, h.end_offset, h.note, h.color, h.created_at, h.updated_at
+    FROM highlights h
+    JOIN articles a ON a.id = h.article_id
+    JOIN feeds f ON f.id = a.feed_id
+    JOIN subscriptions s ON s.feed_id = a.feed_id AND s.user_id = h.user_id
+    LEFT JOIN article_states st ON st.article_id = a.id AND st.user_id = h.user_id
+    WHERE h.user_id = "#;
+
+const USER_SELECT: &str = "SELECT id, username, is_admin, disabled, password_hash IS NOT NULL AS has_password, created_at FROM users";
+const WEBHOOK_SELECT: &str = "SELECT id, user_id, url, event, feed_id, folder_id, keyword, enabled, secret, created_at FROM webhooks";
+
 const SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS users (
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    username   TEXT NOT NULL UNIQUE,
+    is_admin   INTEGER NOT NULL DEFAULT 0,
+    disabled   INTEGER NOT NULL DEFAULT 0,
+    password_hash TEXT,
+    fever_key_hash TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+INSERT INTO users (id, username, is_admin)
+SELECT 1, 'admin', 1 WHERE NOT EXISTS (SELECT 1 FROM users);
+
+CREATE TABLE IF NOT EXISTS sessions (
+    token_hash TEXT PRIMARY KEY,
+    user_id    INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    expires_at DATETIME NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_sessions_user ON sessions(user_id);
+
+CREATE TABLE IF NOT EXISTS api_tokens (
+    id           INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id      INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name         TEXT NOT NULL,
+    token_hash   TEXT NOT NULL UNIQUE,
+    created_at   DATETIME DEFAULT CURRENT_TIMESTAMP,
+    last_used_at DATETIME
+);
+CREATE INDEX IF NOT EXISTS idx_api_tokens_user ON api_tokens(user_id);
+
 CREATE TABLE IF NOT EXISTS folders (
-    id   INTEGER PRIMARY KEY AUTOINCREMENT,
-    name TEXT NOT NULL UNIQUE
+    id        INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id   INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name      TEXT NOT NULL,
+    parent_id INTEGER REFERENCES folders(id) ON DELETE SET NULL,
+    position  INTEGER NOT NULL DEFAULT 0
 );
 
+-- Feeds are shared by everyone subscribed to them
 CREATE TABLE IF NOT EXISTS feeds (
     id              INTEGER PRIMARY KEY AUTOINCREMENT,
     title           TEXT NOT NULL,
-    folder_id       INTEGER REFERENCES folders(id) ON DELETE SET NULL,
     feed_url        TEXT NOT NULL UNIQUE,
     site_url        TEXT,
     description     TEXT,
@@ -56,6 +294,22 @@ CREATE TABLE IF NOT EXISTS feeds (
     updated_at      DATETIME DEFAULT CURRENT_TIMESTAMP
 );
 
+-- A user's subscription to a feed, with their settings for it
+CREATE TABLE IF NOT EXISTS subscriptions (
+    user_id         INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    feed_id         INTEGER NOT NULL REFERENCES feeds(id) ON DELETE CASCADE,
+    folder_id       INTEGER REFERENCES folders(id) ON DELETE SET NULL,
+    custom_title    TEXT,
+    refresh_interval_minutes INTEGER,
+    fetch_full_content INTEGER NOT NULL DEFAULT 0,
+    notify          INTEGER NOT NULL DEFAULT 1,
+    status          TEXT NOT NULL DEFAULT 'active',
+    position        INTEGER NOT NULL DEFAULT 0,
+    created_at      DATETIME DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (user_id, feed_id)
+);
+CREATE INDEX IF NOT EXISTS idx_subscriptions_feed ON subscriptions(feed_id);
+
 CREATE TABLE IF NOT EXISTS articles (
     id           INTEGER PRIMARY KEY AUTOINCREMENT,
     feed_id      INTEGER NOT NULL REFERENCES feeds(id) ON DELETE CASCADE,
@@ -66,75 +320,582 @@ CREATE TABLE IF NOT EXISTS articles (
     summary      TEXT,
     content      TEXT,
     image_url    TEXT,
+    enclosure_url  TEXT,
+    enclosure_type TEXT,
     published_at DATETIME,
-    is_read      INTEGER DEFAULT 0,
-    is_favorite  INTEGER DEFAULT 0,
     created_at   DATETIME DEFAULT CURRENT_TIMESTAMP,
+    normalized_link TEXT,
+    dedup_group_id  INTEGER,
     UNIQUE(feed_id, guid)
 );
 
 CREATE INDEX IF NOT EXISTS idx_articles_feed_id ON articles(feed_id);
 CREATE INDEX IF NOT EXISTS idx_articles_published ON articles(published_at DESC);
-CREATE INDEX IF NOT EXISTS idx_articles_unread ON articles(feed_id, is_read);
-CREATE INDEX IF NOT EXISTS idx_articles_favorite ON articles(is_favorite) WHERE is_favorite = 1;
 
-CREATE VIRTUAL TABLE IF NOT EXISTS articles_fts USING fts5(
+-- Read and favorite flags per user; articles without a row are unread
+CREATE TABLE IF NOT EXISTS article_states (
+    user_id      INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    article_id   INTEGER NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    is_read      INTEGER NOT NULL DEFAULT 0,
+    read_at      DATETIME,
+    is_favorite  INTEGER NOT NULL DEFAULT 0,
+    favorited_at DATETIME,
+    updated_at   DATETIME,
+    PRIMARY KEY (user_id, article_id)
+);
+CREATE INDEX IF NOT EXISTS idx_article_states_article ON article_states(article_id);
+CREATE INDEX IF NOT EXISTS idx_article_states_read_at ON article_states(user_id, read_at) WHERE read_at IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_article_states_favorite ON article_states(user_id) WHERE is_favorite = 1;
+
+CREATE TABLE IF NOT EXISTS article_categories (
+    article_id INTEGER NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    name       TEXT NOT NULL,
+    PRIMARY KEY (article_id, name)
+);
+
+CREATE TABLE IF NOT EXISTS smart_folders (
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id    INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name       TEXT NOT NULL,
+    query      TEXT NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS labels (
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id    INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name       TEXT NOT NULL,
+    color      TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(user_id, name)
+);
+
+CREATE TABLE IF NOT EXISTS article_labels (
+    article_id INTEGER NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    label_id   INTEGER NOT NULL REFERENCES labels(id) ON DELETE CASCADE,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (article_id, label_id)
+);
+CREATE INDEX IF NOT EXISTS idx_article_labels_label ON article_labels(label_id);
+
+CREATE TABLE IF NOT EXISTS highlights (
+    id           INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id      INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    article_id   INTEGER NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    quote        TEXT NOT NULL,
+    prefix       TEXT,
+    suffix       TEXT,
+    start_offset INTEGER,
+    end_offset   INTEGER,
+    note         TEXT,
+    color        TEXT,
+    created_at   DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at   DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+CREATE INDEX IF NOT EXISTS idx_highlights_article ON highlights(article_id);
+
+-- Hidden and deleted articles, so refreshes do not insert them again
+CREATE TABLE IF NOT EXISTS article_tombstones (
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id    INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    feed_id    INTEGER NOT NULL REFERENCES feeds(id) ON DELETE CASCADE,
+    guid       TEXT NOT NULL,
+    title      TEXT NOT NULL,
+    article_id INTEGER REFERENCES articles(id) ON DELETE SET NULL,
+    hidden_at  DATETIME DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(user_id, feed_id, guid)
+);
+
+CREATE TABLE IF NOT EXISTS feed_fetches (
+    id           INTEGER PRIMARY KEY AUTOINCREMENT,
+    feed_id      INTEGER NOT NULL REFERENCES feeds(id) ON DELETE CASCADE,
+    fetched_at   DATETIME DEFAULT CURRENT_TIMESTAMP,
+    success      INTEGER NOT NULL,
+    status_code  INTEGER,
+    duration_ms  INTEGER NOT NULL,
+    bytes        INTEGER,
+    new_articles INTEGER NOT NULL DEFAULT 0,
+    error        TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_feed_fetches_feed ON feed_fetches(feed_id, id);
+
+CREATE TABLE IF NOT EXISTS webhooks (
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id    INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    url        TEXT NOT NULL,
+    event      TEXT NOT NULL,
+    -- Filters; deleting the feed or folder deletes the webhook rather than widening it
+    feed_id    INTEGER REFERENCES feeds(id) ON DELETE CASCADE,
+    folder_id  INTEGER REFERENCES folders(id) ON DELETE CASCADE,
+    keyword    TEXT,
+    enabled    INTEGER NOT NULL DEFAULT 1,
+    secret     TEXT NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+CREATE INDEX IF NOT EXISTS idx_webhooks_user ON webhooks(user_id);
+
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+    id           INTEGER PRIMARY KEY AUTOINCREMENT,
+    webhook_id   INTEGER NOT NULL REFERENCES webhooks(id) ON DELETE CASCADE,
+    delivery_id  TEXT NOT NULL,
+    event        TEXT NOT NULL,
+    attempt      INTEGER NOT NULL,
+    success      INTEGER NOT NULL,
+    status_code  INTEGER,
+    error        TEXT,
+    duration_ms  INTEGER NOT NULL,
+    delivered_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_webhook ON webhook_deliveries(webhook_id, id);
+
+CREATE TABLE IF NOT EXISTS settings (
+    key   TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+"#;
+
+/// Move aside the tables `MIGRATE_TO_USERS` recreates.
+const RENAME_PRE_USERS_TABLES: &str = r#"
+ALTER TABLE feeds RENAME TO pre_users_feeds;
+ALTER TABLE folders RENAME TO pre_users_folders;
+ALTER TABLE smart_folders RENAME TO pre_users_smart_folders;
+ALTER TABLE labels RENAME TO pre_users_labels;
+ALTER TABLE highlights RENAME TO pre_users_highlights;
+ALTER TABLE article_tombstones RENAME TO pre_users_article_tombstones;
+DROP INDEX IF EXISTS idx_highlights_article;
+"#;
+
+/// Fill the recreated tables, turning feed settings into subscriptions and read and
+/// favorite flags into article states of the default user.
+const MIGRATE_TO_USERS: &str = r#"
+INSERT INTO feeds (id, title, feed_url, site_url, description, language, favicon_url,
+    last_fetched_at, last_build_date, created_at, updated_at)
+SELECT id, title, feed_url, site_url, description, language, favicon_url,
+    last_fetched_at, last_build_date, created_at, updated_at
+FROM pre_users_feeds;
+
+INSERT INTO subscriptions (user_id, feed_id, folder_id, custom_title, refresh_interval_minutes,
+    fetch_full_content, notify, status, position, created_at)
+SELECT 1, id, folder_id, custom_title, refresh_interval_minutes,
+    fetch_full_content, notify, status, position, created_at
+FROM pre_users_feeds;
+
+INSERT INTO folders (id, user_id, name, parent_id, position)
+SELECT id, 1, name, parent_id, position FROM pre_users_folders;
+
+INSERT INTO smart_folders (id, user_id, name, query, created_at)
+SELECT id, 1, name, query, created_at FROM pre_users_smart_folders;
+
+INSERT INTO labels (id, user_id, name, color, created_at)
+SELECT id, 1, name, color, created_at FROM pre_users_labels;
+
+INSERT INTO highlights (id, user_id, article_id, quote, prefix, suffix, start_offset,
+    end_offset, note, color, created_at, updated_at)
+SELECT id, 1, article_id, quote, prefix, suffix, start_offset,
+    end_offset, note, color, created_at, updated_at
+FROM pre_users_highlights;
+
+INSERT INTO article_tombstones (id, user_id, feed_id, guid, title, article_id, hidden_at)
+SELECT id, 1, feed_id, guid, title, article_id, hidden_at FROM pre_users_article_tombstones;
+
+INSERT INTO article_states (user_id, article_id, is_read, read_at, is_favorite, favorited_at)
+SELECT 1, id, COALESCE(is_read, 0), read_at, COALESCE(is_favorite, 0), favorited_at
+FROM articles
+WHERE is_read = 1 OR is_favorite = 1;
+
+DROP TABLE pre_users_feeds;
+DROP TABLE pre_users_folders;
+DROP TABLE pre_users_smart_folders;
+DROP TABLE pre_users_labels;
+DROP TABLE pre_users_highlights;
+DROP TABLE pre_users_article_tombstones;
+
+DROP INDEX IF EXISTS idx_articles_unread;
+DROP INDEX IF EXISTS idx_articles_favorite;
+DROP INDEX IF EXISTS idx_articles_read_at;
+ALTER TABLE articles DROP COLUMN is_read;
+ALTER TABLE articles DROP COLUMN is_favorite;
+ALTER TABLE articles DROP COLUMN read_at;
+ALTER TABLE articles DROP COLUMN favorited_at;
+"#;
+
+/// Move aside the folders table of databases where names were unique per user.
+const RENAME_UNIQUE_NAME_FOLDERS: &str = r#"
+ALTER TABLE folders RENAME TO unique_name_folders;
+"#;
+
+const MIGRATE_UNIQUE_NAME_FOLDERS: &str = r#"
+INSERT INTO folders (id, user_id, name, parent_id, position)
+SELECT id, user_id, name, parent_id, position FROM unique_name_folders;
+
+DROP TABLE unique_name_folders;
+"#;
+
+/// Bumped whenever `FTS_SCHEMA` changes, so existing databases get re-indexed.
+const SEARCH_INDEX_VERSION: &str = "3";
+
+/// Full-text index over articles, rebuilt from scratch when applied.
+///
+/// Porter stemming only helps English, but `unicode61` with diacritics removed
+/// still makes accented European text match its unaccented spelling.
+const FTS_SCHEMA: &str = r#"
+DROP TRIGGER IF EXISTS articles_ai;
+DROP TRIGGER IF EXISTS articles_ad;
+DROP TRIGGER IF EXISTS articles_au;
+DROP TABLE IF EXISTS articles_fts;
+
+CREATE VIRTUAL TABLE articles_fts USING fts5(
     title,
+    summary,
+    author,
     content,
     content=articles,
-    content_rowid=id
+    content_rowid=id,
+    tokenize='porter unicode61 remove_diacritics 2'
 );
 
-CREATE TRIGGER IF NOT EXISTS articles_ai AFTER INSERT ON articles BEGIN
-    INSERT INTO articles_fts(rowid, title, content)
-    VALUES (new.id, new.title, new.content);
+CREATE TRIGGER articles_ai AFTER INSERT ON articles BEGIN
+    INSERT INTO articles_fts(rowid, title, summary, author, content)
+    VALUES (new.id, new.title, new.summary, new.author, new.content);
 END;
 
-CREATE TRIGGER IF NOT EXISTS articles_ad AFTER DELETE ON articles BEGIN
-    INSERT INTO articles_fts(articles_fts, rowid, title, content)
-    VALUES ('delete', old.id, old.title, old.content);
+CREATE TRIGGER articles_ad AFTER DELETE ON articles BEGIN
+    INSERT INTO articles_fts(articles_fts, rowid, title, summary, author, content)
+    VALUES ('delete', old.id, old.title, old.summary, old.author, old.content);
 END;
 
-CREATE TRIGGER IF NOT EXISTS articles_au AFTER UPDATE ON articles BEGIN
-    INSERT INTO articles_fts(articles_fts, rowid, title, content)
-    VALUES ('delete', old.id, old.title, old.content);
-    INSERT INTO articles_fts(rowid, title, content)
-    VALUES (new.id, new.title, new.content);
+CREATE TRIGGER articles_au AFTER UPDATE OF title, summary, author, content ON articles BEGIN
+    INSERT INTO articles_fts(articles_fts, rowid, title, summary, author, content)
+    VALUES ('delete', old.id, old.title, old.summary, old.author, old.content);
+    INSERT INTO articles_fts(rowid, title, summary, author, content)
+    VALUES (new.id, new.title, new.summary, new.author, new.content);
 END;
 
-CREATE TABLE IF NOT EXISTS settings (
-    key   TEXT PRIMARY KEY,
-    value TEXT NOT NULL
-);
+INSERT INTO articles_fts(articles_fts) VALUES ('rebuild');
+"#;
+
+/// Columns added after the first release, as `(table, column, definition)`.
+const COLUMN_MIGRATIONS: &[(&str, &str, &str)] = &[
+    ("articles", "normalized_link", "TEXT"),
+    ("articles", "dedup_group_id", "INTEGER"),
+    ("articles", "enclosure_url", "TEXT"),
+    ("articles", "enclosure_type", "TEXT"),
+    (
+        "folders",
+        "parent_id",
+        "INTEGER REFERENCES folders(id) ON DELETE SET NULL",
+    ),
+    ("folders", "position", "INTEGER NOT NULL DEFAULT 0"),
+    ("users", "password_hash", "TEXT"),
+    ("users", "fever_key_hash", "TEXT"),
+    ("article_states", "updated_at", "DATETIME"),
+];
+
+/// Columns that moved to subscriptions and article states, added to databases from
+/// before multi-user support that predate them so that `MIGRATE_TO_USERS` finds them.
+const PRE_USERS_COLUMN_MIGRATIONS: &[(&str, &str, &str)] = &[
+    ("articles", "read_at", "DATETIME"),
+    ("articles", "favorited_at", "DATETIME"),
+    ("feeds", "custom_title", "TEXT"),
+    ("feeds", "refresh_interval_minutes", "INTEGER"),
+    ("feeds", "fetch_full_content", "INTEGER NOT NULL DEFAULT 0"),
+    ("feeds", "notify", "INTEGER NOT NULL DEFAULT 1"),
+    ("feeds", "status", "TEXT NOT NULL DEFAULT 'active'"),
+    ("feeds", "position", "INTEGER NOT NULL DEFAULT 0"),
+];
+
+/// Schema objects that depend on migrated columns.
+const POST_MIGRATION_SCHEMA: &str = r#"
+CREATE INDEX IF NOT EXISTS idx_articles_normalized_link ON articles(normalized_link);
+CREATE INDEX IF NOT EXISTS idx_articles_guid ON articles(guid);
+CREATE INDEX IF NOT EXISTS idx_articles_dedup_group ON articles(dedup_group_id);
+CREATE INDEX IF NOT EXISTS idx_highlights_user ON highlights(user_id, created_at);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_users_fever_key ON users(fever_key_hash);
+-- Folder names only need to differ among siblings
+CREATE UNIQUE INDEX IF NOT EXISTS idx_folders_user_parent_name
+    ON folders(user_id, COALESCE(parent_id, 0), name);
+
+-- Record when an article's read or starred state last changed, for clients that sync
+-- changes since their last visit
+CREATE TRIGGER IF NOT EXISTS article_states_ai AFTER INSERT ON article_states BEGIN
+    UPDATE article_states SET updated_at = CURRENT_TIMESTAMP
+    WHERE user_id = new.user_id AND article_id = new.article_id;
+END;
+CREATE TRIGGER IF NOT EXISTS article_states_au AFTER UPDATE OF is_read, is_favorite ON article_states BEGIN
+    UPDATE article_states SET updated_at = CURRENT_TIMESTAMP
+    WHERE user_id = new.user_id AND article_id = new.article_id;
+END;
 "#;
 
 #[async_trait]
 impl Database for SqliteDatabase {
+    fn for_user(&self, user_id: i64) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            user_id,
+        }
+    }
+
+    fn user_id(&self) -> i64 {
+        self.user_id
+    }
+
+    async fn get_users(&self) -> DbResult<Vec<User>> {
+        let users = sqlx::query_as::<_, UserRow>(&format!(
+            "{} ORDER BY username COLLATE NOCASE",
+            USER_SELECT
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(users.into_iter().map(|u| u.into()).collect())
+    }
+
+    async fn get_user(&self, id: i64) -> DbResult<Option<User>> {
+        let user = sqlx::query_as::<_, UserRow>(&format!("{} WHERE id = ?", USER_SELECT))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(user.map(|u| u.into()))
+    }
+
+    async fn get_user_by_name(&self, username: &str) -> DbResult<Option<User>> {
+        let user = sqlx::query_as::<_, UserRow>(&format!("{} WHERE username = ?", USER_SELECT))
+            .bind(username)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(user.map(|u| u.into()))
+    }
+
+    async fn create_user(&self, user: &NewUser, password_hash: Option<&str>) -> DbResult<User> {
+        let row = sqlx::query_as::<_, UserRow>(
+            "INSERT INTO users (username, is_admin, password_hash) VALUES (?, ?, ?) RETURNING id, username, is_admin, disabled, password_hash IS NOT NULL AS has_password, created_at",
+        )
+        .bind(&user.username)
+        .bind(user.is_admin)
+        .bind(password_hash)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(row.into())
+    }
+
+    async fn set_user_disabled(&self, id: i64, disabled: bool) -> DbResult<()> {
+        let result = sqlx::query("UPDATE users SET disabled = ? WHERE id = ?")
+            .bind(disabled)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound(format!("user {}", id)));
+        }
+        Ok(())
+    }
+
+    async fn delete_user(&self, id: i64) -> DbResult<()> {
+        let mut tx = self.pool.begin().await?;
+        let result = sqlx::query("DELETE FROM users WHERE id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound(format!("user {}", id)));
+        }
+        sqlx::query(DELETE_UNSUBSCRIBED_FEEDS)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_password_hash(&self, id: i64) -> DbResult<Option<String>> {
+        let hash =
+            sqlx::query_scalar::<_, Option<String>>("SELECT password_hash FROM users WHERE id = ?")
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(hash.flatten())
+    }
+
+    async fn set_password_hash(&self, id: i64, password_hash: Option<&str>) -> DbResult<()> {
+        let mut tx = self.pool.begin().await?;
+        let result = sqlx::query("UPDATE users SET password_hash = ? WHERE id = ?")
+            .bind(password_hash)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound(format!("user {}", id)));
+        }
+        sqlx::query("DELETE FROM sessions WHERE user_id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn create_session(
+        &self,
+        user_id: i64,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> DbResult<()> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM sessions WHERE expires_at <= ?")
+            .bind(Utc::now())
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("INSERT INTO sessions (token_hash, user_id, expires_at) VALUES (?, ?, ?)")
+            .bind(token_hash)
+            .bind(user_id)
+            .bind(expires_at)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_session_user(&self, token_hash: &str) -> DbResult<Option<User>> {
+        let user = sqlx::query_as::<_, UserRow>(&format!(
+            "{} WHERE id = (SELECT user_id FROM sessions WHERE token_hash = ? AND expires_at > ?)",
+            USER_SELECT
+        ))
+        .bind(token_hash)
+        .bind(Utc::now())
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(user.map(|u| u.into()))
+    }
+
+    async fn delete_session(&self, token_hash: &str) -> DbResult<()> {
+        sqlx::query("DELETE FROM sessions WHERE token_hash = ?")
+            .bind(token_hash)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_api_tokens(&self) -> DbResult<Vec<ApiToken>> {
+        let tokens = sqlx::query_as::<_, ApiTokenRow>(
+            "SELECT id, name, created_at, last_used_at FROM api_tokens WHERE user_id = ? ORDER BY id",
+        )
+        .bind(self.user_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(tokens.into_iter().map(|t| t.into()).collect())
+    }
+
+    async fn create_api_token(&self, name: &str, token_hash: &str) -> DbResult<ApiToken> {
+        let token = sqlx::query_as::<_, ApiTokenRow>(
+            "INSERT INTO api_tokens (user_id, name, token_hash) VALUES (?, ?, ?) RETURNING id, name, created_at, last_used_at",
+        )
+        .bind(self.user_id)
+        .bind(name)
+        .bind(token_hash)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(token.into())
+    }
+
+    async fn delete_api_token(&self, id: i64) -> DbResult<()> {
+        let result = sqlx::query("DELETE FROM api_tokens WHERE id = ? AND user_id = ?")
+            .bind(id)
+            .bind(self.user_id)
+            .execute(&self.pool)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound(format!("API token {}", id)));
+        }
+        Ok(())
+    }
+
+    async fn get_token_user(&self, token_hash: &str) -> DbResult<Option<User>> {
+        let user_id = sqlx::query_scalar::<_, i64>(
+            "UPDATE api_tokens SET last_used_at = ? WHERE token_hash = ? RETURNING user_id",
+        )
+        .bind(Utc::now())
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+        match user_id {
+            Some(id) => self.get_user(id).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn set_fever_key_hash(&self, key_hash: Option<&str>) -> DbResult<()> {
+        let result = sqlx::query("UPDATE users SET fever_key_hash = ? WHERE id = ?")
+            .bind(key_hash)
+            .bind(self.user_id)
+            .execute(&self.pool)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound(format!("user {}", self.user_id)));
+        }
+        Ok(())
+    }
+
+    async fn get_fever_user(&self, key_hash: &str) -> DbResult<Option<User>> {
+        let user =
+            sqlx::query_as::<_, UserRow>(&format!("{} WHERE fever_key_hash = ?", USER_SELECT))
+                .bind(key_hash)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(user.map(|u| u.into()))
+    }
+
     async fn insert_feed(&self, feed: &NewFeed) -> DbResult<i64> {
-        let result = sqlx::query(
+        let mut tx = self.pool.begin().await?;
+
+        // Another user may already subscribe to the same URL
+        let feed_id = sqlx::query_scalar::<_, i64>(
             r#"
-            INSERT INTO feeds (title, folder_id, feed_url, site_url, description, language, favicon_url, last_build_date)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO feeds (title, feed_url, site_url, description, language, favicon_url, last_build_date)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (feed_url) DO UPDATE SET feed_url = excluded.feed_url
+            RETURNING id
             "#,
         )
         .bind(&feed.title)
-        .bind(feed.folder_id)
         .bind(&feed.feed_url)
         .bind(&feed.site_url)
         .bind(&feed.description)
         .bind(&feed.language)
         .bind(&feed.favicon_url)
         .bind(feed.last_build_date)
-        .execute(&self.pool)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO subscriptions (user_id, feed_id, folder_id, position)
+            VALUES (?1, ?2, ?3,
+                (SELECT COALESCE(MAX(position) + 1, 0) FROM subscriptions WHERE user_id = ?1 AND folder_id IS ?3))
+            "#,
+        )
+        .bind(self.user_id)
+        .bind(feed_id)
+        .bind(feed.folder_id)
+        .execute(&mut *tx)
         .await?;
 
-        Ok(result.last_insert_rowid())
+        tx.commit().await?;
+        Ok(feed_id)
     }
 
     async fn get_feed(&self, id: i64) -> DbResult<Option<Feed>> {
         let feed = sqlx::query_as::<_, FeedRow>(
-            "SELECT id, title, folder_id, feed_url, site_url, description, language, favicon_url, last_fetched_at, last_build_date, created_at, updated_at FROM feeds WHERE id = ?",
+            r#"
+            SELECT
+                f.id, COALESCE(s.custom_title, f.title) as title, s.folder_id, f.feed_url,
+                f.site_url, f.description, f.language, f.favicon_url, f.last_fetched_at,
+                f.last_build_date, f.created_at, f.updated_at, s.custom_title,
+                s.refresh_interval_minutes, s.fetch_full_content, s.notify, s.status
+            FROM feeds f
+            JOIN subscriptions s ON s.feed_id = f.id
+            WHERE s.user_id = ? AND f.id = ?
+            "#,
         )
+        .bind(self.user_id)
         .bind(id)
         .fetch_optional(&self.pool)
         .await?;
@@ -146,14 +907,23 @@ impl Database for SqliteDatabase {
         let feeds = sqlx::query_as::<_, FeedWithMetaRow>(
             r#"
             SELECT
-                f.id, f.title, f.folder_id, f.feed_url, f.site_url, f.description,
-                f.language, f.favicon_url, f.last_fetched_at, f.last_build_date,
-                f.created_at, f.updated_at,
-                (SELECT COUNT(*) FROM articles a WHERE a.feed_id = f.id AND a.is_read = 0) as unread_count
+                f.id, COALESCE(s.custom_title, f.title) as title, s.folder_id, f.feed_url,
+                f.site_url, f.description, f.language, f.favicon_url, f.last_fetched_at,
+                f.last_build_date, f.created_at, f.updated_at, s.custom_title,
+                s.refresh_interval_minutes, s.fetch_full_content, s.notify, s.status, s.position,
+                CASE WHEN s.status = 'muted' THEN 0 ELSE
+                    (SELECT COUNT(*) FROM articles a
+                        LEFT JOIN article_states st ON st.article_id = a.id AND st.user_id = s.user_id
+                        WHERE a.feed_id = f.id AND COALESCE(st.is_read, 0) = 0
+                        AND NOT EXISTS (SELECT 1 FROM article_tombstones t WHERE t.user_id = s.user_id AND t.feed_id = a.feed_id AND t.guid = a.guid))
+                END as unread_count
             FROM feeds f
-            ORDER BY f.title COLLATE NOCASE
+            JOIN subscriptions s ON s.feed_id = f.id
+            WHERE s.user_id = ?
+            ORDER BY s.position, COALESCE(s.custom_title, f.title) COLLATE NOCASE
             "#,
         )
+        .bind(self.user_id)
         .fetch_all(&self.pool)
         .await?;
 
@@ -161,10 +931,96 @@ impl Database for SqliteDatabase {
     }
 
     async fn delete_feed(&self, id: i64) -> DbResult<()> {
-        sqlx::query("DELETE FROM feeds WHERE id = ?")
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM subscriptions WHERE user_id = ? AND feed_id = ?")
+            .bind(self.user_id)
             .bind(id)
-            .execute(&self.pool)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(DELETE_UNSUBSCRIBED_FEEDS)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn update_feed(&self, id: i64, update: &FeedUpdate) -> DbResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        let subscribed = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM subscriptions WHERE user_id = ? AND feed_id = ?",
+        )
+        .bind(self.user_id)
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?
+            > 0;
+        if !subscribed {
+            return Err(DbError::NotFound(format!("feed {}", id)));
+        }
+
+        if update.feed_url.is_some() || update.site_url.is_some() {
+            let subscribers = sqlx::query_scalar::<_, i64>(
+                "SELECT COUNT(*) FROM subscriptions WHERE feed_id = ?",
+            )
+            .bind(id)
+            .fetch_one(&mut *tx)
             .await?;
+            if subscribers > 1 {
+                return Err(DbError::InvalidInput(
+                    "the feed is shared with other users; subscribe to the new URL instead"
+                        .to_string(),
+                ));
+            }
+        }
+
+        let mut qb: QueryBuilder<Sqlite> =
+            QueryBuilder::new("UPDATE feeds SET updated_at = CURRENT_TIMESTAMP");
+        if let Some(feed_url) = &update.feed_url {
+            qb.push(", feed_url = ");
+            qb.push_bind(feed_url);
+        }
+        if let Some(site_url) = &update.site_url {
+            qb.push(", site_url = NULLIF(");
+            qb.push_bind(site_url);
+            qb.push(", '')");
+        }
+        qb.push(" WHERE id = ");
+        qb.push_bind(id);
+        qb.build().execute(&mut *tx).await?;
+
+        // The no-op assignment keeps the statement valid when only the feed's URLs change
+        let mut qb: QueryBuilder<Sqlite> =
+            QueryBuilder::new("UPDATE subscriptions SET user_id = user_id");
+        if let Some(title) = &update.title {
+            qb.push(", custom_title = NULLIF(");
+            qb.push_bind(title);
+            qb.push(", '')");
+        }
+        if let Some(minutes) = update.refresh_interval_minutes {
+            qb.push(", refresh_interval_minutes = NULLIF(");
+            qb.push_bind(minutes);
+            qb.push(", 0)");
+        }
+        if let Some(fetch_full_content) = update.fetch_full_content {
+            qb.push(", fetch_full_content = ");
+            qb.push_bind(fetch_full_content);
+        }
+        if let Some(notify) = update.notify {
+            qb.push(", notify = ");
+            qb.push_bind(notify);
+        }
+        if let Some(status) = update.status {
+            qb.push(", status = ");
+            qb.push_bind(status.as_str());
+        }
+        qb.push(" WHERE user_id = ");
+        qb.push_bind(self.user_id);
+        qb.push(" AND feed_id = ");
+        qb.push_bind(id);
+        qb.build().execute(&mut *tx).await?;
+
+        tx.commit().await?;
         Ok(())
     }
 
@@ -190,18 +1046,27 @@ impl Database for SqliteDatabase {
     }
 
     async fn get_feed_url(&self, id: i64) -> DbResult<Option<String>> {
-        let result = sqlx::query_scalar::<_, String>("SELECT feed_url FROM feeds WHERE id = ?")
-            .bind(id)
-            .fetch_optional(&self.pool)
-            .await?;
+        let result = sqlx::query_scalar::<_, String>(
+            "SELECT f.feed_url FROM feeds f JOIN subscriptions s ON s.feed_id = f.id WHERE s.user_id = ? AND f.id = ?",
+        )
+        .bind(self.user_id)
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
         Ok(result)
     }
 
     async fn insert_article(&self, article: &NewArticle) -> DbResult<InsertResult> {
         let result = sqlx::query(
             r#"
-            INSERT OR IGNORE INTO articles (feed_id, guid, title, link, author, summary, content, image_url, published_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT OR IGNORE INTO articles (feed_id, guid, title, link, author, summary, content, image_url, enclosure_url, enclosure_type, published_at, normalized_link)
+            SELECT ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?
+            WHERE NOT EXISTS (SELECT 1 FROM article_tombstones WHERE feed_id = ? AND guid = ?)
+               OR EXISTS (
+                   SELECT 1 FROM subscriptions s
+                   WHERE s.feed_id = ? AND NOT EXISTS (
+                       SELECT 1 FROM article_tombstones t
+                       WHERE t.user_id = s.user_id AND t.feed_id = s.feed_id AND t.guid = ?))
             "#,
         )
         .bind(article.feed_id)
@@ -212,29 +1077,32 @@ impl Database for SqliteDatabase {
         .bind(&article.summary)
         .bind(&article.content)
         .bind(&article.image_url)
+        .bind(&article.enclosure_url)
+        .bind(&article.enclosure_type)
         .bind(article.published_at)
+        .bind(&article.normalized_link)
+        .bind(article.feed_id)
+        .bind(&article.guid)
+        .bind(article.feed_id)
+        .bind(&article.guid)
         .execute(&self.pool)
         .await?;
 
         if result.rows_affected() > 0 {
-            Ok(InsertResult::Inserted(result.last_insert_rowid()))
+            let id = result.last_insert_rowid();
+            self.insert_categories(id, &article.categories).await?;
+            Ok(InsertResult::Inserted(id))
         } else {
             Ok(InsertResult::Ignored)
         }
     }
 
     async fn get_article(&self, id: i64) -> DbResult<Option<Article>> {
-        let article = sqlx::query_as::<_, ArticleRow>(
-            r#"
-            SELECT
-                a.id, a.feed_id, a.guid, a.title, a.link, a.author, a.summary, a.content,
-                a.image_url, a.published_at, a.is_read, a.is_favorite, a.created_at,
-                f.title as feed_title, f.favicon_url as feed_favicon_url
-            FROM articles a
-            JOIN feeds f ON a.feed_id = f.id
-            WHERE a.id = ?
-            "#,
-        )
+        let article = sqlx::query_as::<_, ArticleRow>(&format!(
+            "{}? AND a.id = ?{}",
+            ARTICLE_SELECT, NOT_HIDDEN
+        ))
+        .bind(self.user_id)
         .bind(id)
         .fetch_optional(&self.pool)
         .await?;
@@ -243,32 +1111,11 @@ impl Database for SqliteDatabase {
     }
 
     async fn get_articles(&self, query: &ArticleQuery) -> DbResult<Vec<Article>> {
-        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new(
-            r#"
-            SELECT
-                a.id, a.feed_id, a.guid, a.title, a.link, a.author, a.summary, a.content,
-                a.image_url, a.published_at, a.is_read, a.is_favorite, a.created_at,
-                f.title as feed_title, f.favicon_url as feed_favicon_url
-            FROM articles a
-            JOIN feeds f ON a.feed_id = f.id
-            WHERE 1=1
-            "#,
-        );
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new(ARTICLE_SELECT);
+        qb.push_bind(self.user_id);
+        push_article_filters(&mut qb, query, self.user_id);
 
-        if let Some(feed_id) = query.feed_id {
-            qb.push(" AND a.feed_id = ");
-            qb.push_bind(feed_id);
-        }
-
-        if query.unread_only {
-            qb.push(" AND a.is_read = 0");
-        }
-
-        if query.favorites_only {
-            qb.push(" AND a.is_favorite = 1");
-        }
-
-        qb.push(" ORDER BY a.published_at DESC NULLS LAST, a.created_at DESC");
+        qb.push(article_order(query));
         qb.push(" LIMIT ");
         qb.push_bind(query.limit);
         qb.push(" OFFSET ");
@@ -283,161 +1130,1490 @@ impl Database for SqliteDatabase {
     }
 
     async fn toggle_read(&self, id: i64) -> DbResult<()> {
+        let Some(is_read) = sqlx::query_scalar::<_, i32>(&format!(
+            "SELECT COALESCE(st.is_read, 0) {}? AND a.id = ?",
+            ARTICLE_FROM
+        ))
+        .bind(self.user_id)
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?
+        else {
+            return Ok(());
+        };
+        self.set_read(id, is_read == 0).await
+    }
+
+    async fn set_read(&self, id: i64, read: bool) -> DbResult<()> {
+        // Apply the new state to every copy in the article's duplicate group
         sqlx::query(
-            "UPDATE articles SET is_read = CASE WHEN is_read = 0 THEN 1 ELSE 0 END WHERE id = ?",
+            r#"
+            INSERT INTO article_states (user_id, article_id, is_read, read_at)
+            SELECT s.user_id, a.id, ?1, CASE WHEN ?1 = 1 THEN CURRENT_TIMESTAMP END
+            FROM articles a
+            JOIN subscriptions s ON s.feed_id = a.feed_id
+            WHERE s.user_id = ?2
+              AND (a.id = ?3 OR a.dedup_group_id = (SELECT dedup_group_id FROM articles WHERE id = ?3))
+            ON CONFLICT (user_id, article_id) DO UPDATE
+            SET is_read = excluded.is_read,
+                read_at = CASE WHEN excluded.is_read = 1
+                    THEN COALESCE(article_states.read_at, excluded.read_at) END
+            "#,
         )
+        .bind(read)
+        .bind(self.user_id)
         .bind(id)
         .execute(&self.pool)
         .await?;
         Ok(())
     }
 
-    async fn mark_all_read(&self, feed_id: Option<i64>) -> DbResult<()> {
-        if let Some(fid) = feed_id {
-            sqlx::query("UPDATE articles SET is_read = 1 WHERE feed_id = ?")
-                .bind(fid)
+    async fn mark_all_read(&self, scope: ArticleScope) -> DbResult<()> {
+        if let Some(query) = scope.query() {
+            return self.mark_articles_read(&query).await;
+        }
+        sqlx::query(
+            r#"
+            INSERT INTO article_states (user_id, article_id, is_read, read_at)
+            SELECT s.user_id, a.id, 1, CURRENT_TIMESTAMP
+            FROM articles a
+            JOIN subscriptions s ON s.feed_id = a.feed_id
+            WHERE s.user_id = ?
+            ON CONFLICT (user_id, article_id) DO UPDATE
+            SET is_read = 1, read_at = COALESCE(article_states.read_at, excluded.read_at)
+            "#,
+        )
+        .bind(self.user_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn mark_all_unread(&self, scope: ArticleScope) -> DbResult<()> {
+        let Some(query) = scope.query() else {
+            sqlx::query("UPDATE article_states SET is_read = 0, read_at = NULL WHERE user_id = ?")
+                .bind(self.user_id)
                 .execute(&self.pool)
                 .await?;
+            return Ok(());
+        };
+        let mut qb: QueryBuilder<Sqlite> =
+            QueryBuilder::new("WITH matching AS (SELECT a.id, a.dedup_group_id ");
+        qb.push(ARTICLE_FROM);
+        qb.push_bind(self.user_id);
+        push_article_filters(&mut qb, &query, self.user_id);
+        qb.push(
+            r#")
+            UPDATE article_states SET is_read = 0, read_at = NULL
+            WHERE user_id = "#,
+        );
+        qb.push_bind(self.user_id);
+        qb.push(
+            r#"
+              AND article_id IN (
+                SELECT id FROM articles
+                WHERE id IN (SELECT id FROM matching)
+                   OR dedup_group_id IN (SELECT dedup_group_id FROM matching))
+            "#,
+        );
+
+        qb.build().execute(&self.pool).await?;
+        Ok(())
+    }
+
+    async fn count_articles(&self, query: &ArticleQuery) -> DbResult<i64> {
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT COUNT(*) ");
+        qb.push(ARTICLE_FROM);
+        qb.push_bind(self.user_id);
+        push_article_filters(&mut qb, query, self.user_id);
+
+        let count = qb.build_query_scalar::<i64>().fetch_one(&self.pool).await?;
+        Ok(count)
+    }
+
+    async fn get_article_ids(&self, query: &ArticleQuery) -> DbResult<Vec<i64>> {
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT a.id ");
+        qb.push(ARTICLE_FROM);
+        qb.push_bind(self.user_id);
+        push_article_filters(&mut qb, query, self.user_id);
+        qb.push(article_order(query));
+
+        let ids = qb.build_query_scalar::<i64>().fetch_all(&self.pool).await?;
+        Ok(ids)
+    }
+
+    async fn hide_article(&self, id: i64) -> DbResult<HiddenArticle> {
+        let hidden = sqlx::query_as::<_, HiddenArticleRow>(INSERT_TOMBSTONE)
+            .bind(self.user_id)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("article {}", id)))?;
+        Ok(hidden.into())
+    }
+
+    async fn delete_article(&self, id: i64) -> DbResult<HiddenArticle> {
+        let mut tx = self.pool.begin().await?;
+
+        let hidden = sqlx::query_as::<_, HiddenArticleRow>(INSERT_TOMBSTONE)
+            .bind(self.user_id)
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("article {}", id)))?;
+        sqlx::query("UPDATE article_tombstones SET article_id = NULL WHERE id = ?")
+            .bind(hidden.id)
+            .execute(&mut *tx)
+            .await?;
+        // Other subscribers may still see the article
+        sqlx::query(
+            r#"
+            DELETE FROM articles
+            WHERE id = ? AND NOT EXISTS (
+                SELECT 1 FROM subscriptions s
+                WHERE s.feed_id = articles.feed_id AND NOT EXISTS (
+                    SELECT 1 FROM article_tombstones t
+                    WHERE t.user_id = s.user_id AND t.feed_id = articles.feed_id AND t.guid = articles.guid))
+            "#,
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(HiddenArticle {
+            article_id: None,
+            ..hidden.into()
+        })
+    }
+
+    async fn get_hidden_articles(&self, feed_id: Option<i64>) -> DbResult<Vec<HiddenArticle>> {
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new(
+            "SELECT id, feed_id, guid, title, article_id, hidden_at FROM article_tombstones WHERE user_id = ",
+        );
+        qb.push_bind(self.user_id);
+        if let Some(feed_id) = feed_id {
+            qb.push(" AND feed_id = ");
+            qb.push_bind(feed_id);
+        }
+        qb.push(" ORDER BY hidden_at DESC, id DESC");
+
+        let hidden = qb
+            .build_query_as::<HiddenArticleRow>()
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(hidden.into_iter().map(|h| h.into()).collect())
+    }
+
+    async fn unhide_article(&self, hidden_id: i64) -> DbResult<()> {
+        let result = sqlx::query("DELETE FROM article_tombstones WHERE id = ? AND user_id = ?")
+            .bind(hidden_id)
+            .bind(self.user_id)
+            .execute(&self.pool)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound(format!("hidden article {}", hidden_id)));
+        }
+        Ok(())
+    }
+
+    async fn mark_articles_read(&self, query: &ArticleQuery) -> DbResult<()> {
+        let mut qb: QueryBuilder<Sqlite> =
+            QueryBuilder::new("WITH matching AS (SELECT a.id, a.dedup_group_id ");
+        qb.push(ARTICLE_FROM);
+        qb.push_bind(self.user_id);
+        push_article_filters(&mut qb, query, self.user_id);
+        qb.push(
+            r#")
+            INSERT INTO article_states (user_id, article_id, is_read, read_at)
+            SELECT s.user_id, a.id, 1, CURRENT_TIMESTAMP
+            FROM articles a
+            JOIN subscriptions s ON s.feed_id = a.feed_id
+            WHERE s.user_id = "#,
+        );
+        qb.push_bind(self.user_id);
+        qb.push(
+            r#"
+              AND (a.id IN (SELECT id FROM matching)
+                   OR a.dedup_group_id IN (SELECT dedup_group_id FROM matching))
+            ON CONFLICT (user_id, article_id) DO UPDATE
+            SET is_read = 1, read_at = COALESCE(article_states.read_at, excluded.read_at)
+            "#,
+        );
+
+        qb.build().execute(&self.pool).await?;
+        Ok(())
+    }
+
+    async fn toggle_favorite(&self, id: i64) -> DbResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO article_states (user_id, article_id, is_favorite, favorited_at)
+            SELECT s.user_id, a.id, 1, CURRENT_TIMESTAMP
+            FROM articles a
+            JOIN subscriptions s ON s.feed_id = a.feed_id
+            WHERE s.user_id = ? AND a.id = ?
+            ON CONFLICT (user_id, article_id) DO UPDATE
+            SET is_favorite = 1 - article_states.is_favorite,
+                favorited_at = CASE WHEN article_states.is_favorite = 0 THEN CURRENT_TIMESTAMP END
+            "#,
+        )
+        .bind(self.user_id)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn set_favorite(&self, id: i64, favorite: bool) -> DbResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO article_states (user_id, article_id, is_favorite, favorited_at)
+            SELECT s.user_id, a.id, ?1, CASE WHEN ?1 = 1 THEN CURRENT_TIMESTAMP END
+            FROM articles a
+            JOIN subscriptions s ON s.feed_id = a.feed_id
+            WHERE s.user_id = ?2 AND a.id = ?3
+            ON CONFLICT (user_id, article_id) DO UPDATE
+            SET is_favorite = excluded.is_favorite,
+                favorited_at = CASE WHEN excluded.is_favorite = 1
+                    THEN COALESCE(article_states.favorited_at, excluded.favorited_at) END
+            "#,
+        )
+        .bind(favorite)
+        .bind(self.user_id)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_favorites_count(&self) -> DbResult<i64> {
+        let count = sqlx::query_scalar::<_, i64>(&format!(
+            "SELECT COUNT(*) {}? AND st.is_favorite = 1{}",
+            ARTICLE_FROM, NOT_HIDDEN
+        ))
+        .bind(self.user_id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(count)
+    }
+
+    async fn reindex_search(&self) -> DbResult<()> {
+        sqlx::query("INSERT INTO articles_fts(articles_fts) VALUES ('rebuild')")
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn search_articles(
+        &self,
+        query: &SearchQuery,
+        limit: i64,
+        offset: i64,
+    ) -> DbResult<Vec<SearchHit>> {
+        let fts_match = query.fts5_match();
+
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("");
+        match &fts_match {
+            Some(_) => qb.push(
+                r#"
+                SELECT s.*,
+                    -bm25(articles_fts, 10.0, 4.0, 2.0, 1.0) AS score,
+                    snippet(articles_fts, -1, char(2), char(3), '…', 24) AS snippet
+                FROM articles_fts
+                JOIN ("#,
+            ),
+            None => qb.push("SELECT s.*, 0.0 AS score, NULL AS snippet FROM ("),
+        };
+        qb.push(ARTICLE_SELECT);
+        qb.push_bind(self.user_id);
+        qb.push(NOT_HIDDEN);
+        push_search_filters(&mut qb, query);
+        qb.push(") s");
+
+        if let Some(expr) = fts_match {
+            qb.push(" ON s.id = articles_fts.rowid WHERE articles_fts MATCH ");
+            qb.push_bind(expr);
+            qb.push(" ORDER BY score DESC, s.id DESC");
         } else {
-            sqlx::query("UPDATE articles SET is_read = 1")
-                .execute(&self.pool)
-                .await?;
+            qb.push(" ORDER BY s.published_at DESC NULLS LAST, s.created_at DESC");
+        }
+        qb.push(" LIMIT ");
+        qb.push_bind(limit);
+        qb.push(" OFFSET ");
+        qb.push_bind(offset);
+
+        let hits = qb
+            .build_query_as::<SearchHitRow>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(hits.into_iter().map(|h| h.into()).collect())
+    }
+
+    async fn update_article_content(&self, id: i64, content: &str) -> DbResult<()> {
+        sqlx::query("UPDATE articles SET content = ? WHERE id = ?")
+            .bind(content)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_article_link(&self, id: i64) -> DbResult<Option<String>> {
+        let result = sqlx::query_scalar::<_, Option<String>>(&format!(
+            "SELECT a.link {}? AND a.id = ?",
+            ARTICLE_FROM
+        ))
+        .bind(self.user_id)
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?
+        .flatten();
+        Ok(result)
+    }
+
+    async fn get_feed_guids(&self, feed_id: i64) -> DbResult<Vec<(i64, String)>> {
+        let guids = sqlx::query_as::<_, (i64, String)>(&format!(
+            "SELECT a.id, a.guid {}? AND a.feed_id = ?{} ORDER BY a.id",
+            ARTICLE_FROM, NOT_HIDDEN
+        ))
+        .bind(self.user_id)
+        .bind(feed_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(guids)
+    }
+
+    async fn find_duplicate_candidates(
+        &self,
+        article: &NewArticle,
+    ) -> DbResult<Vec<DuplicateCandidate>> {
+        // Same link or permalink GUID, however many there are
+        let permalink_guid = dedup::is_permalink_guid(&article.guid).then_some(&article.guid);
+        let mut candidates = sqlx::query_as::<_, DuplicateCandidateRow>(
+            r#"
+            SELECT id, feed_id, guid, title, normalized_link, dedup_group_id as group_id
+            FROM articles
+            WHERE feed_id != ?
+              AND ((normalized_link IS NOT NULL AND normalized_link = ?) OR guid = ?)
+            ORDER BY id
+            "#,
+        )
+        .bind(article.feed_id)
+        .bind(&article.normalized_link)
+        .bind(permalink_guid)
+        .fetch_all(&self.pool)
+        .await?;
+
+        // Then the most recent articles around the same time, for title matches
+        if let Some(published_at) = article.published_at {
+            let nearby = sqlx::query_as::<_, DuplicateCandidateRow>(
+                r#"
+                SELECT id, feed_id, guid, title, normalized_link, dedup_group_id as group_id
+                FROM articles
+                WHERE feed_id != ? AND published_at BETWEEN ? AND ?
+                ORDER BY published_at DESC
+                LIMIT ?
+                "#,
+            )
+            .bind(article.feed_id)
+            .bind(published_at - DUPLICATE_WINDOW)
+            .bind(published_at + DUPLICATE_WINDOW)
+            .bind(MAX_TITLE_CANDIDATES)
+            .fetch_all(&self.pool)
+            .await?;
+            let exact: HashSet<_> = candidates.iter().map(|c| c.id).collect();
+            candidates.extend(nearby.into_iter().filter(|c| !exact.contains(&c.id)));
+        }
+
+        Ok(candidates.into_iter().map(|c| c.into()).collect())
+    }
+
+    async fn set_duplicate_group(&self, article_id: i64, group_id: i64) -> DbResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            "UPDATE articles SET dedup_group_id = id WHERE id = ? AND dedup_group_id IS NULL",
+        )
+        .bind(group_id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("UPDATE articles SET dedup_group_id = ? WHERE id = ?")
+            .bind(group_id)
+            .bind(article_id)
+            .execute(&mut *tx)
+            .await?;
+
+        // A new copy of a story starts out read for everyone who already read it
+        sqlx::query(
+            r#"
+            INSERT INTO article_states (user_id, article_id, is_read, read_at)
+            SELECT st.user_id, ?2, 1, MAX(st.read_at)
+            FROM article_states st
+            JOIN articles a ON a.id = st.article_id
+            WHERE a.dedup_group_id = ?1 AND a.id <> ?2 AND st.is_read = 1
+            GROUP BY st.user_id
+            ON CONFLICT (user_id, article_id) DO UPDATE SET is_read = 1, read_at = excluded.read_at
+            "#,
+        )
+        .bind(group_id)
+        .bind(article_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_duplicate_sources(&self, group_ids: &[i64]) -> DbResult<Vec<ArticleSource>> {
+        if group_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new(
+            r#"
+            SELECT a.dedup_group_id as group_id, a.id as article_id, a.feed_id,
+                   COALESCE(s.custom_title, f.title) as feed_title, a.link
+            FROM articles a
+            JOIN feeds f ON a.feed_id = f.id
+            JOIN subscriptions s ON s.feed_id = a.feed_id
+            WHERE s.user_id = "#,
+        );
+        qb.push_bind(self.user_id);
+        qb.push(NOT_HIDDEN);
+        qb.push(" AND a.dedup_group_id IN (");
+        let mut ids = qb.separated(", ");
+        for id in group_ids {
+            ids.push_bind(*id);
         }
+        qb.push(") ORDER BY a.id");
+
+        let sources = qb
+            .build_query_as::<ArticleSourceRow>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(sources.into_iter().map(|s| s.into()).collect())
+    }
+
+    async fn get_reads_per_day(&self, since: DateTime<Utc>) -> DbResult<Vec<DailyReadCount>> {
+        let days = sqlx::query_as::<_, DailyReadCountRow>(
+            r#"
+            SELECT date(st.read_at) as day, COUNT(*) as count
+            FROM article_states st
+            JOIN articles a ON a.id = st.article_id
+            JOIN subscriptions s ON s.feed_id = a.feed_id AND s.user_id = st.user_id
+            WHERE st.user_id = ? AND st.read_at IS NOT NULL
+              AND julianday(st.read_at) >= julianday(?)
+            GROUP BY date(st.read_at)
+            ORDER BY day
+            "#,
+        )
+        .bind(self.user_id)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(days.into_iter().map(|d| d.into()).collect())
+    }
+
+    async fn get_feed_read_stats(&self) -> DbResult<Vec<FeedReadStats>> {
+        let feeds = sqlx::query_as::<_, FeedReadStatsRow>(
+            r#"
+            SELECT
+                f.id as feed_id, COALESCE(s.custom_title, f.title) as feed_title,
+                COUNT(a.id) as total_articles,
+                COUNT(st.read_at) as read_articles,
+                AVG(CASE WHEN julianday(st.read_at) >= julianday(a.published_at)
+                    THEN (julianday(st.read_at) - julianday(a.published_at)) * 86400.0 END)
+                    as avg_seconds_to_read,
+                MAX(st.read_at) as last_read_at
+            FROM subscriptions s
+            JOIN feeds f ON f.id = s.feed_id
+            LEFT JOIN articles a ON a.feed_id = f.id
+            LEFT JOIN article_states st ON st.article_id = a.id AND st.user_id = s.user_id
+            WHERE s.user_id = ?
+            GROUP BY f.id, s.custom_title, f.title
+            ORDER BY COALESCE(s.custom_title, f.title) COLLATE NOCASE
+            "#,
+        )
+        .bind(self.user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(feeds.into_iter().map(|f| f.into()).collect())
+    }
+
+    async fn get_average_time_to_read(&self) -> DbResult<Option<f64>> {
+        let avg = sqlx::query_scalar::<_, Option<f64>>(
+            r#"
+            SELECT AVG((julianday(st.read_at) - julianday(a.published_at)) * 86400.0)
+            FROM article_states st
+            JOIN articles a ON a.id = st.article_id
+            JOIN subscriptions s ON s.feed_id = a.feed_id AND s.user_id = st.user_id
+            WHERE st.user_id = ? AND julianday(st.read_at) >= julianday(a.published_at)
+            "#,
+        )
+        .bind(self.user_id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(avg)
+    }
+
+    async fn record_feed_fetch(&self, fetch: &NewFeedFetch) -> DbResult<()> {
+        sqlx::query(
+            "INSERT INTO feed_fetches (feed_id, success, status_code, duration_ms, bytes, new_articles, error) VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(fetch.feed_id)
+        .bind(fetch.success)
+        .bind(fetch.status_code)
+        .bind(fetch.duration_ms)
+        .bind(fetch.bytes)
+        .bind(fetch.new_articles)
+        .bind(&fetch.error)
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            "DELETE FROM feed_fetches WHERE feed_id = ? AND id NOT IN (SELECT id FROM feed_fetches WHERE feed_id = ? ORDER BY id DESC LIMIT ?)",
+        )
+        .bind(fetch.feed_id)
+        .bind(fetch.feed_id)
+        .bind(FETCH_HISTORY_LEN)
+        .execute(&self.pool)
+        .await?;
         Ok(())
     }
 
-    async fn mark_all_unread(&self, feed_id: Option<i64>) -> DbResult<()> {
-        if let Some(fid) = feed_id {
-            sqlx::query("UPDATE articles SET is_read = 0 WHERE feed_id = ?")
-                .bind(fid)
-                .execute(&self.pool)
-                .await?;
-        } else {
-            sqlx::query("UPDATE articles SET is_read = 0")
-                .execute(&self.pool)
-                .await?;
-        }
-        Ok(())
+    async fn get_feed_health(
+        &self,
+        feed_id: i64,
+        active_since: DateTime<Utc>,
+        dead_before: DateTime<Utc>,
+    ) -> DbResult<Option<FeedHealth>> {
+        let Some(created_at) = sqlx::query_scalar::<_, Option<DateTime<Utc>>>(
+            "SELECT f.created_at FROM feeds f JOIN subscriptions s ON s.feed_id = f.id WHERE s.user_id = ? AND f.id = ?",
+        )
+        .bind(self.user_id)
+        .bind(feed_id)
+        .fetch_optional(&self.pool)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        let recent_posts = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM articles WHERE feed_id = ? AND julianday(COALESCE(published_at, created_at)) >= julianday(?)",
+        )
+        .bind(feed_id)
+        .bind(active_since)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let last_entry_at = sqlx::query_scalar::<_, Option<DateTime<Utc>>>(
+            "SELECT COALESCE(published_at, created_at) FROM articles WHERE feed_id = ? ORDER BY julianday(COALESCE(published_at, created_at)) DESC LIMIT 1",
+        )
+        .bind(feed_id)
+        .fetch_optional(&self.pool)
+        .await?
+        .flatten();
+
+        let (fetch_count, successes, avg_response_ms, avg_response_bytes) =
+            sqlx::query_as::<_, (i64, i64, Option<f64>, Option<f64>)>(
+                "SELECT COUNT(*), COALESCE(SUM(success), 0), AVG(duration_ms), AVG(bytes) FROM feed_fetches WHERE feed_id = ?",
+            )
+            .bind(feed_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        let recent_fetches = sqlx::query_as::<_, FeedFetchRow>(
+            "SELECT id, feed_id, fetched_at, success, status_code, duration_ms, bytes, new_articles, error FROM feed_fetches WHERE feed_id = ? ORDER BY id DESC LIMIT ?",
+        )
+        .bind(feed_id)
+        .bind(RECENT_FETCHES)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let weeks = (Utc::now() - active_since).num_seconds().max(1) as f64 / (7.0 * 86400.0);
+        Ok(Some(FeedHealth {
+            feed_id,
+            posts_per_week: recent_posts as f64 / weeks,
+            last_entry_at,
+            fetch_count,
+            success_rate: (fetch_count > 0).then(|| successes as f64 / fetch_count as f64),
+            avg_response_ms,
+            avg_response_bytes,
+            // A feed that never published anything counts from when it was added
+            is_dead: last_entry_at
+                .or(created_at)
+                .is_some_and(|at| at < dead_before),
+            recent_fetches: recent_fetches.into_iter().map(|f| f.into()).collect(),
+        }))
+    }
+
+    async fn get_folders(&self) -> DbResult<Vec<Folder>> {
+        let folders = sqlx::query_as::<_, FolderRow>(
+            r#"
+            WITH RECURSIVE subtree(root_id, id) AS (
+                SELECT id, id FROM folders WHERE user_id = ?1
+                UNION
+                SELECT s.root_id, f.id FROM folders f JOIN subtree s ON f.parent_id = s.id
+            )
+            SELECT
+                f.id, f.name, f.parent_id, f.position,
+                (SELECT COUNT(*) FROM subscriptions WHERE user_id = f.user_id AND folder_id = f.id) as feed_count,
+                (
+                    SELECT COUNT(*) FROM articles a
+                    JOIN subscriptions su ON su.feed_id = a.feed_id AND su.user_id = f.user_id
+                    JOIN subtree s ON su.folder_id = s.id
+                    LEFT JOIN article_states st ON st.article_id = a.id AND st.user_id = su.user_id
+                    WHERE s.root_id = f.id AND COALESCE(st.is_read, 0) = 0 AND su.status <> 'muted'
+                        AND NOT EXISTS (SELECT 1 FROM article_tombstones t WHERE t.user_id = su.user_id AND t.feed_id = a.feed_id AND t.guid = a.guid)
+                ) as unread_count
+            FROM folders f
+            WHERE f.user_id = ?1
+            ORDER BY f.position, f.name COLLATE NOCASE
+            "#,
+        )
+        .bind(self.user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(folders.into_iter().map(|f| f.into()).collect())
+    }
+
+    async fn create_folder(&self, name: &str, parent_id: Option<i64>) -> DbResult<Folder> {
+        let (id, position) = sqlx::query_as::<_, (i64, i64)>(
+            "INSERT INTO folders (user_id, name, parent_id, position) VALUES (?1, ?2, ?3, (SELECT COALESCE(MAX(position) + 1, 0) FROM folders WHERE user_id = ?1 AND parent_id IS ?3)) RETURNING id, position",
+        )
+        .bind(self.user_id)
+        .bind(name)
+        .bind(parent_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(Folder {
+            id,
+            name: name.to_string(),
+            parent_id,
+            position,
+            feed_count: 0,
+            unread_count: 0,
+        })
+    }
+
+    async fn rename_folder(&self, id: i64, name: &str) -> DbResult<()> {
+        sqlx::query("UPDATE folders SET name = ? WHERE id = ? AND user_id = ?")
+            .bind(name)
+            .bind(id)
+            .bind(self.user_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_folder(&self, id: i64, mode: FolderDeleteMode) -> DbResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        let parent_id = sqlx::query_scalar::<_, Option<i64>>(
+            "SELECT parent_id FROM folders WHERE id = ? AND user_id = ?",
+        )
+        .bind(id)
+        .bind(self.user_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| DbError::NotFound(format!("folder {}", id)))?;
+
+        match mode {
+            FolderDeleteMode::Reparent => {
+                sqlx::query(
+                    "UPDATE subscriptions SET folder_id = ? WHERE user_id = ? AND folder_id = ?",
+                )
+                .bind(parent_id)
+                .bind(self.user_id)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+                sqlx::query("UPDATE folders SET parent_id = ? WHERE parent_id = ?")
+                    .bind(parent_id)
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+                sqlx::query("DELETE FROM folders WHERE id = ?")
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            FolderDeleteMode::Cascade => {
+                sqlx::query(
+                    r#"
+            WITH RECURSIVE subtree(id) AS (
+                SELECT id FROM folders WHERE id = ?
+                UNION
+                SELECT f.id FROM folders f JOIN subtree s ON f.parent_id = s.id
+            )
+            DELETE FROM subscriptions WHERE user_id = ? AND folder_id IN (SELECT id FROM subtree)
+            "#,
+                )
+                .bind(id)
+                .bind(self.user_id)
+                .execute(&mut *tx)
+                .await?;
+                sqlx::query(
+                    r#"
+            WITH RECURSIVE subtree(id) AS (
+                SELECT id FROM folders WHERE id = ?
+                UNION
+                SELECT f.id FROM folders f JOIN subtree s ON f.parent_id = s.id
+            )
+            DELETE FROM folders WHERE id IN (SELECT id FROM subtree)
+            "#,
+                )
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+                sqlx::query(DELETE_UNSUBSCRIBED_FEEDS)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn move_folder(&self, id: i64, parent_id: Option<i64>) -> DbResult<()> {
+        let result = sqlx::query(
+            "UPDATE folders SET parent_id = ?1, position = (SELECT COALESCE(MAX(position) + 1, 0) FROM folders WHERE user_id = ?3 AND parent_id IS ?1 AND id <> ?2) WHERE id = ?2 AND user_id = ?3",
+        )
+        .bind(parent_id)
+        .bind(id)
+        .bind(self.user_id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound(format!("folder {}", id)));
+        }
+        Ok(())
+    }
+
+    async fn reorder_folders(&self, parent_id: Option<i64>, folder_ids: &[i64]) -> DbResult<()> {
+        let mut tx = self.pool.begin().await?;
+        for (position, folder_id) in folder_ids.iter().enumerate() {
+            sqlx::query(
+                "UPDATE folders SET position = ? WHERE id = ? AND user_id = ? AND parent_id IS ?",
+            )
+            .bind(position as i64)
+            .bind(folder_id)
+            .bind(self.user_id)
+            .bind(parent_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn move_feed_to_folder(&self, feed_id: i64, folder_id: Option<i64>) -> DbResult<()> {
+        sqlx::query(
+            "UPDATE subscriptions SET folder_id = ?1, position = (SELECT COALESCE(MAX(position) + 1, 0) FROM subscriptions WHERE user_id = ?2 AND folder_id IS ?1 AND feed_id <> ?3) WHERE user_id = ?2 AND feed_id = ?3",
+        )
+        .bind(folder_id)
+        .bind(self.user_id)
+        .bind(feed_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn reorder_feeds(&self, folder_id: Option<i64>, feed_ids: &[i64]) -> DbResult<()> {
+        let mut tx = self.pool.begin().await?;
+        for (position, feed_id) in feed_ids.iter().enumerate() {
+            sqlx::query(
+                "UPDATE subscriptions SET position = ? WHERE user_id = ? AND feed_id = ? AND folder_id IS ?",
+            )
+            .bind(position as i64)
+            .bind(self.user_id)
+            .bind(feed_id)
+            .bind(folder_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_smart_folders(&self) -> DbResult<Vec<SmartFolder>> {
+        let folders = sqlx::query_as::<_, SmartFolderRow>(
+            "SELECT id, name, query, created_at FROM smart_folders WHERE user_id = ? ORDER BY name COLLATE NOCASE",
+        )
+        .bind(self.user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(folders.into_iter().map(|f| f.into()).collect())
+    }
+
+    async fn get_smart_folder(&self, id: i64) -> DbResult<Option<SmartFolder>> {
+        let folder = sqlx::query_as::<_, SmartFolderRow>(
+            "SELECT id, name, query, created_at FROM smart_folders WHERE id = ? AND user_id = ?",
+        )
+        .bind(id)
+        .bind(self.user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(folder.map(|f| f.into()))
+    }
+
+    async fn create_smart_folder(&self, folder: &NewSmartFolder) -> DbResult<SmartFolder> {
+        let row = sqlx::query_as::<_, SmartFolderRow>(
+            "INSERT INTO smart_folders (user_id, name, query) VALUES (?, ?, ?) RETURNING id, name, query, created_at",
+        )
+        .bind(self.user_id)
+        .bind(&folder.name)
+        .bind(&folder.query)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.into())
+    }
+
+    async fn update_smart_folder(&self, id: i64, folder: &NewSmartFolder) -> DbResult<()> {
+        let result = sqlx::query(
+            "UPDATE smart_folders SET name = ?, query = ? WHERE id = ? AND user_id = ?",
+        )
+        .bind(&folder.name)
+        .bind(&folder.query)
+        .bind(id)
+        .bind(self.user_id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound(format!("smart folder {}", id)));
+        }
+        Ok(())
+    }
+
+    async fn delete_smart_folder(&self, id: i64) -> DbResult<()> {
+        sqlx::query("DELETE FROM smart_folders WHERE id = ? AND user_id = ?")
+            .bind(id)
+            .bind(self.user_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_labels(&self) -> DbResult<Vec<Label>> {
+        let labels = sqlx::query_as::<_, LabelRow>(
+            r#"
+            SELECT
+                l.id, l.name, l.color,
+                (SELECT COUNT(*) FROM article_labels WHERE label_id = l.id) as article_count
+            FROM labels l
+            WHERE l.user_id = ?
+            ORDER BY l.name COLLATE NOCASE
+            "#,
+        )
+        .bind(self.user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(labels.into_iter().map(|l| l.into()).collect())
+    }
+
+    async fn create_label(&self, label: &NewLabel) -> DbResult<Label> {
+        let id = sqlx::query_scalar::<_, i64>(
+            "INSERT INTO labels (user_id, name, color) VALUES (?, ?, ?) RETURNING id",
+        )
+        .bind(self.user_id)
+        .bind(&label.name)
+        .bind(&label.color)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(Label {
+            id,
+            name: label.name.clone(),
+            color: label.color.clone(),
+            article_count: 0,
+        })
+    }
+
+    async fn update_label(&self, id: i64, label: &NewLabel) -> DbResult<()> {
+        let result =
+            sqlx::query("UPDATE labels SET name = ?, color = ? WHERE id = ? AND user_id = ?")
+                .bind(&label.name)
+                .bind(&label.color)
+                .bind(id)
+                .bind(self.user_id)
+                .execute(&self.pool)
+                .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound(format!("label {}", id)));
+        }
+        Ok(())
+    }
+
+    async fn delete_label(&self, id: i64) -> DbResult<()> {
+        sqlx::query("DELETE FROM labels WHERE id = ? AND user_id = ?")
+            .bind(id)
+            .bind(self.user_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn add_label_to_articles(&self, label_id: i64, article_ids: &[i64]) -> DbResult<()> {
+        if article_ids.is_empty() {
+            return Ok(());
+        }
+
+        // Only the user's own labels on articles they can see
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new(
+            r#"
+            INSERT OR IGNORE INTO article_labels (article_id, label_id)
+            SELECT a.id, l.id FROM articles a
+            JOIN subscriptions s ON s.feed_id = a.feed_id
+            JOIN labels l ON l.user_id = s.user_id
+            WHERE s.user_id = "#,
+        );
+        qb.push_bind(self.user_id);
+        qb.push(" AND l.id = ");
+        qb.push_bind(label_id);
+        qb.push(" AND a.id IN (");
+        let mut ids = qb.separated(", ");
+        for article_id in article_ids {
+            ids.push_bind(*article_id);
+        }
+        qb.push(")");
+        qb.build().execute(&self.pool).await?;
+        Ok(())
+    }
+
+    async fn remove_label_from_articles(&self, label_id: i64, article_ids: &[i64]) -> DbResult<()> {
+        if article_ids.is_empty() {
+            return Ok(());
+        }
+
+        let mut qb: QueryBuilder<Sqlite> =
+            QueryBuilder::new("DELETE FROM article_labels WHERE label_id = ");
+        qb.push_bind(label_id);
+        qb.push(" AND label_id IN (SELECT id FROM labels WHERE user_id = ");
+        qb.push_bind(self.user_id);
+        qb.push(") AND article_id IN (");
+        let mut ids = qb.separated(", ");
+        for article_id in article_ids {
+            ids.push_bind(*article_id);
+        }
+        qb.push(")");
+        qb.build().execute(&self.pool).await?;
+        Ok(())
+    }
+
+    async fn get_article_labels(&self, article_ids: &[i64]) -> DbResult<Vec<(i64, i64)>> {
+        if article_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new(
+            "SELECT al.article_id, al.label_id FROM article_labels al JOIN labels l ON l.id = al.label_id WHERE l.user_id = ",
+        );
+        qb.push_bind(self.user_id);
+        qb.push(" AND al.article_id IN (");
+        let mut ids = qb.separated(", ");
+        for article_id in article_ids {
+            ids.push_bind(*article_id);
+        }
+        qb.push(") ORDER BY al.article_id, al.label_id");
+
+        let pairs = qb
+            .build_query_as::<(i64, i64)>()
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(pairs)
+    }
+
+    async fn get_highlights(&self, query: &HighlightQuery) -> DbResult<Vec<Highlight>> {
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new(HIGHLIGHT_SELECT);
+        qb.push_bind(self.user_id);
+
+        if let Some(article_id) = query.article_id {
+            qb.push(" AND h.article_id = ");
+            qb.push_bind(article_id);
+        }
+
+        if let Some(label_id) = query.label_id {
+            qb.push(
+                " AND EXISTS (SELECT 1 FROM article_labels al JOIN labels l ON l.id = al.label_id WHERE al.article_id = h.article_id AND l.user_id = h.user_id AND al.label_id = ",
+            );
+            qb.push_bind(label_id);
+            qb.push(")");
+        }
+
+        if let Some(search) = &query.search {
+            for term in &search.terms {
+                qb.push(if term.negated { " AND NOT (" } else { " AND (" });
+                qb.push("LOWER(h.quote || ' ' || COALESCE(h.note, '')) LIKE ");
+                qb.push_bind(like_contains(&term.text));
+                qb.push(" ESCAPE '\\')");
+            }
+            push_filter_conditions(&mut qb, &search.filters);
+        }
+
+        qb.push(" ORDER BY h.created_at DESC, h.id DESC");
+        qb.push(" LIMIT ");
+        qb.push_bind(query.limit.unwrap_or(-1));
+        qb.push(" OFFSET ");
+        qb.push_bind(query.offset);
+
+        let highlights = qb
+            .build_query_as::<HighlightRow>()
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(highlights.into_iter().map(|h| h.into()).collect())
+    }
+
+    async fn get_highlight(&self, id: i64) -> DbResult<Option<Highlight>> {
+        let highlight =
+            sqlx::query_as::<_, HighlightRow>(&format!("{}? AND h.id = ?", HIGHLIGHT_SELECT))
+                .bind(self.user_id)
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        Ok(highlight.map(|h| h.into()))
+    }
+
+    async fn create_highlight(&self, highlight: &NewHighlight) -> DbResult<Highlight> {
+        let id = sqlx::query_scalar::<_, i64>(
+            r#"
+            INSERT INTO highlights
+                (user_id, article_id, quote, prefix, suffix, start_offset, end_offset, note, color)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            RETURNING id
+            "#,
+        )
+        .bind(self.user_id)
+        .bind(highlight.article_id)
+        .bind(&highlight.quote)
+        .bind(&highlight.prefix)
+        .bind(&highlight.suffix)
+        .bind(highlight.start_offset)
+        .bind(highlight.end_offset)
+        .bind(&highlight.note)
+        .bind(&highlight.color)
+        .fetch_one(&self.pool)
+        .await?;
+
+        self.get_highlight(id)
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("highlight {}", id)))
+    }
+
+    async fn update_highlight(&self, id: i64, highlight: &UpdateHighlight) -> DbResult<()> {
+        let result = sqlx::query(
+            "UPDATE highlights SET note = ?, color = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ? AND user_id = ?",
+        )
+        .bind(&highlight.note)
+        .bind(&highlight.color)
+        .bind(id)
+        .bind(self.user_id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound(format!("highlight {}", id)));
+        }
+        Ok(())
+    }
+
+    async fn delete_highlight(&self, id: i64) -> DbResult<()> {
+        sqlx::query("DELETE FROM highlights WHERE id = ? AND user_id = ?")
+            .bind(id)
+            .bind(self.user_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_webhooks(&self) -> DbResult<Vec<Webhook>> {
+        let webhooks = sqlx::query_as::<_, WebhookRow>(&format!(
+            "{} WHERE user_id = ? ORDER BY id",
+            WEBHOOK_SELECT
+        ))
+        .bind(self.user_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(webhooks.into_iter().map(|w| w.into()).collect())
+    }
+
+    async fn get_webhook(&self, id: i64) -> DbResult<Option<Webhook>> {
+        let webhook = sqlx::query_as::<_, WebhookRow>(&format!(
+            "{} WHERE id = ? AND user_id = ?",
+            WEBHOOK_SELECT
+        ))
+        .bind(id)
+        .bind(self.user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(webhook.map(|w| w.into()))
+    }
+
+    async fn create_webhook(&self, webhook: &NewWebhook, secret: &str) -> DbResult<Webhook> {
+        let id = sqlx::query_scalar::<_, i64>(
+            "INSERT INTO webhooks (user_id, url, event, feed_id, folder_id, keyword, enabled, secret) VALUES (?, ?, ?, ?, ?, ?, ?, ?) RETURNING id",
+        )
+        .bind(self.user_id)
+        .bind(&webhook.url)
+        .bind(webhook.event.as_str())
+        .bind(webhook.feed_id)
+        .bind(webhook.folder_id)
+        .bind(&webhook.keyword)
+        .bind(webhook.enabled)
+        .bind(secret)
+        .fetch_one(&self.pool)
+        .await?;
+
+        self.get_webhook(id)
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("webhook {}", id)))
+    }
+
+    async fn update_webhook(&self, id: i64, webhook: &NewWebhook) -> DbResult<()> {
+        let result = sqlx::query(
+            "UPDATE webhooks SET url = ?, event = ?, feed_id = ?, folder_id = ?, keyword = ?, enabled = ? WHERE id = ? AND user_id = ?",
+        )
+        .bind(&webhook.url)
+        .bind(webhook.event.as_str())
+        .bind(webhook.feed_id)
+        .bind(webhook.folder_id)
+        .bind(&webhook.keyword)
+        .bind(webhook.enabled)
+        .bind(id)
+        .bind(self.user_id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound(format!("webhook {}", id)));
+        }
+        Ok(())
+    }
+
+    async fn delete_webhook(&self, id: i64) -> DbResult<()> {
+        let result = sqlx::query("DELETE FROM webhooks WHERE id = ? AND user_id = ?")
+            .bind(id)
+            .bind(self.user_id)
+            .execute(&self.pool)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound(format!("webhook {}", id)));
+        }
+        Ok(())
+    }
+
+    async fn get_webhook_deliveries(
+        &self,
+        webhook_id: i64,
+        limit: i64,
+    ) -> DbResult<Vec<WebhookDelivery>> {
+        let deliveries = sqlx::query_as::<_, WebhookDeliveryRow>(
+            r#"
+            SELECT d.id, d.webhook_id, d.delivery_id, d.event, d.attempt, d.success,
+                d.status_code, d.error, d.duration_ms, d.delivered_at
+            FROM webhook_deliveries d
+            JOIN webhooks w ON w.id = d.webhook_id
+            WHERE d.webhook_id = ? AND w.user_id = ?
+            ORDER BY d.id DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(self.user_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(deliveries.into_iter().map(|d| d.into()).collect())
+    }
+
+    async fn get_enabled_webhooks(&self, event: WebhookEvent) -> DbResult<Vec<Webhook>> {
+        let webhooks = sqlx::query_as::<_, WebhookRow>(&format!(
+            "{} WHERE event = ? AND enabled = 1 ORDER BY id",
+            WEBHOOK_SELECT
+        ))
+        .bind(event.as_str())
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(webhooks.into_iter().map(|w| w.into()).collect())
+    }
+
+    async fn record_webhook_delivery(&self, delivery: &NewWebhookDelivery) -> DbResult<()> {
+        sqlx::query(
+            "INSERT INTO webhook_deliveries (webhook_id, delivery_id, event, attempt, success, status_code, error, duration_ms) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(delivery.webhook_id)
+        .bind(&delivery.delivery_id)
+        .bind(&delivery.event)
+        .bind(delivery.attempt)
+        .bind(delivery.success)
+        .bind(delivery.status_code)
+        .bind(&delivery.error)
+        .bind(delivery.duration_ms)
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            "DELETE FROM webhook_deliveries WHERE webhook_id = ? AND id NOT IN (SELECT id FROM webhook_deliveries WHERE webhook_id = ? ORDER BY id DESC LIMIT ?)",
+        )
+        .bind(delivery.webhook_id)
+        .bind(delivery.webhook_id)
+        .bind(WEBHOOK_DELIVERY_HISTORY_LEN)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
+
+fn article_order(query: &ArticleQuery) -> &'static str {
+    match (query.sort, query.oldest_first) {
+        (ArticleSort::Published, false) => {
+            " ORDER BY a.published_at DESC NULLS LAST, a.created_at DESC"
+        }
+        (ArticleSort::Published, true) => {
+            " ORDER BY a.published_at ASC NULLS LAST, a.created_at ASC"
+        }
+        (ArticleSort::FirstSeen, false) => " ORDER BY a.created_at DESC, a.id DESC",
+        (ArticleSort::FirstSeen, true) => " ORDER BY a.created_at ASC, a.id ASC",
+        (ArticleSort::Id, false) => " ORDER BY a.id DESC",
+        (ArticleSort::Id, true) => " ORDER BY a.id ASC",
+    }
+}
+
+/// Append the conditions of the user's article query, minus paging, as `AND` clauses.
+fn push_article_filters(qb: &mut QueryBuilder<'_, Sqlite>, query: &ArticleQuery, user_id: i64) {
+    if let Some(feed_id) = query.feed_id {
+        qb.push(" AND a.feed_id = ");
+        qb.push_bind(feed_id);
+    }
+
+    match query.folder_id {
+        Some(FolderId::Folder(folder_id)) => {
+            qb.push(
+                " AND s.folder_id IN (WITH RECURSIVE subtree(id) AS (SELECT id FROM folders WHERE id = ",
+            );
+            qb.push_bind(folder_id);
+            qb.push(" UNION SELECT fo.id FROM folders fo JOIN subtree sub ON fo.parent_id = sub.id) SELECT id FROM subtree)");
+        }
+        Some(FolderId::Unfiled) => {
+            qb.push(" AND s.folder_id IS NULL");
+        }
+        None => {}
+    }
+
+    qb.push(NOT_HIDDEN);
+
+    if query.hides_muted() {
+        qb.push(" AND s.status <> 'muted'");
+    }
+
+    if query.unread_only {
+        qb.push(" AND COALESCE(st.is_read, 0) = 0");
+    }
+
+    if query.favorites_only {
+        qb.push(" AND st.is_favorite = 1");
     }
 
-    async fn toggle_favorite(&self, id: i64) -> DbResult<()> {
-        sqlx::query(
-            "UPDATE articles SET is_favorite = CASE WHEN is_favorite = 0 THEN 1 ELSE 0 END WHERE id = ?",
-        )
-        .bind(id)
-        .execute(&self.pool)
-        .await?;
-        Ok(())
+    if query.collapse_duplicates {
+        // Keep the earliest copy of each duplicate group among the articles this same
+        // query matches, so copies that are hidden, muted, read or outside the view
+        // don't stand in for the group
+        let mut representatives = query.clone();
+        representatives.collapse_duplicates = false;
+        qb.push(" AND (a.dedup_group_id IS NULL OR a.id IN (SELECT MIN(a.id) ");
+        qb.push(ARTICLE_FROM);
+        qb.push_bind(user_id);
+        push_article_filters(qb, &representatives, user_id);
+        qb.push(" AND a.dedup_group_id IS NOT NULL GROUP BY a.dedup_group_id))");
     }
 
-    async fn get_favorites_count(&self) -> DbResult<i64> {
-        let count =
-            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM articles WHERE is_favorite = 1")
-                .fetch_one(&self.pool)
-                .await?;
-        Ok(count)
+    if let Some(label_id) = query.label_id {
+        qb.push(
+            " AND EXISTS (SELECT 1 FROM article_labels al JOIN labels l ON l.id = al.label_id WHERE al.article_id = a.id AND l.user_id = s.user_id AND al.label_id = ",
+        );
+        qb.push_bind(label_id);
+        qb.push(")");
     }
 
-    async fn search_articles(&self, query: &str, limit: i64) -> DbResult<Vec<Article>> {
-        let articles = sqlx::query_as::<_, ArticleRow>(
-            r#"
-            SELECT
-                a.id, a.feed_id, a.guid, a.title, a.link, a.author, a.summary, a.content,
-                a.image_url, a.published_at, a.is_read, a.is_favorite, a.created_at,
-                f.title as feed_title, f.favicon_url as feed_favicon_url
-            FROM articles a
-            JOIN feeds f ON a.feed_id = f.id
-            JOIN articles_fts fts ON a.id = fts.rowid
-            WHERE articles_fts MATCH ?
-            ORDER BY bm25(articles_fts)
-            LIMIT ?
-            "#,
-        )
-        .bind(query)
-        .bind(limit)
-        .fetch_all(&self.pool)
-        .await?;
+    if let Some(after) = query.published_after {
+        qb.push(" AND julianday(COALESCE(a.published_at, a.created_at)) >= julianday(");
+        qb.push_bind(after);
+        qb.push(")");
+    }
 
-        Ok(articles.into_iter().map(|a| a.into()).collect())
+    if let Some(before) = query.published_before {
+        qb.push(" AND julianday(COALESCE(a.published_at, a.created_at)) < julianday(");
+        qb.push_bind(before);
+        qb.push(")");
     }
 
-    async fn update_article_content(&self, id: i64, content: &str) -> DbResult<()> {
-        sqlx::query("UPDATE articles SET content = ? WHERE id = ?")
-            .bind(content)
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
-        Ok(())
+    if let Some(after) = query.created_after {
+        qb.push(" AND julianday(a.created_at) >= julianday(");
+        qb.push_bind(after);
+        qb.push(")");
     }
 
-    async fn get_article_link(&self, id: i64) -> DbResult<Option<String>> {
-        let result = sqlx::query_scalar::<_, String>("SELECT link FROM articles WHERE id = ?")
-            .bind(id)
-            .fetch_optional(&self.pool)
-            .await?;
-        Ok(result)
+    if let Some(before) = query.created_before {
+        qb.push(" AND julianday(a.created_at) < julianday(");
+        qb.push_bind(before);
+        qb.push(")");
     }
 
-    async fn get_folders(&self) -> DbResult<Vec<Folder>> {
-        let folders = sqlx::query_as::<_, FolderRow>(
-            r#"
-            SELECT
-                f.id, f.name,
-                (SELECT COUNT(*) FROM feeds WHERE folder_id = f.id) as feed_count
-            FROM folders f
-            ORDER BY f.name COLLATE NOCASE
-            "#,
-        )
-        .fetch_all(&self.pool)
-        .await?;
+    if let Some(after) = query.modified_after {
+        qb.push(" AND (julianday(a.created_at) >= julianday(");
+        qb.push_bind(after);
+        qb.push(") OR julianday(st.updated_at) >= julianday(");
+        qb.push_bind(after);
+        qb.push("))");
+    }
 
-        Ok(folders.into_iter().map(|f| f.into()).collect())
+    if let Some(author) = &query.author {
+        qb.push(" AND LOWER(COALESCE(a.author, '')) LIKE ");
+        qb.push_bind(like_contains(author));
+        qb.push(" ESCAPE '\\'");
     }
 
-    async fn create_folder(&self, name: &str) -> DbResult<Folder> {
-        let result = sqlx::query("INSERT INTO folders (name) VALUES (?)")
-            .bind(name)
-            .execute(&self.pool)
-            .await?;
+    if query.has_enclosure {
+        qb.push(" AND a.enclosure_url IS NOT NULL");
+    }
 
-        Ok(Folder {
-            id: result.last_insert_rowid(),
-            name: name.to_string(),
-            feed_count: 0,
-        })
+    if let Some(min_length) = query.min_content_length {
+        qb.push(" AND LENGTH(COALESCE(a.content, a.summary, '')) >= ");
+        qb.push_bind(min_length);
     }
 
-    async fn rename_folder(&self, id: i64, name: &str) -> DbResult<()> {
-        sqlx::query("UPDATE folders SET name = ? WHERE id = ?")
-            .bind(name)
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
-        Ok(())
+    if let Some(after_id) = query.after_id {
+        qb.push(" AND a.id > ");
+        qb.push_bind(after_id);
+    }
+    if let Some(before_id) = query.before_id {
+        qb.push(" AND a.id < ");
+        qb.push_bind(before_id);
+    }
+    if let Some(article_ids) = &query.ids {
+        if article_ids.is_empty() {
+            qb.push(" AND 1 = 0");
+        } else {
+            qb.push(" AND a.id IN (");
+            let mut ids = qb.separated(", ");
+            for article_id in article_ids {
+                ids.push_bind(*article_id);
+            }
+            qb.push(")");
+        }
     }
 
-    async fn delete_folder(&self, id: i64) -> DbResult<()> {
-        // First set folder_id to NULL for all feeds in this folder
-        sqlx::query("UPDATE feeds SET folder_id = NULL WHERE folder_id = ?")
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
+    if let Some(search) = &query.search {
+        if let Some(expr) = search.fts5_match() {
+            qb.push(" AND a.id IN (SELECT rowid FROM articles_fts WHERE articles_fts MATCH ");
+            qb.push_bind(expr);
+            qb.push(")");
+        }
+        push_search_filters(qb, search);
+    }
+}
 
-        // Then delete the folder
-        sqlx::query("DELETE FROM folders WHERE id = ?")
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
-        Ok(())
+/// Append the negated terms and filters of a search query as `AND` conditions.
+fn push_search_filters(qb: &mut QueryBuilder<'_, Sqlite>, query: &SearchQuery) {
+    for term in query.negative_terms() {
+        qb.push(" AND a.id NOT IN (SELECT rowid FROM articles_fts WHERE articles_fts MATCH ");
+        qb.push_bind(fts5_quote(&term.text));
+        qb.push(")");
     }
 
-    async fn move_feed_to_folder(&self, feed_id: i64, folder_id: Option<i64>) -> DbResult<()> {
-        sqlx::query("UPDATE feeds SET folder_id = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?")
-            .bind(folder_id)
-            .bind(feed_id)
-            .execute(&self.pool)
-            .await?;
-        Ok(())
+    for term in query.substring_terms() {
+        qb.push(if term.negated { " AND NOT (" } else { " AND (" });
+        qb.push(
+            "LOWER(a.title || ' ' || COALESCE(a.summary, '') || ' ' || COALESCE(a.author, '') || ' ' || COALESCE(a.content, '')) LIKE ",
+        );
+        qb.push_bind(like_contains(&term.text));
+        qb.push(" ESCAPE '\\')");
+    }
+
+    push_filter_conditions(qb, &query.filters);
+}
+
+/// Append search filters, which all apply to the article `a`, its feed `f`, the
+/// subscription `s` and the read state `st`, as `AND` conditions.
+fn push_filter_conditions(qb: &mut QueryBuilder<'_, Sqlite>, filters: &[SearchFilter]) {
+    for filter in filters {
+        qb.push(if filter.negated {
+            " AND NOT ("
+        } else {
+            " AND ("
+        });
+        match &filter.field {
+            FilterField::Feed(feed) => match feed.parse::<i64>() {
+                Ok(id) => {
+                    qb.push("a.feed_id = ");
+                    qb.push_bind(id);
+                }
+                Err(_) => {
+                    qb.push("LOWER(COALESCE(s.custom_title, f.title)) LIKE ");
+                    qb.push_bind(like_contains(feed));
+                    qb.push(" ESCAPE '\\'");
+                }
+            },
+            FilterField::Folder(name) => {
+                qb.push(
+                    "EXISTS (SELECT 1 FROM folders fo WHERE fo.id = s.folder_id AND LOWER(fo.name) = ",
+                );
+                qb.push_bind(name.to_lowercase());
+                qb.push(")");
+            }
+            FilterField::Author(author) => {
+                qb.push("LOWER(COALESCE(a.author, '')) LIKE ");
+                qb.push_bind(like_contains(author));
+                qb.push(" ESCAPE '\\'");
+            }
+            FilterField::Tag(tag) => {
+                qb.push(
+                    "EXISTS (SELECT 1 FROM article_categories c WHERE c.article_id = a.id AND LOWER(c.name) = ",
+                );
+                qb.push_bind(tag.to_lowercase());
+                qb.push(")");
+            }
+            FilterField::Label(name) => {
+                qb.push(
+                    "EXISTS (SELECT 1 FROM article_labels al JOIN labels l ON l.id = al.label_id WHERE al.article_id = a.id AND l.user_id = s.user_id AND LOWER(l.name) = ",
+                );
+                qb.push_bind(name.to_lowercase());
+                qb.push(")");
+            }
+            FilterField::Unread => {
+                qb.push("COALESCE(st.is_read, 0) = 0");
+            }
+            FilterField::Starred => {
+                qb.push("COALESCE(st.is_favorite, 0) = 1");
+            }
+            FilterField::Before(before) => {
+                qb.push("julianday(COALESCE(a.published_at, a.created_at)) < julianday(");
+                qb.push_bind(*before);
+                qb.push(")");
+            }
+            FilterField::After(after) => {
+                qb.push("julianday(COALESCE(a.published_at, a.created_at)) >= julianday(");
+                qb.push_bind(*after);
+                qb.push(")");
+            }
+        }
+        qb.push(")");
     }
 }
 
@@ -456,6 +2632,11 @@ struct FeedRow {
     last_build_date: Option<chrono::DateTime<chrono::Utc>>,
     created_at: Option<chrono::DateTime<chrono::Utc>>,
     updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    custom_title: Option<String>,
+    refresh_interval_minutes: Option<i64>,
+    fetch_full_content: i32,
+    notify: i32,
+    status: String,
 }
 
 impl From<FeedRow> for Feed {
@@ -473,6 +2654,11 @@ impl From<FeedRow> for Feed {
             last_build_date: row.last_build_date,
             created_at: row.created_at,
             updated_at: row.updated_at,
+            custom_title: row.custom_title,
+            refresh_interval_minutes: row.refresh_interval_minutes,
+            fetch_full_content: row.fetch_full_content != 0,
+            notify: row.notify != 0,
+            status: row.status.parse().unwrap_or_default(),
         }
     }
 }
@@ -491,6 +2677,12 @@ struct FeedWithMetaRow {
     last_build_date: Option<chrono::DateTime<chrono::Utc>>,
     created_at: Option<chrono::DateTime<chrono::Utc>>,
     updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    custom_title: Option<String>,
+    refresh_interval_minutes: Option<i64>,
+    fetch_full_content: i32,
+    notify: i32,
+    status: String,
+    position: i64,
     unread_count: i64,
 }
 
@@ -509,6 +2701,12 @@ impl From<FeedWithMetaRow> for FeedWithMeta {
             last_build_date: row.last_build_date,
             created_at: row.created_at,
             updated_at: row.updated_at,
+            custom_title: row.custom_title,
+            refresh_interval_minutes: row.refresh_interval_minutes,
+            fetch_full_content: row.fetch_full_content != 0,
+            notify: row.notify != 0,
+            status: row.status.parse().unwrap_or_default(),
+            position: row.position,
             unread_count: row.unread_count,
         }
     }
@@ -525,10 +2723,15 @@ struct ArticleRow {
     summary: Option<String>,
     content: Option<String>,
     image_url: Option<String>,
+    enclosure_url: Option<String>,
+    enclosure_type: Option<String>,
     published_at: Option<chrono::DateTime<chrono::Utc>>,
     is_read: i32,
     is_favorite: i32,
     created_at: Option<chrono::DateTime<chrono::Utc>>,
+    read_at: Option<chrono::DateTime<chrono::Utc>>,
+    favorited_at: Option<chrono::DateTime<chrono::Utc>>,
+    dedup_group_id: Option<i64>,
     feed_title: Option<String>,
     feed_favicon_url: Option<String>,
 }
@@ -545,12 +2748,81 @@ impl From<ArticleRow> for Article {
             summary: row.summary,
             content: row.content,
             image_url: row.image_url,
+            enclosure_url: row.enclosure_url,
+            enclosure_type: row.enclosure_type,
             published_at: row.published_at,
             is_read: row.is_read != 0,
             is_favorite: row.is_favorite != 0,
             created_at: row.created_at,
+            read_at: row.read_at,
+            favorited_at: row.favorited_at,
+            dedup_group_id: row.dedup_group_id,
             feed_title: row.feed_title,
             feed_favicon_url: row.feed_favicon_url,
+            duplicates: Vec::new(),
+            label_ids: Vec::new(),
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct SearchHitRow {
+    #[sqlx(flatten)]
+    article: ArticleRow,
+    score: f64,
+    snippet: Option<String>,
+}
+
+impl From<SearchHitRow> for SearchHit {
+    fn from(row: SearchHitRow) -> Self {
+        SearchHit {
+            article: row.article.into(),
+            score: row.score,
+            snippet: row.snippet.as_deref().map(highlight_snippet),
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct DuplicateCandidateRow {
+    id: i64,
+    feed_id: i64,
+    guid: String,
+    title: String,
+    normalized_link: Option<String>,
+    group_id: Option<i64>,
+}
+
+impl From<DuplicateCandidateRow> for DuplicateCandidate {
+    fn from(row: DuplicateCandidateRow) -> Self {
+        DuplicateCandidate {
+            id: row.id,
+            feed_id: row.feed_id,
+            guid: row.guid,
+            title: row.title,
+            normalized_link: row.normalized_link,
+            group_id: row.group_id,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct ArticleSourceRow {
+    group_id: i64,
+    article_id: i64,
+    feed_id: i64,
+    feed_title: Option<String>,
+    link: Option<String>,
+}
+
+impl From<ArticleSourceRow> for ArticleSource {
+    fn from(row: ArticleSourceRow) -> Self {
+        ArticleSource {
+            group_id: row.group_id,
+            article_id: row.article_id,
+            feed_id: row.feed_id,
+            feed_title: row.feed_title,
+            link: row.link,
         }
     }
 }
@@ -559,7 +2831,10 @@ impl From<ArticleRow> for Article {
 struct FolderRow {
     id: i64,
     name: String,
+    parent_id: Option<i64>,
+    position: i64,
     feed_count: i64,
+    unread_count: i64,
 }
 
 impl From<FolderRow> for Folder {
@@ -567,7 +2842,284 @@ impl From<FolderRow> for Folder {
         Folder {
             id: row.id,
             name: row.name,
+            parent_id: row.parent_id,
+            position: row.position,
             feed_count: row.feed_count,
+            unread_count: row.unread_count,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct DailyReadCountRow {
+    day: NaiveDate,
+    count: i64,
+}
+
+impl From<DailyReadCountRow> for DailyReadCount {
+    fn from(row: DailyReadCountRow) -> Self {
+        DailyReadCount {
+            day: row.day,
+            count: row.count,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct FeedReadStatsRow {
+    feed_id: i64,
+    feed_title: String,
+    total_articles: i64,
+    read_articles: i64,
+    avg_seconds_to_read: Option<f64>,
+    last_read_at: Option<DateTime<Utc>>,
+}
+
+impl From<FeedReadStatsRow> for FeedReadStats {
+    fn from(row: FeedReadStatsRow) -> Self {
+        FeedReadStats {
+            feed_id: row.feed_id,
+            feed_title: row.feed_title,
+            total_articles: row.total_articles,
+            read_articles: row.read_articles,
+            read_ratio: if row.total_articles > 0 {
+                row.read_articles as f64 / row.total_articles as f64
+            } else {
+                0.0
+            },
+            avg_seconds_to_read: row.avg_seconds_to_read,
+            last_read_at: row.last_read_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct SmartFolderRow {
+    id: i64,
+    name: String,
+    query: String,
+    created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<SmartFolderRow> for SmartFolder {
+    fn from(row: SmartFolderRow) -> Self {
+        SmartFolder {
+            id: row.id,
+            name: row.name,
+            query: row.query,
+            created_at: row.created_at,
+            unread_count: 0,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct LabelRow {
+    id: i64,
+    name: String,
+    color: Option<String>,
+    article_count: i64,
+}
+
+impl From<LabelRow> for Label {
+    fn from(row: LabelRow) -> Self {
+        Label {
+            id: row.id,
+            name: row.name,
+            color: row.color,
+            article_count: row.article_count,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct FeedFetchRow {
+    id: i64,
+    feed_id: i64,
+    fetched_at: Option<chrono::DateTime<chrono::Utc>>,
+    success: i32,
+    status_code: Option<i64>,
+    duration_ms: i64,
+    bytes: Option<i64>,
+    new_articles: i64,
+    error: Option<String>,
+}
+
+impl From<FeedFetchRow> for FeedFetch {
+    fn from(row: FeedFetchRow) -> Self {
+        FeedFetch {
+            id: row.id,
+            feed_id: row.feed_id,
+            fetched_at: row.fetched_at,
+            success: row.success != 0,
+            status_code: row.status_code,
+            duration_ms: row.duration_ms,
+            bytes: row.bytes,
+            new_articles: row.new_articles,
+            error: row.error,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct HiddenArticleRow {
+    id: i64,
+    feed_id: i64,
+    guid: String,
+    title: String,
+    article_id: Option<i64>,
+    hidden_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<HiddenArticleRow> for HiddenArticle {
+    fn from(row: HiddenArticleRow) -> Self {
+        HiddenArticle {
+            id: row.id,
+            feed_id: row.feed_id,
+            guid: row.guid,
+            title: row.title,
+            article_id: row.article_id,
+            hidden_at: row.hidden_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct HighlightRow {
+    id: i64,
+    article_id: i64,
+    article_title: String,
+    quote: String,
+    prefix: Option<String>,
+    suffix: Option<String>,
+    start_offset: Option<i64>,
+    end_offset: Option<i64>,
+    note: Option<String>,
+    color: Option<String>,
+    created_at: Option<chrono::DateTime<chrono::Utc>>,
+    updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<HighlightRow> for Highlight {
+    fn from(row: HighlightRow) -> Self {
+        Highlight {
+            id: row.id,
+            article_id: row.article_id,
+            article_title: row.article_title,
+            quote: row.quote,
+            prefix: row.prefix,
+            suffix: row.suffix,
+            start_offset: row.start_offset,
+            end_offset: row.end_offset,
+            note: row.note,
+            color: row.color,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct UserRow {
+    id: i64,
+    username: String,
+    is_admin: i32,
+    disabled: i32,
+    has_password: i32,
+    created_at: Option<DateTime<Utc>>,
+}
+
+impl From<UserRow> for User {
+    fn from(row: UserRow) -> Self {
+        User {
+            id: row.id,
+            username: row.username,
+            is_admin: row.is_admin != 0,
+            disabled: row.disabled != 0,
+            has_password: row.has_password != 0,
+            created_at: row.created_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct ApiTokenRow {
+    id: i64,
+    name: String,
+    created_at: Option<DateTime<Utc>>,
+    last_used_at: Option<DateTime<Utc>>,
+}
+
+impl From<ApiTokenRow> for ApiToken {
+    fn from(row: ApiTokenRow) -> Self {
+        ApiToken {
+            id: row.id,
+            name: row.name,
+            created_at: row.created_at,
+            last_used_at: row.last_used_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct WebhookRow {
+    id: i64,
+    user_id: i64,
+    url: String,
+    event: String,
+    feed_id: Option<i64>,
+    folder_id: Option<i64>,
+    keyword: Option<String>,
+    enabled: i32,
+    secret: String,
+    created_at: Option<DateTime<Utc>>,
+}
+
+impl From<WebhookRow> for Webhook {
+    fn from(row: WebhookRow) -> Self {
+        Webhook {
+            id: row.id,
+            user_id: row.user_id,
+            url: row.url,
+            // Only ever written from a `WebhookEvent`
+            event: row.event.parse().unwrap_or(WebhookEvent::NewArticle),
+            feed_id: row.feed_id,
+            folder_id: row.folder_id,
+            keyword: row.keyword,
+            enabled: row.enabled != 0,
+            secret: row.secret,
+            created_at: row.created_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct WebhookDeliveryRow {
+    id: i64,
+    webhook_id: i64,
+    delivery_id: String,
+    event: String,
+    attempt: i64,
+    success: i32,
+    status_code: Option<i64>,
+    error: Option<String>,
+    duration_ms: i64,
+    delivered_at: Option<DateTime<Utc>>,
+}
+
+impl From<WebhookDeliveryRow> for WebhookDelivery {
+    fn from(row: WebhookDeliveryRow) -> Self {
+        WebhookDelivery {
+            id: row.id,
+            webhook_id: row.webhook_id,
+            delivery_id: row.delivery_id,
+            event: row.event,
+            attempt: row.attempt,
+            success: row.success != 0,
+            status_code: row.status_code,
+            error: row.error,
+            duration_ms: row.duration_ms,
+            delivered_at: row.delivered_at,
         }
     }
 }
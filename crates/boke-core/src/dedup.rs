@@ -0,0 +1,224 @@
+//! Cross-feed duplicate detection for articles.
+//!
+//! The same story often arrives through several feeds (a site's main feed and
+//! its category feeds, or aggregators reposting a link). Duplicates are matched
+//! by normalized link first and by title similarity as a fallback.
+
+use std::collections::HashSet;
+
+use chrono::Duration;
+use url::Url;
+
+use crate::models::DuplicateCandidate;
+
+/// How far apart two publish dates may be for a title-only match.
+pub const DUPLICATE_WINDOW: Duration = Duration::hours(48);
+
+/// Most articles compared by title; link and GUID matches are always all compared.
+pub const MAX_TITLE_CANDIDATES: i64 = 200;
+
+/// Minimum Jaccard similarity between title word sets to treat them as the same story.
+const TITLE_SIMILARITY_THRESHOLD: f64 = 0.8;
+
+/// Titles with fewer words than this never match on similarity alone.
+const MIN_TITLE_WORDS: usize = 4;
+
+/// Query parameters that only carry tracking information.
+const TRACKING_PARAMS: &[&str] = &[
+    "fbclid", "gclid", "dclid", "msclkid", "yclid", "igshid", "mc_cid", "mc_eid", "_hsenc",
+    "_hsmi", "ref", "ref_src", "ref_url", "cmpid", "spm",
+];
+
+/// Normalize an article link so that copies of the same URL compare equal.
+///
+/// Drops the scheme, `www.` prefix, default ports, fragments, trailing slashes and
+/// tracking parameters (`utm_*`, `fbclid`, ...), and sorts the remaining query.
+pub fn normalize_link(link: &str) -> Option<String> {
+    let url = Url::parse(link.trim()).ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+
+    let host = url.host_str()?.to_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+
+    let mut normalized = host.to_string();
+    if let Some(port) = url.port() {
+        normalized.push_str(&format!(":{}", port));
+    }
+
+    let path = url.path().trim_end_matches('/');
+    normalized.push_str(path);
+
+    let mut params: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(key, _)| !is_tracking_param(key))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    if !params.is_empty() {
+        params.sort();
+        let query = params
+            .iter()
+            .map(|(key, value)| {
+                if value.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}={}", key, value)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("&");
+        normalized.push('?');
+        normalized.push_str(&query);
+    }
+
+    Some(normalized)
+}
+
+/// Whether a GUID is the article's own URL rather than an id that only means something
+/// within its feed, so that other feeds carrying it have the same story.
+pub fn is_permalink_guid(guid: &str) -> bool {
+    normalize_link(guid).is_some()
+}
+
+fn is_tracking_param(key: &str) -> bool {
+    let key = key.to_lowercase();
+    key.starts_with("utm_") || TRACKING_PARAMS.contains(&key.as_str())
+}
+
+/// Split a title into lowercase alphanumeric words.
+fn title_words(title: &str) -> HashSet<String> {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect()
+}
+
+/// Jaccard similarity of the word sets of two titles, from 0.0 to 1.0.
+pub fn title_similarity(a: &str, b: &str) -> f64 {
+    let a = title_words(a);
+    let b = title_words(b);
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let intersection = a.intersection(&b).count();
+    let union = a.union(&b).count();
+    intersection as f64 / union as f64
+}
+
+/// Decide whether a candidate article is a copy of the one being ingested.
+///
+/// Candidates are expected to come from other feeds and, for title matches,
+/// from within [`DUPLICATE_WINDOW`] of the new article.
+pub fn is_duplicate(
+    normalized_link: Option<&str>,
+    guid: &str,
+    title: &str,
+    candidate: &DuplicateCandidate,
+) -> bool {
+    if let (Some(link), Some(other)) = (normalized_link, candidate.normalized_link.as_deref())
+        && link == other
+    {
+        return true;
+    }
+    if guid == candidate.guid && is_permalink_guid(guid) {
+        return true;
+    }
+
+    title_words(title).len() >= MIN_TITLE_WORDS
+        && title_similarity(title, &candidate.title) >= TITLE_SIMILARITY_THRESHOLD
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(title: &str, link: Option<&str>) -> DuplicateCandidate {
+        DuplicateCandidate {
+            id: 1,
+            feed_id: 2,
+            guid: "1".to_string(),
+            title: title.to_string(),
+            normalized_link: link.map(|l| l.to_string()),
+            group_id: None,
+        }
+    }
+
+    #[test]
+    fn normalize_strips_tracking_params() {
+        let link = "https://www.example.com/post/?utm_source=rss&utm_medium=feed&id=3&fbclid=x";
+        assert_eq!(
+            normalize_link(link).as_deref(),
+            Some("example.com/post?id=3")
+        );
+    }
+
+    #[test]
+    fn normalize_ignores_scheme_and_fragment() {
+        assert_eq!(
+            normalize_link("http://Example.com/a/b#comments"),
+            normalize_link("https://example.com/a/b/")
+        );
+    }
+
+    #[test]
+    fn normalize_sorts_query() {
+        assert_eq!(
+            normalize_link("https://example.com/?b=2&a=1"),
+            normalize_link("https://example.com/?a=1&b=2")
+        );
+    }
+
+    #[test]
+    fn normalize_rejects_non_http() {
+        assert_eq!(normalize_link("mailto:someone@example.com"), None);
+        assert_eq!(normalize_link("not a url"), None);
+    }
+
+    #[test]
+    fn similar_titles_match() {
+        let c = candidate("Rust 1.80 released with LazyLock", None);
+        assert!(is_duplicate(
+            None,
+            "a",
+            "Rust 1.80 Released With LazyLock!",
+            &c
+        ));
+    }
+
+    #[test]
+    fn short_titles_do_not_match() {
+        let c = candidate("Weekly update", None);
+        assert!(!is_duplicate(None, "a", "Weekly update", &c));
+    }
+
+    #[test]
+    fn different_titles_do_not_match() {
+        let c = candidate("Rust 1.80 released with LazyLock", None);
+        assert!(!is_duplicate(
+            None,
+            "a",
+            "Go 1.23 adds range over functions",
+            &c
+        ));
+    }
+
+    #[test]
+    fn same_link_matches() {
+        let c = candidate("Something else entirely", Some("example.com/post"));
+        assert!(is_duplicate(Some("example.com/post"), "a", "Title", &c));
+    }
+
+    #[test]
+    fn same_permalink_guid_matches() {
+        let mut c = candidate("Something else entirely", Some("proxy.example.com/1"));
+        c.guid = "https://example.com/post".to_string();
+        assert!(is_duplicate(None, "https://example.com/post", "Title", &c));
+
+        // Plain ids only identify an article within its own feed
+        c.guid = "1".to_string();
+        assert!(!is_duplicate(None, "1", "Title", &c));
+    }
+}
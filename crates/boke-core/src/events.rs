@@ -0,0 +1,140 @@
+//! Change notifications published by the services, for live-updating clients.
+
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+/// Events a slow subscriber may fall behind by before it misses some.
+const BUS_CAPACITY: usize = 1024;
+
+/// Something that changed, as seen by the user it concerns.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AppEvent {
+    RefreshStarted {
+        feed_id: i64,
+    },
+    RefreshFinished {
+        feed_id: i64,
+        new_articles: i64,
+    },
+    RefreshFailed {
+        feed_id: i64,
+        error: String,
+    },
+    /// A refresh brought in new articles.
+    NewArticles {
+        feed_id: i64,
+        count: i64,
+        article_ids: Vec<i64>,
+    },
+    /// An article was marked read or unread, or favorited or unfavorited.
+    ArticleChanged {
+        article_id: i64,
+        is_read: bool,
+        is_favorite: bool,
+    },
+    /// An article became a favorite; follows its `ArticleChanged`.
+    ArticleFavorited {
+        article_id: i64,
+    },
+    /// A label was put on articles that did not have it.
+    ArticlesLabelled {
+        label_id: i64,
+        article_ids: Vec<i64>,
+    },
+    /// Many articles were marked at once; clients should reload their lists.
+    ArticlesMarked {
+        read: bool,
+    },
+    /// A subscription was added, removed or edited; `None` when several changed.
+    FeedsChanged {
+        feed_id: Option<i64>,
+    },
+    FoldersChanged,
+    /// The listener fell behind and missed events; clients should reload everything.
+    Resync,
+}
+
+/// Who an event is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Audience {
+    User(i64),
+    /// Everyone subscribed to the feed. Feeds are shared, so a refresh one user started
+    /// brings new articles to all of them.
+    FeedSubscribers(i64),
+}
+
+#[derive(Debug, Clone)]
+pub struct BusEvent {
+    pub audience: Audience,
+    pub event: AppEvent,
+}
+
+/// Broadcasts [`AppEvent`]s from the services to whoever is listening.
+///
+/// Publishing never blocks or fails; with no subscribers events are dropped.
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<BusEvent>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(BUS_CAPACITY);
+        Self { sender }
+    }
+
+    pub fn publish(&self, audience: Audience, event: AppEvent) {
+        let _ = self.sender.send(BusEvent { audience, event });
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<BusEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subscribers_receive_published_events() {
+        let bus = EventBus::new();
+        let mut receiver = bus.subscribe();
+        bus.publish(Audience::User(1), AppEvent::FoldersChanged);
+
+        let received = receiver.try_recv().unwrap();
+        assert_eq!(received.audience, Audience::User(1));
+        assert_eq!(received.event, AppEvent::FoldersChanged);
+    }
+
+    #[test]
+    fn publishing_without_subscribers_is_fine() {
+        EventBus::new().publish(Audience::FeedSubscribers(1), AppEvent::FoldersChanged);
+    }
+
+    #[test]
+    fn events_serialize_with_type_tag() {
+        let json = serde_json::to_value(AppEvent::NewArticles {
+            feed_id: 3,
+            count: 2,
+            article_ids: vec![10, 11],
+        })
+        .unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "type": "new_articles",
+                "feed_id": 3,
+                "count": 2,
+                "article_ids": [10, 11]
+            })
+        );
+    }
+}
@@ -47,6 +47,8 @@ pub fn parse(xml: &[u8], feed_url: &str) -> Result<Feed, FeedError> {
                             updated: None,
                             categories: Vec::new(),
                             image_url: None,
+                            enclosure_url: None,
+                            enclosure_type: None,
                         });
                     }
                     "author" => in_author = true,
@@ -166,6 +168,7 @@ fn extract_link(
 ) {
     let mut href = String::new();
     let mut rel = String::from("alternate"); // default rel is alternate
+    let mut mime_type = None;
 
     for attr in e.attributes().flatten() {
         let key = std::str::from_utf8(attr.key.as_ref()).unwrap_or("");
@@ -173,10 +176,21 @@ fn extract_link(
         match key {
             "href" => href = val,
             "rel" => rel = val,
+            "type" if !val.is_empty() => mime_type = Some(val),
             _ => {}
         }
     }
 
+    if rel == "enclosure" && in_entry && !href.is_empty() {
+        if let Some(entry) = &mut *current_entry
+            && entry.enclosure_url.is_none()
+        {
+            entry.enclosure_url = Some(href);
+            entry.enclosure_type = mime_type;
+        }
+        return;
+    }
+
     if !href.is_empty() && (rel == "alternate" || rel.is_empty()) {
         if in_entry {
             if let Some(entry) = &mut *current_entry
@@ -43,6 +43,9 @@ pub struct FeedEntry {
     pub updated: Option<DateTime<Utc>>,
     pub categories: Vec<String>,
     pub image_url: Option<String>,
+    /// The first non-image enclosure, e.g. a podcast episode.
+    pub enclosure_url: Option<String>,
+    pub enclosure_type: Option<String>,
 }
 
 // Aliases for service layer
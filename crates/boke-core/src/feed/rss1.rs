@@ -61,6 +61,8 @@ pub fn parse(xml: &[u8], feed_url: &str) -> Result<Feed, FeedError> {
                             updated: None,
                             categories: Vec::new(),
                             image_url: None,
+                            enclosure_url: None,
+                            enclosure_type: None,
                         });
                     }
                     _ => {}
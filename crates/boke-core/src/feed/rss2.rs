@@ -1,5 +1,5 @@
 use quick_xml::Reader;
-use quick_xml::events::Event;
+use quick_xml::events::{BytesStart, Event};
 
 use super::date::parse_date;
 use super::error::FeedError;
@@ -53,25 +53,13 @@ pub fn parse(xml: &[u8], feed_url: &str) -> Result<Feed, FeedError> {
                             updated: None,
                             categories: Vec::new(),
                             image_url: None,
+                            enclosure_url: None,
+                            enclosure_type: None,
                         });
                     }
                     "enclosure" if in_item => {
                         if let Some(ref mut entry) = current_entry {
-                            // Extract image from enclosure
-                            let mut is_image = false;
-                            let mut url = String::new();
-                            for attr in e.attributes().flatten() {
-                                let key = std::str::from_utf8(attr.key.as_ref()).unwrap_or("");
-                                let val = attr.unescape_value().unwrap_or_default().to_string();
-                                match key {
-                                    "url" => url = val,
-                                    "type" if val.starts_with("image/") => is_image = true,
-                                    _ => {}
-                                }
-                            }
-                            if is_image && !url.is_empty() {
-                                entry.image_url = Some(url);
-                            }
+                            apply_enclosure(e, entry);
                         }
                     }
                     _ => {}
@@ -80,6 +68,14 @@ pub fn parse(xml: &[u8], feed_url: &str) -> Result<Feed, FeedError> {
                 current_tag = local;
                 current_ns_tag = full;
             }
+            Ok(Event::Empty(ref e)) => {
+                if in_item
+                    && e.local_name().as_ref() == b"enclosure"
+                    && let Some(ref mut entry) = current_entry
+                {
+                    apply_enclosure(e, entry);
+                }
+            }
             Ok(Event::End(ref e)) => {
                 let local = std::str::from_utf8(e.local_name().as_ref())
                     .unwrap_or("")
@@ -145,6 +141,33 @@ pub fn parse(xml: &[u8], feed_url: &str) -> Result<Feed, FeedError> {
     Ok(feed)
 }
 
+/// Use an image enclosure as the entry's image and keep the first other one as media.
+fn apply_enclosure(e: &BytesStart<'_>, entry: &mut FeedEntry) {
+    let mut url = String::new();
+    let mut mime_type = None;
+    for attr in e.attributes().flatten() {
+        let val = attr.unescape_value().unwrap_or_default().to_string();
+        match attr.key.as_ref() {
+            b"url" => url = val,
+            b"type" if !val.is_empty() => mime_type = Some(val),
+            _ => {}
+        }
+    }
+    if url.is_empty() {
+        return;
+    }
+
+    if mime_type
+        .as_deref()
+        .is_some_and(|t| t.starts_with("image/"))
+    {
+        entry.image_url = Some(url);
+    } else if entry.enclosure_url.is_none() {
+        entry.enclosure_url = Some(url);
+        entry.enclosure_type = mime_type;
+    }
+}
+
 fn apply_text(
     feed: &mut Feed,
     tag: &str,
@@ -179,10 +202,8 @@ fn apply_text(
             "link" => feed.link = text.to_string(),
             "description" => feed.description = Some(text.to_string()),
             "language" => feed.language = Some(text.to_string()),
-            "lastBuildDate" | "pubDate" => {
-                if feed.last_updated.is_none() {
-                    feed.last_updated = parse_date(text);
-                }
+            "lastBuildDate" | "pubDate" if feed.last_updated.is_none() => {
+                feed.last_updated = parse_date(text);
             }
             _ => {}
         }
@@ -245,4 +266,33 @@ mod tests {
         let second = &feed.entries[1];
         assert_eq!(second.id, "https://example.com/post-2");
     }
+
+    #[test]
+    fn test_parse_enclosures() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0">
+  <channel>
+    <title>Podcast</title>
+    <link>https://example.com</link>
+    <item>
+      <title>Episode 1</title>
+      <guid>ep-1</guid>
+      <enclosure url="https://example.com/cover.jpg" type="image/jpeg" length="100"/>
+      <enclosure url="https://example.com/ep1.mp3" type="audio/mpeg" length="1234"/>
+    </item>
+  </channel>
+</rss>"#;
+
+        let feed = parse(xml.as_bytes(), "https://example.com/feed").unwrap();
+        let entry = &feed.entries[0];
+        assert_eq!(
+            entry.image_url.as_deref(),
+            Some("https://example.com/cover.jpg")
+        );
+        assert_eq!(
+            entry.enclosure_url.as_deref(),
+            Some("https://example.com/ep1.mp3")
+        );
+        assert_eq!(entry.enclosure_type.as_deref(), Some("audio/mpeg"));
+    }
 }
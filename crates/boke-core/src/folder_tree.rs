@@ -0,0 +1,172 @@
+//! Assembling the folder hierarchy and guarding it against cycles.
+//!
+//! Folders are stored flat with a `parent_id`; these helpers work on the lists returned
+//! by the database, which are already in display order.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::models::{FeedWithMeta, Folder, FolderNode, FolderTree};
+
+/// Nest folders and feeds, summing unread counts up the tree.
+///
+/// Folders whose parent no longer exists are shown at the top level, and feeds in such
+/// a missing folder are treated as unfiled.
+pub fn build_tree(folders: Vec<Folder>, feeds: Vec<FeedWithMeta>) -> FolderTree {
+    let ids: HashSet<i64> = folders.iter().map(|f| f.id).collect();
+
+    let mut children: HashMap<Option<i64>, Vec<Folder>> = HashMap::new();
+    for folder in folders {
+        let parent = folder.parent_id.filter(|p| ids.contains(p));
+        children.entry(parent).or_default().push(folder);
+    }
+
+    let mut feeds_by_folder: HashMap<Option<i64>, Vec<FeedWithMeta>> = HashMap::new();
+    for feed in feeds {
+        let folder = feed.folder_id.filter(|f| ids.contains(f));
+        feeds_by_folder.entry(folder).or_default().push(feed);
+    }
+
+    let folders = build_level(None, &mut children, &mut feeds_by_folder);
+    let feeds = feeds_by_folder.remove(&None).unwrap_or_default();
+    let unread_count = folders.iter().map(|f| f.folder.unread_count).sum::<i64>()
+        + feeds.iter().map(|f| f.unread_count).sum::<i64>();
+
+    FolderTree {
+        folders,
+        feeds,
+        unread_count,
+    }
+}
+
+fn build_level(
+    parent: Option<i64>,
+    children: &mut HashMap<Option<i64>, Vec<Folder>>,
+    feeds_by_folder: &mut HashMap<Option<i64>, Vec<FeedWithMeta>>,
+) -> Vec<FolderNode> {
+    let folders = children.remove(&parent).unwrap_or_default();
+    folders
+        .into_iter()
+        .map(|mut folder| {
+            let sub = build_level(Some(folder.id), children, feeds_by_folder);
+            let feeds = feeds_by_folder.remove(&Some(folder.id)).unwrap_or_default();
+            // Recount from the nested feeds so the totals always add up in the sidebar
+            folder.unread_count = sub.iter().map(|f| f.folder.unread_count).sum::<i64>()
+                + feeds.iter().map(|f| f.unread_count).sum::<i64>();
+            FolderNode {
+                folder,
+                children: sub,
+                feeds,
+            }
+        })
+        .collect()
+}
+
+/// Whether putting `folder_id` inside `new_parent` would make it its own ancestor.
+pub fn creates_cycle(folders: &[Folder], folder_id: i64, new_parent: i64) -> bool {
+    is_within(folders, new_parent, folder_id)
+}
+
+/// Whether `folder_id` is `ancestor` or one of its sub-folders, at any depth.
+pub fn is_within(folders: &[Folder], folder_id: i64, ancestor: i64) -> bool {
+    let parents: HashMap<i64, Option<i64>> = folders.iter().map(|f| (f.id, f.parent_id)).collect();
+
+    let mut seen = HashSet::new();
+    let mut current = Some(folder_id);
+    while let Some(id) = current {
+        if id == ancestor {
+            return true;
+        }
+        if !seen.insert(id) {
+            // Already looping without passing through `ancestor`
+            return false;
+        }
+        current = parents.get(&id).copied().flatten();
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::FeedStatus;
+
+    fn folder(id: i64, parent_id: Option<i64>) -> Folder {
+        Folder {
+            id,
+            name: format!("folder {id}"),
+            parent_id,
+            position: 0,
+            feed_count: 0,
+            unread_count: 0,
+        }
+    }
+
+    fn feed(id: i64, folder_id: Option<i64>, unread_count: i64) -> FeedWithMeta {
+        FeedWithMeta {
+            id,
+            title: format!("feed {id}"),
+            folder_id,
+            feed_url: format!("https://example.com/{id}"),
+            site_url: None,
+            description: None,
+            language: None,
+            favicon_url: None,
+            last_fetched_at: None,
+            last_build_date: None,
+            created_at: None,
+            updated_at: None,
+            custom_title: None,
+            refresh_interval_minutes: None,
+            fetch_full_content: false,
+            notify: true,
+            status: FeedStatus::Active,
+            position: 0,
+            unread_count,
+        }
+    }
+
+    #[test]
+    fn nests_folders_and_sums_unread() {
+        let tree = build_tree(
+            vec![folder(1, None), folder(2, Some(1)), folder(3, Some(2))],
+            vec![
+                feed(10, Some(1), 1),
+                feed(11, Some(3), 4),
+                feed(12, None, 2),
+            ],
+        );
+
+        assert_eq!(tree.unread_count, 7);
+        assert_eq!(tree.feeds.len(), 1);
+        let top = &tree.folders[0];
+        assert_eq!(top.folder.unread_count, 5);
+        assert_eq!(top.children[0].folder.unread_count, 4);
+        assert_eq!(top.children[0].children[0].feeds[0].id, 11);
+    }
+
+    #[test]
+    fn orphans_move_to_top_level() {
+        let tree = build_tree(vec![folder(2, Some(99))], vec![feed(10, Some(98), 3)]);
+        assert_eq!(tree.folders[0].folder.id, 2);
+        assert_eq!(tree.feeds[0].id, 10);
+        assert_eq!(tree.unread_count, 3);
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let folders = vec![folder(1, None), folder(2, Some(1)), folder(3, Some(2))];
+        assert!(creates_cycle(&folders, 1, 3));
+        assert!(creates_cycle(&folders, 2, 2));
+        assert!(!creates_cycle(&folders, 3, 1));
+        assert!(!creates_cycle(&folders, 1, 4));
+    }
+
+    #[test]
+    fn finds_sub_folders_at_any_depth() {
+        let folders = vec![folder(1, None), folder(2, Some(1)), folder(3, Some(2))];
+        assert!(is_within(&folders, 3, 1));
+        assert!(is_within(&folders, 1, 1));
+        assert!(!is_within(&folders, 1, 3));
+        assert!(!is_within(&folders, 4, 1));
+    }
+}
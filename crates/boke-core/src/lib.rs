@@ -1,14 +1,35 @@
+pub mod auth;
 pub mod db;
+pub mod dedup;
+pub mod events;
 pub mod feed;
+pub mod folder_tree;
+pub mod markdown;
 pub mod models;
 pub mod opml;
+pub mod search;
 pub mod services;
 pub mod utils;
 
 // Re-export commonly used types
-pub use db::{Database, DatabasePool, DbError, DbResult};
+pub use db::{DEFAULT_USER_ID, Database, DatabasePool, DbError, DbResult};
+pub use events::{AppEvent, Audience, BusEvent, EventBus};
 pub use feed::{FeedParser, ParsedFeed, ParsedFeedEntry};
-pub use models::{Article, ArticleQuery, Feed, FeedWithMeta, Folder, NewArticle, NewFeed};
-pub use opml::{OpmlError, parse_opml};
-pub use services::{ArticleService, FeedService, FolderService, RefreshResult};
+pub use models::{
+    ApiToken, Article, ArticleQuery, ArticleScope, ArticleSort, ArticleSource, CreatedApiToken,
+    Feed, FeedFetch, FeedHealth, FeedStatus, FeedUpdate, FeedWithMeta, Folder, FolderDeleteMode,
+    FolderId, FolderNode, FolderTree, HiddenArticle, Highlight, HighlightQuery, Label, NewArticle,
+    NewFeed, NewFeedFetch, NewHighlight, NewLabel, NewSmartFolder, NewUser, NewWebhook,
+    ReadingStats, SearchHit, SmartFolder, UpdateHighlight, User, Webhook, WebhookDelivery,
+    WebhookEvent,
+};
+pub use opml::{
+    OpmlEntry, OpmlError, OpmlFeed, OpmlOutline, feed_entries, folder_paths, parse_opml, write_opml,
+};
+pub use search::SearchQuery;
+pub use services::{
+    ArticleService, FeedService, FolderService, HighlightService, ImportEvent, ImportItem,
+    ImportJob, ImportJobs, ImportResult, ImportStatus, LabelService, RefreshResult,
+    SmartFolderService, StatsService, UserService, WebhookService,
+};
 pub use utils::{extract_article_content, resolve_relative_urls};
@@ -0,0 +1,163 @@
+//! Markdown export of highlights and notes.
+//!
+//! Each article becomes a heading linking to the original, followed by its highlights
+//! as block quotes with the note, if any, as a paragraph underneath.
+
+use crate::models::{Article, Highlight};
+
+/// Render one article's highlights as a Markdown document.
+pub fn article_highlights(article: &Article, highlights: &[Highlight]) -> String {
+    let mut out = String::new();
+    push_article(&mut out, article, highlights, 1);
+    out
+}
+
+/// Render the highlights of several articles under a common title, e.g. a label name.
+pub fn collection_highlights(title: &str, articles: &[(Article, Vec<Highlight>)]) -> String {
+    let mut out = format!("# {}\n", single_line(title));
+    for (article, highlights) in articles {
+        out.push('\n');
+        push_article(&mut out, article, highlights, 2);
+    }
+    out
+}
+
+fn push_article(out: &mut String, article: &Article, highlights: &[Highlight], level: usize) {
+    out.push_str(&"#".repeat(level));
+    out.push(' ');
+    let title = escape_link_text(&single_line(&article.title));
+    match &article.link {
+        Some(link) => out.push_str(&format!("[{}](<{}>)\n", title, link)),
+        None => {
+            out.push_str(&title);
+            out.push('\n');
+        }
+    }
+
+    let date = article
+        .published_at
+        .or(article.created_at)
+        .map(|d| d.format("%Y-%m-%d").to_string());
+    let source: Vec<String> = [article.feed_title.clone(), article.author.clone(), date]
+        .into_iter()
+        .flatten()
+        .map(|s| single_line(&s))
+        .filter(|s| !s.is_empty())
+        .collect();
+    if !source.is_empty() {
+        out.push_str(&format!("\n*{}*\n", source.join(" · ")));
+    }
+
+    for highlight in highlights {
+        out.push('\n');
+        for line in highlight.quote.trim().lines() {
+            let line = line.trim();
+            if line.is_empty() {
+                out.push_str(">\n");
+            } else {
+                out.push_str(&format!("> {}\n", line));
+            }
+        }
+        if let Some(note) = highlight.note.as_deref().map(str::trim)
+            && !note.is_empty()
+        {
+            out.push('\n');
+            out.push_str(note);
+            out.push('\n');
+        }
+    }
+}
+
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn escape_link_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn article(title: &str, link: Option<&str>) -> Article {
+        Article {
+            id: 1,
+            feed_id: 1,
+            guid: "g".to_string(),
+            title: title.to_string(),
+            link: link.map(|l| l.to_string()),
+            author: Some("Ada".to_string()),
+            summary: None,
+            content: None,
+            image_url: None,
+            enclosure_url: None,
+            enclosure_type: None,
+            published_at: Some(Utc.with_ymd_and_hms(2024, 5, 17, 8, 0, 0).unwrap()),
+            is_read: false,
+            is_favorite: false,
+            created_at: None,
+            read_at: None,
+            favorited_at: None,
+            dedup_group_id: None,
+            feed_title: Some("Example Blog".to_string()),
+            feed_favicon_url: None,
+            duplicates: Vec::new(),
+            label_ids: Vec::new(),
+        }
+    }
+
+    fn highlight(quote: &str, note: Option<&str>) -> Highlight {
+        Highlight {
+            id: 1,
+            article_id: 1,
+            article_title: String::new(),
+            quote: quote.to_string(),
+            prefix: None,
+            suffix: None,
+            start_offset: None,
+            end_offset: None,
+            note: note.map(|n| n.to_string()),
+            color: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn renders_article_with_quotes_and_notes() {
+        let md = article_highlights(
+            &article("Ownership [explained]", Some("https://example.com/a")),
+            &[
+                highlight("Each value has\n\nan owner.", Some("Key idea")),
+                highlight("Borrowing", None),
+            ],
+        );
+        assert_eq!(
+            md,
+            "# [Ownership \\[explained\\]](<https://example.com/a>)\n\
+             \n*Example Blog · Ada · 2024-05-17*\n\
+             \n> Each value has\n>\n> an owner.\n\
+             \nKey idea\n\
+             \n> Borrowing\n"
+        );
+    }
+
+    #[test]
+    fn collection_nests_articles_under_title() {
+        let md = collection_highlights(
+            "To share",
+            &[(article("No link", None), vec![highlight("Quote", None)])],
+        );
+        assert!(md.starts_with("# To share\n\n## No link\n"));
+        assert!(md.ends_with("> Quote\n"));
+    }
+}
@@ -1,6 +1,15 @@
 use chrono::{DateTime, Utc};
+use md5::{Digest, Md5};
 use serde::{Deserialize, Serialize};
 
+use super::FolderId;
+use crate::search::SearchQuery;
+
+/// The MD5 hex digest of an article's guid, which the Nextcloud News API names items by.
+pub fn guid_hash(guid: &str) -> String {
+    hex::encode(Md5::digest(guid.as_bytes()))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Article {
     pub id: i64,
@@ -12,13 +21,57 @@ pub struct Article {
     pub summary: Option<String>,
     pub content: Option<String>,
     pub image_url: Option<String>,
+    /// Attached media such as a podcast episode.
+    pub enclosure_url: Option<String>,
+    pub enclosure_type: Option<String>,
     pub published_at: Option<DateTime<Utc>>,
     pub is_read: bool,
     pub is_favorite: bool,
     pub created_at: Option<DateTime<Utc>>,
+    pub read_at: Option<DateTime<Utc>>,
+    pub favorited_at: Option<DateTime<Utc>>,
+    pub dedup_group_id: Option<i64>,
     // Joined fields
     pub feed_title: Option<String>,
     pub feed_favicon_url: Option<String>,
+    /// Copies of this story from other feeds, filled in when duplicates are collapsed.
+    #[serde(default)]
+    pub duplicates: Vec<ArticleSource>,
+    /// Ids of the user labels applied to this article, filled in by the service.
+    #[serde(default)]
+    pub label_ids: Vec<i64>,
+}
+
+/// An article matched by a search, with its relevance and a highlighted excerpt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    #[serde(flatten)]
+    pub article: Article,
+    /// Relevance, higher is better; 0 when the query has no text terms.
+    pub score: f64,
+    /// HTML-escaped excerpt with the matched words wrapped in `<mark>`.
+    pub snippet: Option<String>,
+}
+
+/// Another feed's copy of a story in a duplicate group.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArticleSource {
+    pub group_id: i64,
+    pub article_id: i64,
+    pub feed_id: i64,
+    pub feed_title: Option<String>,
+    pub link: Option<String>,
+}
+
+/// An article from another feed that may be a copy of a newly inserted one.
+#[derive(Debug, Clone)]
+pub struct DuplicateCandidate {
+    pub id: i64,
+    pub feed_id: i64,
+    pub guid: String,
+    pub title: String,
+    pub normalized_link: Option<String>,
+    pub group_id: Option<i64>,
 }
 
 #[derive(Debug, Clone)]
@@ -31,14 +84,105 @@ pub struct NewArticle {
     pub summary: Option<String>,
     pub content: Option<String>,
     pub image_url: Option<String>,
+    pub enclosure_url: Option<String>,
+    pub enclosure_type: Option<String>,
     pub published_at: Option<DateTime<Utc>>,
+    pub normalized_link: Option<String>,
+    /// Feed-provided categories, searchable with `tag:`.
+    pub categories: Vec<String>,
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct ArticleQuery {
     pub feed_id: Option<i64>,
+    /// Only articles from feeds in this folder or its sub-folders, or from unfiled feeds.
+    pub folder_id: Option<FolderId>,
     pub offset: i64,
     pub limit: i64,
     pub unread_only: bool,
     pub favorites_only: bool,
+    /// Show one row per duplicate group, with the other copies in `duplicates`.
+    pub collapse_duplicates: bool,
+    /// Only articles carrying this user label.
+    pub label_id: Option<i64>,
+    /// Only articles matching this search, e.g. a smart folder's query.
+    pub search: Option<SearchQuery>,
+    pub sort: ArticleSort,
+    /// Oldest first instead of newest first.
+    pub oldest_first: bool,
+    /// Published (or first seen) at or after this instant.
+    pub published_after: Option<DateTime<Utc>>,
+    /// Published (or first seen) before this instant.
+    pub published_before: Option<DateTime<Utc>>,
+    /// First fetched at or after this instant.
+    pub created_after: Option<DateTime<Utc>>,
+    /// First fetched before this instant.
+    pub created_before: Option<DateTime<Utc>>,
+    /// Case-insensitive substring of the author.
+    pub author: Option<String>,
+    /// Only articles with an enclosure such as a podcast episode.
+    pub has_enclosure: bool,
+    /// Only articles whose content, or summary if there is none, has at least this many
+    /// characters of HTML.
+    pub min_content_length: Option<i64>,
+    /// Only articles with a larger id, for clients that sync by id.
+    pub after_id: Option<i64>,
+    /// Only articles with a smaller id.
+    pub before_id: Option<i64>,
+    /// Only these articles.
+    pub ids: Option<Vec<i64>>,
+    /// Only articles first fetched, or whose read or starred state changed, at or after
+    /// this instant.
+    pub modified_after: Option<DateTime<Utc>>,
+}
+
+impl ArticleQuery {
+    /// Whether muted feeds' articles are left out. They only show up when that feed,
+    /// the favorites or a label is viewed.
+    pub fn hides_muted(&self) -> bool {
+        self.feed_id.is_none() && !self.favorites_only && self.label_id.is_none()
+    }
+}
+
+/// The timestamp an article list is ordered by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArticleSort {
+    /// Publish date, with undated articles last.
+    #[default]
+    Published,
+    /// When the article was first fetched.
+    FirstSeen,
+    /// Article id, which follows the order articles were stored in.
+    Id,
+}
+
+/// The articles a bulk read/unread update applies to.
+///
+/// Serialized as `{"type": "feed", "id": 3}`, `{"type": "folder", "id": "unfiled"}` or
+/// `{"type": "all"}`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase", tag = "type", content = "id")]
+pub enum ArticleScope {
+    #[default]
+    All,
+    Feed(i64),
+    Folder(FolderId),
+}
+
+impl ArticleScope {
+    /// The query selecting this scope's articles, or `None` for every article.
+    pub fn query(self) -> Option<ArticleQuery> {
+        match self {
+            ArticleScope::All => None,
+            ArticleScope::Feed(feed_id) => Some(ArticleQuery {
+                feed_id: Some(feed_id),
+                ..Default::default()
+            }),
+            ArticleScope::Folder(folder_id) => Some(ArticleQuery {
+                folder_id: Some(folder_id),
+                ..Default::default()
+            }),
+        }
+    }
 }
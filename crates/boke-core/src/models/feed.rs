@@ -1,3 +1,5 @@
+use std::str::FromStr;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -15,6 +17,15 @@ pub struct Feed {
     pub last_build_date: Option<DateTime<Utc>>,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
+    /// User-chosen title; `title` already has it applied.
+    pub custom_title: Option<String>,
+    /// Minimum minutes between refreshes; `None` refreshes on every run.
+    pub refresh_interval_minutes: Option<i64>,
+    /// Replace new items' content with the full page fetched from their link.
+    pub fetch_full_content: bool,
+    /// Count new items from this feed in new-article notifications.
+    pub notify: bool,
+    pub status: FeedStatus,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,6 +42,17 @@ pub struct FeedWithMeta {
     pub last_build_date: Option<DateTime<Utc>>,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
+    /// User-chosen title; `title` already has it applied.
+    pub custom_title: Option<String>,
+    /// Minimum minutes between refreshes; `None` refreshes on every run.
+    pub refresh_interval_minutes: Option<i64>,
+    /// Replace new items' content with the full page fetched from their link.
+    pub fetch_full_content: bool,
+    /// Count new items from this feed in new-article notifications.
+    pub notify: bool,
+    pub status: FeedStatus,
+    /// Sort order among the feeds in the same folder.
+    pub position: i64,
     pub unread_count: i64,
 }
 
@@ -45,3 +67,62 @@ pub struct NewFeed {
     pub favicon_url: Option<String>,
     pub last_build_date: Option<DateTime<Utc>>,
 }
+
+/// Changes to a feed's settings; fields left out keep their current value.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct FeedUpdate {
+    /// Title override; empty to go back to the feed's own title.
+    pub title: Option<String>,
+    pub feed_url: Option<String>,
+    /// Empty to clear.
+    pub site_url: Option<String>,
+    /// 0 to refresh on every run.
+    pub refresh_interval_minutes: Option<i64>,
+    pub fetch_full_content: Option<bool>,
+    pub notify: Option<bool>,
+    pub status: Option<FeedStatus>,
+}
+
+/// Whether a feed is refreshed and where its articles show up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedStatus {
+    #[default]
+    Active,
+    /// Not refreshed until it is resumed.
+    Paused,
+    /// Refreshed, but its articles are left out of "All" and of unread counts.
+    Muted,
+    /// Never refreshed again; its articles are kept and its settings are frozen.
+    Archived,
+}
+
+impl FeedStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FeedStatus::Active => "active",
+            FeedStatus::Paused => "paused",
+            FeedStatus::Muted => "muted",
+            FeedStatus::Archived => "archived",
+        }
+    }
+
+    /// Whether refreshing all feeds fetches this one.
+    pub fn is_refreshed(self) -> bool {
+        matches!(self, FeedStatus::Active | FeedStatus::Muted)
+    }
+}
+
+impl FromStr for FeedStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "active" => Ok(FeedStatus::Active),
+            "paused" => Ok(FeedStatus::Paused),
+            "muted" => Ok(FeedStatus::Muted),
+            "archived" => Ok(FeedStatus::Archived),
+            _ => Err(format!("unknown feed status: {}", s)),
+        }
+    }
+}
@@ -1,8 +1,110 @@
-use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::FeedWithMeta;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Folder {
     pub id: i64,
     pub name: String,
+    pub parent_id: Option<i64>,
+    /// Sort order among the folders sharing a parent.
+    pub position: i64,
     pub feed_count: i64,
+    /// Unread articles in this folder's feeds and in all of its sub-folders.
+    pub unread_count: i64,
+}
+
+/// A folder to restrict articles to, or the feeds that are not in any folder.
+///
+/// Serialized as the folder's id or the string `"unfiled"`; a numeric string is
+/// accepted as well so the same type works in query strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FolderId {
+    Folder(i64),
+    Unfiled,
+}
+
+impl FromStr for FolderId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("unfiled") {
+            return Ok(FolderId::Unfiled);
+        }
+        s.parse()
+            .map(FolderId::Folder)
+            .map_err(|_| format!("invalid folder id: {}", s))
+    }
+}
+
+impl Serialize for FolderId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            FolderId::Folder(id) => serializer.serialize_i64(*id),
+            FolderId::Unfiled => serializer.serialize_str("unfiled"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for FolderId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FolderIdVisitor;
+
+        impl Visitor<'_> for FolderIdVisitor {
+            type Value = FolderId;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a folder id or \"unfiled\"")
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<FolderId, E> {
+                Ok(FolderId::Folder(v))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<FolderId, E> {
+                i64::try_from(v)
+                    .map(FolderId::Folder)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(v), &self))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<FolderId, E> {
+                v.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(FolderIdVisitor)
+    }
+}
+
+/// What happens to a folder's sub-folders and feeds when it is deleted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FolderDeleteMode {
+    /// Move them up into the deleted folder's parent.
+    #[default]
+    Reparent,
+    /// Delete them as well, along with the feeds' articles.
+    Cascade,
+}
+
+/// A folder with its sub-folders and feeds in display order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FolderNode {
+    #[serde(flatten)]
+    pub folder: Folder,
+    pub children: Vec<FolderNode>,
+    pub feeds: Vec<FeedWithMeta>,
+}
+
+/// The whole subscription list as shown in the sidebar.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FolderTree {
+    pub folders: Vec<FolderNode>,
+    /// Feeds that are not in any folder.
+    pub feeds: Vec<FeedWithMeta>,
+    pub unread_count: i64,
 }
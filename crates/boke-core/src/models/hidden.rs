@@ -0,0 +1,14 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A hidden or deleted article, remembered by guid so that refreshes do not bring it back.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HiddenArticle {
+    pub id: i64,
+    pub feed_id: i64,
+    pub guid: String,
+    pub title: String,
+    /// The hidden article, or `None` once it has been deleted.
+    pub article_id: Option<i64>,
+    pub hidden_at: Option<DateTime<Utc>>,
+}
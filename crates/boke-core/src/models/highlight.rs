@@ -0,0 +1,56 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::search::SearchQuery;
+
+/// A passage the user marked while reading, with an optional note.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Highlight {
+    pub id: i64,
+    pub article_id: i64,
+    pub article_title: String,
+    /// The highlighted text as it appeared in the article.
+    pub quote: String,
+    /// Text right before and after the quote, used to find it again when the offsets
+    /// no longer line up, e.g. after the article content was re-fetched.
+    pub prefix: Option<String>,
+    pub suffix: Option<String>,
+    /// Character range of the quote within the article's text content, when known.
+    pub start_offset: Option<i64>,
+    pub end_offset: Option<i64>,
+    pub note: Option<String>,
+    pub color: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewHighlight {
+    pub article_id: i64,
+    pub quote: String,
+    pub prefix: Option<String>,
+    pub suffix: Option<String>,
+    pub start_offset: Option<i64>,
+    pub end_offset: Option<i64>,
+    pub note: Option<String>,
+    pub color: Option<String>,
+}
+
+/// The editable parts of a highlight; the anchor stays fixed once created.
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateHighlight {
+    pub note: Option<String>,
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HighlightQuery {
+    pub article_id: Option<i64>,
+    /// Only highlights on articles carrying this user label.
+    pub label_id: Option<i64>,
+    /// Terms are matched against the quote and note; filters apply to the article.
+    pub search: Option<SearchQuery>,
+    pub offset: i64,
+    /// `None` returns every matching highlight.
+    pub limit: Option<i64>,
+}
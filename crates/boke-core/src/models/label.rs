@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// A user-applied tag such as "to-share" or "follow-up", separate from feed categories.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Label {
+    pub id: i64,
+    pub name: String,
+    pub color: Option<String>,
+    pub article_count: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewLabel {
+    pub name: String,
+    pub color: Option<String>,
+}
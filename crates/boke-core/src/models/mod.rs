@@ -1,7 +1,26 @@
 mod article;
 mod feed;
 mod folder;
+mod hidden;
+mod highlight;
+mod label;
+mod smart_folder;
+mod stats;
+mod user;
+mod webhook;
 
-pub use article::{Article, ArticleQuery, NewArticle};
-pub use feed::{Feed, FeedWithMeta, NewFeed};
-pub use folder::Folder;
+pub use article::{
+    Article, ArticleQuery, ArticleScope, ArticleSort, ArticleSource, DuplicateCandidate,
+    NewArticle, SearchHit, guid_hash,
+};
+pub use feed::{Feed, FeedStatus, FeedUpdate, FeedWithMeta, NewFeed};
+pub use folder::{Folder, FolderDeleteMode, FolderId, FolderNode, FolderTree};
+pub use hidden::HiddenArticle;
+pub use highlight::{Highlight, HighlightQuery, NewHighlight, UpdateHighlight};
+pub use label::{Label, NewLabel};
+pub use smart_folder::{NewSmartFolder, SmartFolder};
+pub use stats::{DailyReadCount, FeedFetch, FeedHealth, FeedReadStats, NewFeedFetch, ReadingStats};
+pub use user::{ApiToken, CreatedApiToken, NewUser, User};
+pub use webhook::{
+    CreatedWebhook, NewWebhook, NewWebhookDelivery, Webhook, WebhookDelivery, WebhookEvent,
+};
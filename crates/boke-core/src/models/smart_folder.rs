@@ -0,0 +1,21 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A saved search shown alongside feeds, e.g. `rust folder:Tech is:unread after:7d`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmartFolder {
+    pub id: i64,
+    pub name: String,
+    /// Query in the search language of [`crate::search`], re-evaluated on every use.
+    pub query: String,
+    pub created_at: Option<DateTime<Utc>>,
+    /// Unread articles currently matching the query, filled in by the service.
+    #[serde(default)]
+    pub unread_count: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewSmartFolder {
+    pub name: String,
+    pub query: String,
+}
@@ -0,0 +1,76 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Reading activity derived from article `read_at` timestamps.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadingStats {
+    pub reads_per_day: Vec<DailyReadCount>,
+    pub feeds: Vec<FeedReadStats>,
+    /// Average seconds between an article's publish date and when it was read.
+    pub avg_seconds_to_read: Option<f64>,
+    /// Feeds with articles of which none were ever read.
+    pub never_read_feeds: Vec<FeedReadStats>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyReadCount {
+    pub day: NaiveDate,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedReadStats {
+    pub feed_id: i64,
+    pub feed_title: String,
+    pub total_articles: i64,
+    pub read_articles: i64,
+    pub read_ratio: f64,
+    pub avg_seconds_to_read: Option<f64>,
+    pub last_read_at: Option<DateTime<Utc>>,
+}
+
+/// One attempt to fetch a feed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedFetch {
+    pub id: i64,
+    pub feed_id: i64,
+    pub fetched_at: Option<DateTime<Utc>>,
+    pub success: bool,
+    pub status_code: Option<i64>,
+    pub duration_ms: i64,
+    /// Size of the response body.
+    pub bytes: Option<i64>,
+    pub new_articles: i64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NewFeedFetch {
+    pub feed_id: i64,
+    pub success: bool,
+    pub status_code: Option<i64>,
+    pub duration_ms: i64,
+    pub bytes: Option<i64>,
+    pub new_articles: i64,
+    pub error: Option<String>,
+}
+
+/// How active and reliable a feed is, for deciding what to unsubscribe from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedHealth {
+    pub feed_id: i64,
+    /// Average entries per week, by publish date, over the activity window.
+    pub posts_per_week: f64,
+    /// Publish (or first seen) date of the newest entry.
+    pub last_entry_at: Option<DateTime<Utc>>,
+    /// Fetches in the recorded history, which keeps only the most recent ones.
+    pub fetch_count: i64,
+    /// Share of recorded fetches that succeeded, from 0 to 1.
+    pub success_rate: Option<f64>,
+    pub avg_response_ms: Option<f64>,
+    pub avg_response_bytes: Option<f64>,
+    /// Nothing new was published since the dead-feed cutoff.
+    pub is_dead: bool,
+    /// Latest fetches, newest first.
+    pub recent_fetches: Vec<FeedFetch>,
+}
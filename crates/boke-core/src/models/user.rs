@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// An account on a shared server, with its own subscriptions, folders and read state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct User {
+    pub id: i64,
+    pub username: String,
+    pub is_admin: bool,
+    /// Disabled users keep their data but cannot use the server.
+    pub disabled: bool,
+    /// Users without a password can only sign in with an API token.
+    pub has_password: bool,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct NewUser {
+    pub username: String,
+    #[serde(default)]
+    pub is_admin: bool,
+    #[serde(default)]
+    pub password: Option<String>,
+}
+
+/// A long-lived token for scripts and third-party clients. The secret itself is only
+/// shown when the token is created.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiToken {
+    pub id: i64,
+    pub name: String,
+    pub created_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+/// A freshly created API token along with its secret.
+#[derive(Debug, Clone, Serialize)]
+pub struct CreatedApiToken {
+    #[serde(flatten)]
+    pub token: ApiToken,
+    pub secret: String,
+}
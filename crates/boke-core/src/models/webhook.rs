@@ -0,0 +1,119 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// What a webhook is called for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    /// A refresh stored a new article in a feed the owner subscribes to.
+    NewArticle,
+    /// The owner favorited an article.
+    Favorited,
+    /// The owner put a label on an article.
+    Labelled,
+}
+
+impl WebhookEvent {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WebhookEvent::NewArticle => "new_article",
+            WebhookEvent::Favorited => "favorited",
+            WebhookEvent::Labelled => "labelled",
+        }
+    }
+}
+
+impl FromStr for WebhookEvent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "new_article" => Ok(WebhookEvent::NewArticle),
+            "favorited" => Ok(WebhookEvent::Favorited),
+            "labelled" => Ok(WebhookEvent::Labelled),
+            _ => Err(format!("unknown webhook event: {}", s)),
+        }
+    }
+}
+
+/// A URL that is sent a signed POST whenever its event happens to an article matching
+/// its filters. The signing secret is only shown when the webhook is created.
+#[derive(Debug, Clone, Serialize)]
+pub struct Webhook {
+    pub id: i64,
+    #[serde(skip)]
+    pub user_id: i64,
+    pub url: String,
+    pub event: WebhookEvent,
+    /// Only articles from this feed.
+    pub feed_id: Option<i64>,
+    /// Only articles from feeds in this folder or its sub-folders.
+    pub folder_id: Option<i64>,
+    /// Only articles whose title or text contains this, ignoring case.
+    pub keyword: Option<String>,
+    pub enabled: bool,
+    #[serde(skip)]
+    pub secret: String,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewWebhook {
+    pub url: String,
+    pub event: WebhookEvent,
+    #[serde(default)]
+    pub feed_id: Option<i64>,
+    #[serde(default)]
+    pub folder_id: Option<i64>,
+    #[serde(default)]
+    pub keyword: Option<String>,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    /// Signing secret to use instead of a generated one; ignored on update.
+    #[serde(default)]
+    pub secret: Option<String>,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+/// A freshly created webhook along with its signing secret.
+#[derive(Debug, Clone, Serialize)]
+pub struct CreatedWebhook {
+    #[serde(flatten)]
+    pub webhook: Webhook,
+    pub secret: String,
+}
+
+/// One attempt at delivering a webhook call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub webhook_id: i64,
+    /// Shared by every attempt at the same call, and sent as `X-Boke-Delivery`.
+    pub delivery_id: String,
+    /// The webhook's event, or `ping` for a test call.
+    pub event: String,
+    /// 1 for the first try.
+    pub attempt: i64,
+    pub success: bool,
+    pub status_code: Option<i64>,
+    pub error: Option<String>,
+    pub duration_ms: i64,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NewWebhookDelivery {
+    pub webhook_id: i64,
+    pub delivery_id: String,
+    pub event: String,
+    pub attempt: i64,
+    pub success: bool,
+    pub status_code: Option<i64>,
+    pub error: Option<String>,
+    pub duration_ms: i64,
+}
@@ -1,7 +1,11 @@
-//! OPML parsing for feed import/export.
+//! OPML parsing and writing for feed import/export.
 
+use crate::models::{FeedWithMeta, FolderNode, FolderTree};
+use chrono::{DateTime, Utc};
 use quick_xml::Reader;
-use quick_xml::events::Event;
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, Event};
+use std::fmt::Write;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -13,33 +17,88 @@ pub enum OpmlError {
     NoFeeds,
 }
 
-/// Parse an OPML file and extract feed URLs.
+/// One `<outline>` in an OPML body: a folder of further outlines, or a subscription.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OpmlOutline {
+    Folder {
+        title: String,
+        children: Vec<OpmlOutline>,
+    },
+    Feed(OpmlFeed),
+}
+
+/// A subscription outline, as written by the exporting reader.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OpmlFeed {
+    /// `xmlUrl`, or `url` for outlines that only carry a link.
+    pub url: String,
+    /// The user's name for the feed: `title`, falling back to `text`.
+    pub title: Option<String>,
+    pub html_url: Option<String>,
+    pub description: Option<String>,
+    /// Folder paths from the `category` attribute; `/Tech/Rust` becomes `["Tech", "Rust"]`.
+    pub categories: Vec<Vec<String>>,
+}
+
+/// A feed to subscribe to and the folder path it belongs in (empty for the top level).
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpmlEntry {
+    pub folder_path: Vec<String>,
+    pub feed: OpmlFeed,
+}
+
+/// Parse an OPML file into its outline tree.
 ///
-/// Returns a list of feed URLs found in the OPML file.
-pub fn parse_opml(xml: &str) -> Result<Vec<String>, OpmlError> {
+/// Outlines with an `xmlUrl` (or only a `url`, as some exporters write for `type="link"`)
+/// are feeds; any other outline is a folder named by its `title` or `text`.
+pub fn parse_opml(xml: &str) -> Result<Vec<OpmlOutline>, OpmlError> {
     let mut reader = Reader::from_str(xml);
-    let mut urls = Vec::new();
     let mut buf = Vec::new();
+    // Open outlines; the bottom frame collects the body's top-level outlines.
+    let mut stack = vec![Frame::default()];
 
     loop {
         match reader.read_event_into(&mut buf) {
-            Ok(Event::Empty(ref e)) | Ok(Event::Start(ref e))
-                if e.name().as_ref() == b"outline" =>
-            {
-                let mut xml_url = None;
-                for attr in e.attributes().flatten() {
-                    if (attr.key.as_ref() == b"xmlUrl" || attr.key.as_ref() == b"xmlurl")
-                        && let Ok(val) = attr.unescape_value()
-                    {
-                        let url = val.to_string();
-                        if !url.is_empty() {
-                            xml_url = Some(url);
+            Ok(Event::Start(ref e)) if e.name().as_ref() == b"outline" => {
+                let frame = match parse_outline(e) {
+                    Parsed::Feed(feed) => {
+                        // A feed with nested outlines keeps them in its own folder.
+                        push(&mut stack, OpmlOutline::Feed(feed));
+                        Frame::default()
+                    }
+                    Parsed::Folder(title) => Frame {
+                        title: Some(title),
+                        children: Vec::new(),
+                    },
+                };
+                stack.push(frame);
+            }
+            Ok(Event::Empty(ref e)) if e.name().as_ref() == b"outline" => {
+                let outline = match parse_outline(e) {
+                    Parsed::Feed(feed) => OpmlOutline::Feed(feed),
+                    Parsed::Folder(title) => OpmlOutline::Folder {
+                        title,
+                        children: Vec::new(),
+                    },
+                };
+                push(&mut stack, outline);
+            }
+            Ok(Event::End(ref e)) if e.name().as_ref() == b"outline" && stack.len() > 1 => {
+                let frame = stack.pop().expect("stack has an open outline");
+                match frame.title {
+                    Some(title) => push(
+                        &mut stack,
+                        OpmlOutline::Folder {
+                            title,
+                            children: frame.children,
+                        },
+                    ),
+                    None => {
+                        for child in frame.children {
+                            push(&mut stack, child);
                         }
                     }
                 }
-                if let Some(url) = xml_url {
-                    urls.push(url);
-                }
             }
             Ok(Event::Eof) => break,
             Err(e) => return Err(OpmlError::ParseError(e.to_string())),
@@ -48,16 +107,349 @@ pub fn parse_opml(xml: &str) -> Result<Vec<String>, OpmlError> {
         buf.clear();
     }
 
-    if urls.is_empty() {
+    // Close any outlines left open by a truncated file.
+    while stack.len() > 1 {
+        let frame = stack.pop().expect("stack has an open outline");
+        let outline = OpmlOutline::Folder {
+            title: frame.title.unwrap_or_default(),
+            children: frame.children,
+        };
+        push(&mut stack, outline);
+    }
+    let outlines = stack.pop().map(|f| f.children).unwrap_or_default();
+
+    if feed_entries(&outlines).is_empty() {
         return Err(OpmlError::NoFeeds);
     }
 
-    Ok(urls)
+    Ok(outlines)
+}
+
+/// Every feed in the tree, in document order, with the folder path to file it under.
+///
+/// Feeds outside any folder use their first `category` as the path, so flat exports that
+/// record folders as categories still import into folders.
+pub fn feed_entries(outlines: &[OpmlOutline]) -> Vec<OpmlEntry> {
+    let mut entries = Vec::new();
+    collect_entries(outlines, &mut Vec::new(), &mut entries);
+    entries
+}
+
+/// Every named folder in the tree as a path from the top level, parents before children.
+pub fn folder_paths(outlines: &[OpmlOutline]) -> Vec<Vec<String>> {
+    let mut paths = Vec::new();
+    collect_folders(outlines, &mut Vec::new(), &mut paths);
+    paths
+}
+
+fn collect_folders(outlines: &[OpmlOutline], path: &mut Vec<String>, out: &mut Vec<Vec<String>>) {
+    for outline in outlines {
+        if let OpmlOutline::Folder { title, children } = outline
+            && !title.is_empty()
+        {
+            path.push(title.clone());
+            out.push(path.clone());
+            collect_folders(children, path, out);
+            path.pop();
+        }
+    }
+}
+
+fn collect_entries(outlines: &[OpmlOutline], path: &mut Vec<String>, out: &mut Vec<OpmlEntry>) {
+    for outline in outlines {
+        match outline {
+            OpmlOutline::Folder { title, children } => {
+                let named = !title.is_empty();
+                if named {
+                    path.push(title.clone());
+                }
+                collect_entries(children, path, out);
+                if named {
+                    path.pop();
+                }
+            }
+            OpmlOutline::Feed(feed) => {
+                let folder_path = if path.is_empty() {
+                    feed.categories.first().cloned().unwrap_or_default()
+                } else {
+                    path.clone()
+                };
+                out.push(OpmlEntry {
+                    folder_path,
+                    feed: feed.clone(),
+                });
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct Frame {
+    /// `None` for the body, or for the children of a feed outline.
+    title: Option<String>,
+    children: Vec<OpmlOutline>,
+}
+
+enum Parsed {
+    Feed(OpmlFeed),
+    Folder(String),
+}
+
+fn push(stack: &mut [Frame], outline: OpmlOutline) {
+    if let Some(frame) = stack.last_mut() {
+        frame.children.push(outline);
+    }
+}
+
+fn parse_outline(e: &BytesStart) -> Parsed {
+    let mut xml_url = None;
+    let mut url = None;
+    let mut title = None;
+    let mut text = None;
+    let mut html_url = None;
+    let mut description = None;
+    let mut category = None;
+
+    for attr in e.attributes().flatten() {
+        let Ok(value) = attr.unescape_value() else {
+            continue;
+        };
+        let value = value.trim();
+        if value.is_empty() {
+            continue;
+        }
+        let slot = match attr.key.as_ref().to_ascii_lowercase().as_slice() {
+            b"xmlurl" => &mut xml_url,
+            b"url" => &mut url,
+            b"title" => &mut title,
+            b"text" => &mut text,
+            b"htmlurl" => &mut html_url,
+            b"description" => &mut description,
+            b"category" => &mut category,
+            _ => continue,
+        };
+        *slot = Some(value.to_string());
+    }
+
+    let title = title.or(text);
+    match xml_url.or(url) {
+        Some(url) => Parsed::Feed(OpmlFeed {
+            url,
+            title,
+            html_url,
+            description,
+            categories: category
+                .as_deref()
+                .map(parse_categories)
+                .unwrap_or_default(),
+        }),
+        None => Parsed::Folder(title.unwrap_or_default()),
+    }
+}
+
+/// Split a `category` attribute: comma-separated, each a `/`-separated path.
+fn parse_categories(category: &str) -> Vec<Vec<String>> {
+    category
+        .split(',')
+        .map(|c| {
+            c.split('/')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .filter(|path| !path.is_empty())
+        .collect()
+}
+
+/// Write a subscription list as OPML 2.0, with folders as nested outlines.
+pub fn write_opml(tree: &FolderTree, title: &str, created_at: DateTime<Utc>) -> String {
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<opml version=\"2.0\">\n");
+    out.push_str("  <head>\n");
+    let _ = writeln!(out, "    <title>{}</title>", escape(title));
+    let _ = writeln!(
+        out,
+        "    <dateCreated>{}</dateCreated>",
+        created_at.to_rfc2822()
+    );
+    out.push_str("  </head>\n");
+    out.push_str("  <body>\n");
+    for folder in &tree.folders {
+        write_folder(&mut out, folder, 2);
+    }
+    for feed in &tree.feeds {
+        write_feed(&mut out, feed, 2);
+    }
+    out.push_str("  </body>\n");
+    out.push_str("</opml>\n");
+    out
+}
+
+fn write_folder(out: &mut String, node: &FolderNode, depth: usize) {
+    let indent = "  ".repeat(depth);
+    let name = escape(&node.folder.name);
+    if node.children.is_empty() && node.feeds.is_empty() {
+        let _ = writeln!(
+            out,
+            "{}<outline text=\"{}\" title=\"{}\"/>",
+            indent, name, name
+        );
+        return;
+    }
+    let _ = writeln!(
+        out,
+        "{}<outline text=\"{}\" title=\"{}\">",
+        indent, name, name
+    );
+    for child in &node.children {
+        write_folder(out, child, depth + 1);
+    }
+    for feed in &node.feeds {
+        write_feed(out, feed, depth + 1);
+    }
+    let _ = writeln!(out, "{}</outline>", indent);
+}
+
+fn write_feed(out: &mut String, feed: &FeedWithMeta, depth: usize) {
+    let title = escape(&feed.title);
+    let _ = write!(
+        out,
+        "{}<outline type=\"rss\" text=\"{}\" title=\"{}\" xmlUrl=\"{}\"",
+        "  ".repeat(depth),
+        title,
+        title,
+        escape(&feed.feed_url)
+    );
+    if let Some(site_url) = feed.site_url.as_deref().filter(|u| !u.is_empty()) {
+        let _ = write!(out, " htmlUrl=\"{}\"", escape(site_url));
+    }
+    if let Some(description) = feed.description.as_deref().filter(|d| !d.is_empty()) {
+        let _ = write!(out, " description=\"{}\"", escape(description));
+    }
+    out.push_str("/>\n");
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::{FeedStatus, Folder};
+
+    fn feed(id: i64, title: &str, url: &str) -> FeedWithMeta {
+        FeedWithMeta {
+            id,
+            title: title.to_string(),
+            folder_id: None,
+            feed_url: url.to_string(),
+            site_url: None,
+            description: None,
+            language: None,
+            favicon_url: None,
+            last_fetched_at: None,
+            last_build_date: None,
+            created_at: None,
+            updated_at: None,
+            custom_title: None,
+            refresh_interval_minutes: None,
+            fetch_full_content: false,
+            notify: true,
+            status: FeedStatus::Active,
+            position: 0,
+            unread_count: 0,
+        }
+    }
+
+    fn folder(
+        id: i64,
+        name: &str,
+        children: Vec<FolderNode>,
+        feeds: Vec<FeedWithMeta>,
+    ) -> FolderNode {
+        FolderNode {
+            folder: Folder {
+                id,
+                name: name.to_string(),
+                parent_id: None,
+                position: 0,
+                feed_count: feeds.len() as i64,
+                unread_count: 0,
+            },
+            children,
+            feeds,
+        }
+    }
+
+    #[test]
+    fn write_nested_opml() {
+        let mut news = feed(1, "News & Views", "https://example.com/feed?a=1&b=2");
+        news.site_url = Some("https://example.com/".to_string());
+        news.description = Some("Daily \"news\"".to_string());
+        let tree = FolderTree {
+            folders: vec![
+                folder(
+                    1,
+                    "Tech",
+                    vec![folder(
+                        2,
+                        "Rust",
+                        vec![],
+                        vec![feed(2, "This Week", "https://rust/feed")],
+                    )],
+                    vec![news],
+                ),
+                folder(3, "Empty", vec![], vec![]),
+            ],
+            feeds: vec![feed(3, "Loose", "https://loose/rss")],
+            unread_count: 0,
+        };
+        let created = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let opml = write_opml(&tree, "Boke subscriptions", created);
+
+        assert_eq!(
+            opml,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<opml version="2.0">
+  <head>
+    <title>Boke subscriptions</title>
+    <dateCreated>Tue, 14 Nov 2023 22:13:20 +0000</dateCreated>
+  </head>
+  <body>
+    <outline text="Tech" title="Tech">
+      <outline text="Rust" title="Rust">
+        <outline type="rss" text="This Week" title="This Week" xmlUrl="https://rust/feed"/>
+      </outline>
+      <outline type="rss" text="News &amp; Views" title="News &amp; Views" xmlUrl="https://example.com/feed?a=1&amp;b=2" htmlUrl="https://example.com/" description="Daily &quot;news&quot;"/>
+    </outline>
+    <outline text="Empty" title="Empty"/>
+    <outline type="rss" text="Loose" title="Loose" xmlUrl="https://loose/rss"/>
+  </body>
+</opml>
+"#
+        );
+    }
+
+    #[test]
+    fn written_opml_parses_back() {
+        let tree = FolderTree {
+            folders: vec![folder(
+                1,
+                "A",
+                vec![],
+                vec![feed(1, "One", "https://one/feed")],
+            )],
+            feeds: vec![feed(2, "Two", "https://two/feed?x=1&y=2")],
+            unread_count: 0,
+        };
+        let outlines = parse_opml(&write_opml(&tree, "Test", Utc::now())).unwrap();
+        let entries = feed_entries(&outlines);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].folder_path, vec!["A"]);
+        assert_eq!(entries[0].feed.url, "https://one/feed");
+        assert_eq!(entries[0].feed.title.as_deref(), Some("One"));
+        assert!(entries[1].folder_path.is_empty());
+        assert_eq!(entries[1].feed.url, "https://two/feed?x=1&y=2");
+    }
 
     #[test]
     fn parse_simple_opml() {
@@ -71,10 +463,25 @@ mod tests {
   </body>
 </opml>"#;
 
-        let urls = parse_opml(opml).unwrap();
-        assert_eq!(urls.len(), 2);
-        assert!(urls.contains(&"https://news.ycombinator.com/rss".to_string()));
-        assert!(urls.contains(&"https://lobste.rs/rss".to_string()));
+        let outlines = parse_opml(opml).unwrap();
+        assert_eq!(
+            outlines,
+            vec![OpmlOutline::Folder {
+                title: "Tech".to_string(),
+                children: vec![
+                    OpmlOutline::Feed(OpmlFeed {
+                        url: "https://news.ycombinator.com/rss".to_string(),
+                        title: Some("Hacker News".to_string()),
+                        ..Default::default()
+                    }),
+                    OpmlOutline::Feed(OpmlFeed {
+                        url: "https://lobste.rs/rss".to_string(),
+                        title: Some("Lobsters".to_string()),
+                        ..Default::default()
+                    }),
+                ],
+            }]
+        );
     }
 
     #[test]
@@ -86,9 +493,111 @@ mod tests {
   </body>
 </opml>"#;
 
-        let urls = parse_opml(opml).unwrap();
-        assert_eq!(urls.len(), 1);
-        assert_eq!(urls[0], "https://example.com/feed.xml");
+        let entries = feed_entries(&parse_opml(opml).unwrap());
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].feed.url, "https://example.com/feed.xml");
+        assert!(entries[0].folder_path.is_empty());
+    }
+
+    #[test]
+    fn parse_nested_folders_and_custom_titles() {
+        let opml = r#"<?xml version="1.0"?>
+<opml version="2.0">
+  <body>
+    <outline text="Tech">
+      <outline title="Rust">
+        <outline text="ignored" title="My Rust Blog" xmlUrl="https://rust/feed" htmlUrl="https://rust/"/>
+      </outline>
+      <outline text="Lobsters" xmlUrl="https://lobste.rs/rss"/>
+    </outline>
+    <outline text="Empty"/>
+  </body>
+</opml>"#;
+
+        let outlines = parse_opml(opml).unwrap();
+        assert_eq!(outlines.len(), 2);
+        assert_eq!(
+            outlines[1],
+            OpmlOutline::Folder {
+                title: "Empty".to_string(),
+                children: vec![],
+            }
+        );
+
+        assert_eq!(
+            folder_paths(&outlines),
+            vec![vec!["Tech"], vec!["Tech", "Rust"], vec!["Empty"]]
+        );
+
+        let entries = feed_entries(&outlines);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].folder_path, vec!["Tech", "Rust"]);
+        assert_eq!(entries[0].feed.title.as_deref(), Some("My Rust Blog"));
+        assert_eq!(entries[0].feed.html_url.as_deref(), Some("https://rust/"));
+        assert_eq!(entries[1].folder_path, vec!["Tech"]);
+        assert_eq!(entries[1].feed.title.as_deref(), Some("Lobsters"));
+    }
+
+    #[test]
+    fn parse_url_only_and_link_outlines() {
+        let opml = r#"<opml version="1.0">
+  <body>
+    <outline type="link" text="Blog" url="https://blog.example.com/"/>
+    <outline url="https://example.com/atom.xml"/>
+    <outline XMLURL="https://shouty.example.com/rss"/>
+  </body>
+</opml>"#;
+
+        let urls: Vec<_> = feed_entries(&parse_opml(opml).unwrap())
+            .into_iter()
+            .map(|e| e.feed.url)
+            .collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://blog.example.com/",
+                "https://example.com/atom.xml",
+                "https://shouty.example.com/rss",
+            ]
+        );
+    }
+
+    #[test]
+    fn categories_become_folders_for_top_level_feeds() {
+        let opml = r#"<opml version="2.0">
+  <body>
+    <outline text="A" xmlUrl="https://a/feed" category="/Tech/Rust, /News"/>
+    <outline text="Folder">
+      <outline text="B" xmlUrl="https://b/feed" category="/Other"/>
+    </outline>
+  </body>
+</opml>"#;
+
+        let entries = feed_entries(&parse_opml(opml).unwrap());
+        assert_eq!(
+            entries[0].feed.categories,
+            vec![vec!["Tech", "Rust"], vec!["News"]]
+        );
+        assert_eq!(entries[0].folder_path, vec!["Tech", "Rust"]);
+        // An enclosing folder wins over the category.
+        assert_eq!(entries[1].folder_path, vec!["Folder"]);
+    }
+
+    #[test]
+    fn feed_outline_children_stay_in_its_folder() {
+        let opml = r#"<opml version="2.0">
+  <body>
+    <outline text="Folder">
+      <outline text="Parent" xmlUrl="https://parent/feed">
+        <outline text="Child" xmlUrl="https://child/feed"/>
+      </outline>
+    </outline>
+  </body>
+</opml>"#;
+
+        let entries = feed_entries(&parse_opml(opml).unwrap());
+        assert_eq!(entries.len(), 2);
+        assert!(entries.iter().all(|e| e.folder_path == vec!["Folder"]));
     }
 
     #[test]
@@ -0,0 +1,519 @@
+//! Structured search query language.
+//!
+//! A query is a list of whitespace-separated terms. Besides plain words it supports
+//! quoted phrases, negation with a leading `-`, and the filters `feed:`, `folder:`,
+//! `author:`, `tag:`, `label:`, `is:unread`, `is:read`, `is:starred`, `before:` and
+//! `after:`.
+//! Dates are either `YYYY-MM-DD` or relative to now (`7d`, `2w`, `3m`, `1y`).
+//!
+//! Chinese, Japanese and Korean terms can't be split into words by the full-text
+//! tokenizers, so they are matched as substrings instead of through the index.
+//!
+//! Parsing never fails: anything that is not a recognised filter is searched as text,
+//! and the backends bind every value as a parameter, so user input never reaches SQL.
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchQuery {
+    pub terms: Vec<SearchTerm>,
+    pub filters: Vec<SearchFilter>,
+}
+
+/// A word or phrase to look up in the full-text index.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchTerm {
+    pub text: String,
+    pub phrase: bool,
+    pub negated: bool,
+}
+
+impl SearchTerm {
+    /// Whether the term is written in a script without spaces between words.
+    pub fn is_unsegmented(&self) -> bool {
+        self.text.chars().any(is_cjk)
+    }
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}' // Hiragana, Katakana
+        | '\u{3400}'..='\u{4DBF}' // CJK Extension A
+        | '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+        | '\u{AC00}'..='\u{D7AF}' // Hangul syllables
+        | '\u{F900}'..='\u{FAFF}' // CJK Compatibility Ideographs
+        | '\u{FF66}'..='\u{FF9F}' // Halfwidth Katakana
+    )
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchFilter {
+    pub field: FilterField,
+    pub negated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterField {
+    /// Feed id, or a case-insensitive substring of the feed title.
+    Feed(String),
+    /// Folder name, compared case-insensitively.
+    Folder(String),
+    /// Case-insensitive substring of the author.
+    Author(String),
+    /// Feed-provided category, compared case-insensitively.
+    Tag(String),
+    /// User label name, compared case-insensitively.
+    Label(String),
+    Unread,
+    Starred,
+    /// Published (or first seen) before this instant.
+    Before(DateTime<Utc>),
+    /// Published (or first seen) at or after this instant.
+    After(DateTime<Utc>),
+}
+
+impl SearchQuery {
+    /// Parse a query, resolving relative dates against the current time.
+    pub fn parse(input: &str) -> Self {
+        Self::parse_at(input, Utc::now())
+    }
+
+    /// Parse a query, resolving relative dates against `now`.
+    pub fn parse_at(input: &str, now: DateTime<Utc>) -> Self {
+        let mut query = SearchQuery::default();
+
+        for token in tokenize(input) {
+            if token.value.is_empty() {
+                continue;
+            }
+
+            if !token.quoted
+                && let Some((field, inverted)) = parse_filter(&token.value, now)
+            {
+                query.filters.push(SearchFilter {
+                    field,
+                    negated: token.negated != inverted,
+                });
+                continue;
+            }
+
+            query.terms.push(SearchTerm {
+                text: token.value,
+                phrase: token.quoted,
+                negated: token.negated,
+            });
+        }
+
+        query
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty() && self.filters.is_empty()
+    }
+
+    /// Indexed terms that must match.
+    pub fn positive_terms(&self) -> impl Iterator<Item = &SearchTerm> {
+        self.terms
+            .iter()
+            .filter(|t| !t.negated && !t.is_unsegmented())
+    }
+
+    /// Indexed terms that must not match.
+    pub fn negative_terms(&self) -> impl Iterator<Item = &SearchTerm> {
+        self.terms
+            .iter()
+            .filter(|t| t.negated && !t.is_unsegmented())
+    }
+
+    /// Terms matched as substrings rather than through the index.
+    pub fn substring_terms(&self) -> impl Iterator<Item = &SearchTerm> {
+        self.terms.iter().filter(|t| t.is_unsegmented())
+    }
+
+    /// An FTS5 `MATCH` expression requiring every positive term.
+    ///
+    /// Each term is emitted as a quoted string, so FTS5 operators and stray quotes in
+    /// user input are matched literally instead of being parsed.
+    pub fn fts5_match(&self) -> Option<String> {
+        let terms: Vec<String> = self.positive_terms().map(|t| fts5_quote(&t.text)).collect();
+        if terms.is_empty() {
+            None
+        } else {
+            Some(terms.join(" "))
+        }
+    }
+}
+
+/// Marks the start of a match in raw snippets produced by the database.
+pub const HIGHLIGHT_START: char = '\u{2}';
+/// Marks the end of a match in raw snippets produced by the database.
+pub const HIGHLIGHT_END: char = '\u{3}';
+
+/// Turn a raw database snippet into HTML-escaped text with matches in `<mark>`.
+///
+/// Snippets are cut from HTML content, so tags (including ones cut in half at
+/// either end) are dropped and entities decoded before the text is re-escaped.
+pub fn highlight_snippet(raw: &str) -> String {
+    // A snippet starting inside a tag begins with its tail, e.g. `ref="/a">text`
+    let raw = match (raw.find('>'), raw.find('<')) {
+        (Some(end), start) if start.is_none_or(|s| end < s) && raw[..end].contains('=') => {
+            &raw[end + 1..]
+        }
+        _ => raw,
+    };
+
+    let mut text = String::with_capacity(raw.len());
+    let mut in_tag = false;
+    for c in raw.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    let text = decode_entities(&text);
+    let mut html = String::with_capacity(text.len());
+    let mut marked = false;
+    let mut pending_space = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            pending_space = !html.is_empty();
+            continue;
+        }
+        if pending_space {
+            html.push(' ');
+            pending_space = false;
+        }
+        match c {
+            HIGHLIGHT_START if !marked => {
+                html.push_str("<mark>");
+                marked = true;
+            }
+            HIGHLIGHT_END if marked => {
+                html.push_str("</mark>");
+                marked = false;
+            }
+            HIGHLIGHT_START | HIGHLIGHT_END => {}
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            _ => html.push(c),
+        }
+    }
+    if marked {
+        html.push_str("</mark>");
+    }
+    html
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Quote a string as an FTS5 string literal.
+pub fn fts5_quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\"\""))
+}
+
+/// Escape `%`, `_` and `\` for a `LIKE ... ESCAPE '\'` pattern.
+pub fn like_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// A `LIKE` pattern matching `text` anywhere, lowercased for case-insensitive use.
+pub fn like_contains(text: &str) -> String {
+    format!("%{}%", like_escape(&text.to_lowercase()))
+}
+
+struct Token {
+    value: String,
+    quoted: bool,
+    negated: bool,
+}
+
+fn tokenize(input: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let mut negated = false;
+        if c == '-' {
+            negated = true;
+            chars.next();
+        }
+
+        let mut value = String::new();
+        let mut quoted = false;
+        let mut in_quotes = false;
+        while let Some(&c) = chars.peek() {
+            if c == '"' {
+                chars.next();
+                if in_quotes {
+                    in_quotes = false;
+                } else {
+                    in_quotes = true;
+                    quoted = true;
+                }
+                continue;
+            }
+            if c.is_whitespace() && !in_quotes {
+                break;
+            }
+            value.push(c);
+            chars.next();
+        }
+
+        // `feed:"Hacker News"` quotes the filter value, not a phrase
+        if quoted && value.contains(':') && !value.starts_with(':') {
+            let (key, _) = value.split_once(':').unwrap_or_default();
+            if is_filter_key(key) {
+                quoted = false;
+            }
+        }
+
+        tokens.push(Token {
+            value: value.trim().to_string(),
+            quoted,
+            negated,
+        });
+    }
+
+    tokens
+}
+
+fn is_filter_key(key: &str) -> bool {
+    matches!(
+        key.to_lowercase().as_str(),
+        "feed" | "folder" | "author" | "tag" | "label" | "is" | "before" | "after"
+    )
+}
+
+/// Parse a `key:value` token into a filter and whether its sense is inverted
+/// (`is:read` is stored as a negated `is:unread`).
+fn parse_filter(token: &str, now: DateTime<Utc>) -> Option<(FilterField, bool)> {
+    let (key, value) = token.split_once(':')?;
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+
+    let field = match key.to_lowercase().as_str() {
+        "feed" => FilterField::Feed(value.to_string()),
+        "folder" => FilterField::Folder(value.to_string()),
+        "author" => FilterField::Author(value.to_string()),
+        "tag" => FilterField::Tag(value.to_string()),
+        "label" => FilterField::Label(value.to_string()),
+        "is" => match value.to_lowercase().as_str() {
+            "unread" => FilterField::Unread,
+            "read" => return Some((FilterField::Unread, true)),
+            "starred" | "favorite" => FilterField::Starred,
+            _ => return None,
+        },
+        "before" => FilterField::Before(parse_date_value(value, now)?),
+        "after" => FilterField::After(parse_date_value(value, now)?),
+        _ => return None,
+    };
+    Some((field, false))
+}
+
+fn parse_date_value(value: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Some(date.and_hms_opt(0, 0, 0)?.and_utc());
+    }
+
+    let unit = value.chars().last()?;
+    let amount: i64 = value[..value.len() - unit.len_utf8()].parse().ok()?;
+    let days_per_unit = match unit.to_ascii_lowercase() {
+        'd' => 1,
+        'w' => 7,
+        'm' => 30,
+        'y' => 365,
+        _ => return None,
+    };
+    // Amounts too large to be a date are as unparseable as any other bad value
+    let days = amount.checked_mul(days_per_unit)?;
+    now.checked_sub_signed(Duration::try_days(days)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        NaiveDate::from_ymd_opt(2024, 6, 15)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+            .and_utc()
+    }
+
+    fn term(text: &str, phrase: bool, negated: bool) -> SearchTerm {
+        SearchTerm {
+            text: text.to_string(),
+            phrase,
+            negated,
+        }
+    }
+
+    #[test]
+    fn parse_plain_words() {
+        let q = SearchQuery::parse_at("rust async", now());
+        assert_eq!(
+            q.terms,
+            vec![term("rust", false, false), term("async", false, false)]
+        );
+        assert!(q.filters.is_empty());
+    }
+
+    #[test]
+    fn parse_phrase_and_negation() {
+        let q = SearchQuery::parse_at(r#""borrow checker" -python"#, now());
+        assert_eq!(
+            q.terms,
+            vec![
+                term("borrow checker", true, false),
+                term("python", false, true)
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_filters() {
+        let q = SearchQuery::parse_at(
+            r#"feed:"Hacker News" folder:Tech author:bob tag:rust label:"to share" is:unread -is:starred"#,
+            now(),
+        );
+        assert!(q.terms.is_empty());
+        let fields: Vec<_> = q.filters.iter().map(|f| (&f.field, f.negated)).collect();
+        assert_eq!(
+            fields,
+            vec![
+                (&FilterField::Feed("Hacker News".to_string()), false),
+                (&FilterField::Folder("Tech".to_string()), false),
+                (&FilterField::Author("bob".to_string()), false),
+                (&FilterField::Tag("rust".to_string()), false),
+                (&FilterField::Label("to share".to_string()), false),
+                (&FilterField::Unread, false),
+                (&FilterField::Starred, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn is_read_is_negated_unread() {
+        let q = SearchQuery::parse_at("is:read", now());
+        assert_eq!(q.filters.len(), 1);
+        assert_eq!(q.filters[0].field, FilterField::Unread);
+        assert!(q.filters[0].negated);
+    }
+
+    #[test]
+    fn parse_dates() {
+        let q = SearchQuery::parse_at("after:2024-01-01 before:7d", now());
+        assert_eq!(
+            q.filters[0].field,
+            FilterField::After(
+                NaiveDate::from_ymd_opt(2024, 1, 1)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap()
+                    .and_utc()
+            )
+        );
+        assert_eq!(
+            q.filters[1].field,
+            FilterField::Before(now() - Duration::days(7))
+        );
+    }
+
+    #[test]
+    fn out_of_range_dates_become_text() {
+        let q = SearchQuery::parse_at(
+            "after:99999999999999d after:2000000000000000y before:-9999999999w",
+            now(),
+        );
+        assert!(q.filters.is_empty());
+        assert_eq!(q.terms.len(), 3);
+    }
+
+    #[test]
+    fn invalid_filters_become_text() {
+        let q = SearchQuery::parse_at("before:someday is:weird http://x.com", now());
+        assert!(q.filters.is_empty());
+        assert_eq!(q.terms.len(), 3);
+    }
+
+    #[test]
+    fn stray_punctuation_is_harmless() {
+        let q = SearchQuery::parse_at(r#"- c++ "unterminated"#, now());
+        assert_eq!(
+            q.terms,
+            vec![term("c++", false, false), term("unterminated", true, false)]
+        );
+        assert_eq!(q.fts5_match().as_deref(), Some(r#""c++" "unterminated""#));
+    }
+
+    #[test]
+    fn cjk_terms_use_substring_match() {
+        let q = SearchQuery::parse_at("東京 tokyo -ラーメン", now());
+        assert_eq!(q.positive_terms().count(), 1);
+        assert_eq!(q.negative_terms().count(), 0);
+        assert_eq!(q.substring_terms().count(), 2);
+        assert_eq!(q.fts5_match().as_deref(), Some(r#""tokyo""#));
+    }
+
+    #[test]
+    fn fts5_quote_escapes_quotes() {
+        assert_eq!(fts5_quote(r#"say "hi""#), r#""say ""hi""""#);
+    }
+
+    #[test]
+    fn highlight_strips_tags_and_marks_matches() {
+        let raw = "…<p>Learn \u{2}Rust\u{3} &amp; <a href=\"/x\">more</a></p>";
+        assert_eq!(
+            highlight_snippet(raw),
+            "… Learn <mark>Rust</mark> &amp; more"
+        );
+    }
+
+    #[test]
+    fn highlight_drops_cut_off_tags() {
+        let raw = "ref=\"/a\">the \u{2}borrow\u{3} checker<img src=\"x";
+        assert_eq!(highlight_snippet(raw), "the <mark>borrow</mark> checker");
+    }
+
+    #[test]
+    fn highlight_balances_marks() {
+        assert_eq!(highlight_snippet("a <b \u{2}x>y\u{3}</b>"), "a y");
+        assert_eq!(highlight_snippet("\u{2}open"), "<mark>open</mark>");
+    }
+
+    #[test]
+    fn like_escape_special_chars() {
+        assert_eq!(like_contains("50%_Off"), r"%50\%\_off%");
+    }
+}
@@ -1,10 +1,13 @@
 use crate::db::{Database, DbResult};
-use crate::models::{Article, ArticleQuery};
+use crate::events::{AppEvent, Audience, EventBus};
+use crate::models::{Article, ArticleQuery, ArticleScope, HiddenArticle, SearchHit, guid_hash};
+use crate::search::SearchQuery;
 use std::sync::Arc;
 
 pub struct ArticleService<D: Database> {
     db: Arc<D>,
     http_client: reqwest::Client,
+    events: EventBus,
 }
 
 impl<D: Database> ArticleService<D> {
@@ -15,39 +18,216 @@ impl<D: Database> ArticleService<D> {
             .build()
             .expect("Failed to create HTTP client");
 
-        Self { db, http_client }
+        Self {
+            db,
+            http_client,
+            events: EventBus::default(),
+        }
+    }
+
+    /// Publish changes to `events` instead of a bus nobody listens to.
+    pub fn with_events(mut self, events: EventBus) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// The same service acting for another user, sharing the HTTP client and event bus.
+    pub fn for_user(&self, user_id: i64) -> Self {
+        Self {
+            db: Arc::new(self.db.for_user(user_id)),
+            http_client: self.http_client.clone(),
+            events: self.events.clone(),
+        }
+    }
+
+    fn publish(&self, event: AppEvent) {
+        self.events
+            .publish(Audience::User(self.db.user_id()), event);
+    }
+
+    /// Announce an article's read and favorite state after it changed.
+    async fn publish_state(&self, id: i64) -> DbResult<Option<Article>> {
+        let article = self.db.get_article(id).await?;
+        if let Some(article) = &article {
+            self.publish(AppEvent::ArticleChanged {
+                article_id: id,
+                is_read: article.is_read,
+                is_favorite: article.is_favorite,
+            });
+        }
+        Ok(article)
+    }
+
+    /// Announce an article's state after its favorite flag changed, and that it became a
+    /// favorite if it was not one before.
+    async fn publish_favorite(&self, id: i64, was_favorite: bool) -> DbResult<()> {
+        if let Some(article) = self.publish_state(id).await?
+            && article.is_favorite
+            && !was_favorite
+        {
+            self.publish(AppEvent::ArticleFavorited { article_id: id });
+        }
+        Ok(())
     }
 
     pub async fn get_articles(&self, query: ArticleQuery) -> DbResult<Vec<Article>> {
-        self.db.get_articles(&query).await
+        let mut articles = self.db.get_articles(&query).await?;
+        if query.collapse_duplicates {
+            self.attach_duplicates(&mut articles).await?;
+        }
+        self.attach_labels(&mut articles).await?;
+        Ok(articles)
+    }
+
+    /// Fill in `label_ids` from the article-label assignments.
+    async fn attach_labels(&self, articles: &mut [Article]) -> DbResult<()> {
+        let ids: Vec<i64> = articles.iter().map(|a| a.id).collect();
+        let pairs = self.db.get_article_labels(&ids).await?;
+
+        for article in articles.iter_mut() {
+            article.label_ids = pairs
+                .iter()
+                .filter(|(article_id, _)| *article_id == article.id)
+                .map(|(_, label_id)| *label_id)
+                .collect();
+        }
+
+        Ok(())
+    }
+
+    /// Fill in `duplicates` for articles that represent a collapsed duplicate group.
+    async fn attach_duplicates(&self, articles: &mut [Article]) -> DbResult<()> {
+        let group_ids: Vec<i64> = articles.iter().filter_map(|a| a.dedup_group_id).collect();
+        let sources = self.db.get_duplicate_sources(&group_ids).await?;
+
+        for article in articles.iter_mut() {
+            let Some(group_id) = article.dedup_group_id else {
+                continue;
+            };
+            article.duplicates = sources
+                .iter()
+                .filter(|s| s.group_id == group_id && s.article_id != article.id)
+                .cloned()
+                .collect();
+        }
+
+        Ok(())
     }
 
     pub async fn get_article(&self, id: i64) -> DbResult<Option<Article>> {
-        self.db.get_article(id).await
+        let Some(mut article) = self.db.get_article(id).await? else {
+            return Ok(None);
+        };
+        self.attach_labels(std::slice::from_mut(&mut article))
+            .await?;
+        Ok(Some(article))
+    }
+
+    /// Ids of every article matching a query, without paging.
+    pub async fn get_article_ids(&self, query: &ArticleQuery) -> DbResult<Vec<i64>> {
+        self.db.get_article_ids(query).await
+    }
+
+    /// Count every article matching a query, without paging.
+    pub async fn count_articles(&self, query: &ArticleQuery) -> DbResult<i64> {
+        self.db.count_articles(query).await
     }
 
     pub async fn toggle_read(&self, id: i64) -> DbResult<()> {
-        self.db.toggle_read(id).await
+        self.db.toggle_read(id).await?;
+        self.publish_state(id).await?;
+        Ok(())
+    }
+
+    pub async fn set_read(&self, id: i64, read: bool) -> DbResult<()> {
+        self.db.set_read(id, read).await?;
+        self.publish_state(id).await?;
+        Ok(())
+    }
+
+    pub async fn mark_all_read(&self, scope: ArticleScope) -> DbResult<()> {
+        self.db.mark_all_read(scope).await?;
+        self.publish(AppEvent::ArticlesMarked { read: true });
+        Ok(())
+    }
+
+    /// Mark every article matching a query as read, e.g. everything with a label.
+    pub async fn mark_articles_read(&self, query: &ArticleQuery) -> DbResult<()> {
+        self.db.mark_articles_read(query).await?;
+        self.publish(AppEvent::ArticlesMarked { read: true });
+        Ok(())
+    }
+
+    pub async fn mark_all_unread(&self, scope: ArticleScope) -> DbResult<()> {
+        self.db.mark_all_unread(scope).await?;
+        self.publish(AppEvent::ArticlesMarked { read: false });
+        Ok(())
+    }
+
+    /// Hide an article everywhere; it stays hidden across refreshes until unhidden.
+    pub async fn hide_article(&self, id: i64) -> DbResult<HiddenArticle> {
+        self.db.hide_article(id).await
     }
 
-    pub async fn mark_all_read(&self, feed_id: Option<i64>) -> DbResult<()> {
-        self.db.mark_all_read(feed_id).await
+    /// Delete an article for good, keeping only what is needed to skip it on refresh.
+    pub async fn delete_article(&self, id: i64) -> DbResult<HiddenArticle> {
+        self.db.delete_article(id).await
     }
 
-    pub async fn mark_all_unread(&self, feed_id: Option<i64>) -> DbResult<()> {
-        self.db.mark_all_unread(feed_id).await
+    pub async fn get_hidden_articles(&self, feed_id: Option<i64>) -> DbResult<Vec<HiddenArticle>> {
+        self.db.get_hidden_articles(feed_id).await
+    }
+
+    /// Undo a hide or delete. A deleted article returns on its feed's next refresh, if the
+    /// feed still lists it.
+    pub async fn unhide_article(&self, hidden_id: i64) -> DbResult<()> {
+        self.db.unhide_article(hidden_id).await
     }
 
     pub async fn toggle_favorite(&self, id: i64) -> DbResult<()> {
-        self.db.toggle_favorite(id).await
+        self.db.toggle_favorite(id).await?;
+        self.publish_favorite(id, false).await
+    }
+
+    /// The article in a feed whose guid has the given [`guid_hash`].
+    pub async fn find_by_guid_hash(&self, feed_id: i64, hash: &str) -> DbResult<Option<i64>> {
+        let guids = self.db.get_feed_guids(feed_id).await?;
+        Ok(guids
+            .into_iter()
+            .find(|(_, guid)| guid_hash(guid).eq_ignore_ascii_case(hash))
+            .map(|(id, _)| id))
+    }
+
+    pub async fn set_favorite(&self, id: i64, favorite: bool) -> DbResult<()> {
+        let was_favorite = self
+            .db
+            .get_article(id)
+            .await?
+            .is_some_and(|a| a.is_favorite);
+        self.db.set_favorite(id, favorite).await?;
+        self.publish_favorite(id, was_favorite).await
     }
 
     pub async fn get_favorites_count(&self) -> DbResult<i64> {
         self.db.get_favorites_count().await
     }
 
-    pub async fn search_articles(&self, query: &str, limit: i64) -> DbResult<Vec<Article>> {
-        self.db.search_articles(query, limit).await
+    /// Rebuild the search index, e.g. after feed languages have changed.
+    pub async fn reindex_search(&self) -> DbResult<()> {
+        self.db.reindex_search().await
+    }
+
+    pub async fn search_articles(
+        &self,
+        query: &str,
+        limit: i64,
+        offset: i64,
+    ) -> DbResult<Vec<SearchHit>> {
+        let query = SearchQuery::parse(query);
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.db.search_articles(&query, limit, offset).await
     }
 
     pub async fn fetch_article_content(&self, id: i64) -> anyhow::Result<String> {
@@ -1,11 +1,22 @@
-use crate::db::{Database, DbResult, InsertResult};
-use crate::feed::{FeedParser, discovery};
-use crate::models::{FeedWithMeta, NewArticle, NewFeed};
+use crate::db::{Database, DbError, DbResult, InsertResult};
+use crate::dedup;
+use crate::events::{AppEvent, Audience, EventBus};
+use crate::feed::{FeedParser, ParsedFeed, ParsedFeedEntry, discovery};
+use crate::models::{
+    FeedStatus, FeedUpdate, FeedWithMeta, Folder, NewArticle, NewFeed, NewFeedFetch,
+};
+use crate::opml::{self, OpmlFeed, OpmlOutline};
+use crate::utils::{extract_article_content, resolve_relative_urls};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashSet;
 use std::sync::Arc;
+use std::time::Instant;
 
+#[derive(Clone)]
 pub struct FeedService<D: Database> {
     db: Arc<D>,
     http_client: reqwest::Client,
+    events: EventBus,
 }
 
 impl<D: Database> FeedService<D> {
@@ -16,7 +27,36 @@ impl<D: Database> FeedService<D> {
             .build()
             .expect("Failed to create HTTP client");
 
-        Self { db, http_client }
+        Self {
+            db,
+            http_client,
+            events: EventBus::default(),
+        }
+    }
+
+    /// Publish changes to `events` instead of a bus nobody listens to.
+    pub fn with_events(mut self, events: EventBus) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// The same service acting for another user, sharing the HTTP client and event bus.
+    pub fn for_user(&self, user_id: i64) -> Self {
+        Self {
+            db: Arc::new(self.db.for_user(user_id)),
+            http_client: self.http_client.clone(),
+            events: self.events.clone(),
+        }
+    }
+
+    pub(crate) fn user_id(&self) -> i64 {
+        self.db.user_id()
+    }
+
+    /// Tell the acting user about a change.
+    pub(crate) fn publish(&self, event: AppEvent) {
+        self.events
+            .publish(Audience::User(self.db.user_id()), event);
     }
 
     pub async fn get_feeds(&self) -> DbResult<Vec<FeedWithMeta>> {
@@ -24,6 +64,13 @@ impl<D: Database> FeedService<D> {
     }
 
     pub async fn add_feed(&self, url: &str) -> anyhow::Result<FeedWithMeta> {
+        let download = self.download_feed(url).await?;
+        self.subscribe(&download).await
+    }
+
+    /// The network half of [`add_feed`](Self::add_feed): discover, fetch and parse the feed,
+    /// and fetch its favicon.
+    async fn download_feed(&self, url: &str) -> anyhow::Result<FeedDownload> {
         // Discover feed URL if needed
         let feed_url = match discovery::discover(url).await {
             Ok(feeds) if !feeds.is_empty() => feeds[0].url.clone(),
@@ -35,6 +82,19 @@ impl<D: Database> FeedService<D> {
         let body = response.text().await?;
         let parsed = FeedParser::parse(&body, &feed_url)?;
 
+        // Best effort
+        let favicon = match parsed.site_url() {
+            Some(site_url) => self.fetch_favicon(site_url).await.ok(),
+            None => None,
+        };
+
+        Ok(FeedDownload { parsed, favicon })
+    }
+
+    /// The database half of [`add_feed`](Self::add_feed).
+    async fn subscribe(&self, download: &FeedDownload) -> anyhow::Result<FeedWithMeta> {
+        let parsed = &download.parsed;
+
         // Insert feed into database
         let new_feed = NewFeed {
             title: parsed.title.clone(),
@@ -51,31 +111,17 @@ impl<D: Database> FeedService<D> {
 
         // Insert articles
         for entry in parsed.items() {
-            let new_article = NewArticle {
-                feed_id,
-                guid: entry.guid().to_string(),
-                title: entry.title.clone(),
-                link: if entry.link.is_empty() {
-                    None
-                } else {
-                    Some(entry.link.clone())
-                },
-                author: entry.author.clone(),
-                summary: entry.summary.clone(),
-                content: entry.content.clone(),
-                image_url: entry.image_url.clone(),
-                published_at: entry.published_at(),
-            };
-            let _ = self.db.insert_article(&new_article).await;
+            let _ = self.insert_entry(feed_id, entry).await;
         }
 
-        // Fetch favicon in background (best effort)
-        if let Some(site_url) = parsed.site_url()
-            && let Ok(favicon) = self.fetch_favicon(site_url).await
-        {
-            let _ = self.db.update_feed_favicon(feed_id, &favicon).await;
+        if let Some(favicon) = &download.favicon {
+            let _ = self.db.update_feed_favicon(feed_id, favicon).await;
         }
 
+        self.publish(AppEvent::FeedsChanged {
+            feed_id: Some(feed_id),
+        });
+
         // Return the feed with metadata
         let feeds = self.db.get_feeds().await?;
         feeds
@@ -84,56 +130,274 @@ impl<D: Database> FeedService<D> {
             .ok_or_else(|| anyhow::anyhow!("Feed not found after insert"))
     }
 
+    /// Work out what importing an OPML outline tree would do, creating its folders.
+    ///
+    /// Missing folders, empty ones included, are created (matched by name, case-insensitively,
+    /// under the same parent). Feeds already subscribed, or listed twice, are marked as
+    /// duplicates and left where they are.
+    pub(crate) async fn plan_import(&self, outlines: &[OpmlOutline]) -> DbResult<Vec<PlannedFeed>> {
+        let mut folders = self.db.get_folders().await?;
+        let mut existing_urls: HashSet<_> = self
+            .db
+            .get_feeds()
+            .await?
+            .iter()
+            .map(|f| f.feed_url.to_lowercase())
+            .collect();
+
+        let folder_count = folders.len();
+        for path in opml::folder_paths(outlines) {
+            self.ensure_folder_path(&path, &mut folders).await?;
+        }
+        let mut planned = Vec::new();
+        for entry in opml::feed_entries(outlines) {
+            let duplicate = !existing_urls.insert(entry.feed.url.to_lowercase());
+            let folder_id = self
+                .ensure_folder_path(&entry.folder_path, &mut folders)
+                .await?;
+            planned.push(PlannedFeed {
+                feed: entry.feed,
+                folder_id,
+                duplicate,
+            });
+        }
+        if folders.len() > folder_count {
+            self.publish(AppEvent::FoldersChanged);
+        }
+        Ok(planned)
+    }
+
+    /// Subscribe to one imported feed, filing it under `folder_id` and keeping a title that
+    /// differs from the publisher's as the feed's custom title.
+    ///
+    /// Fetching runs freely; the database writes wait for `writes`, so concurrent imports
+    /// don't trip over SQLite's single writer.
+    pub(crate) async fn import_feed(
+        &self,
+        imported: &OpmlFeed,
+        folder_id: Option<i64>,
+        writes: &tokio::sync::Mutex<()>,
+    ) -> anyhow::Result<FeedWithMeta> {
+        let download = self.download_feed(&imported.url).await?;
+
+        let _writing = writes.lock().await;
+        let feed = self.subscribe(&download).await?;
+
+        if folder_id.is_some() {
+            self.db.move_feed_to_folder(feed.id, folder_id).await?;
+        }
+        if let Some(title) = imported.title.as_deref()
+            && title != feed.title
+        {
+            let update = FeedUpdate {
+                title: Some(title.to_string()),
+                ..Default::default()
+            };
+            self.db.update_feed(feed.id, &update).await?;
+        }
+
+        Ok(feed)
+    }
+
+    /// Put feeds imported into a folder after the ones already there, in file order rather
+    /// than the order their fetches happened to finish.
+    pub(crate) async fn order_imported(
+        &self,
+        folder_id: Option<i64>,
+        imported_ids: &[i64],
+    ) -> DbResult<()> {
+        let mut existing: Vec<_> = self
+            .db
+            .get_feeds()
+            .await?
+            .into_iter()
+            .filter(|f| f.folder_id == folder_id && !imported_ids.contains(&f.id))
+            .collect();
+        existing.sort_by_key(|f| f.position);
+        let ids: Vec<_> = existing
+            .iter()
+            .map(|f| f.id)
+            .chain(imported_ids.iter().copied())
+            .collect();
+        self.db.reorder_feeds(folder_id, &ids).await
+    }
+
+    /// The folder at `path`, creating any missing folders along the way.
+    async fn ensure_folder_path(
+        &self,
+        path: &[String],
+        folders: &mut Vec<Folder>,
+    ) -> DbResult<Option<i64>> {
+        let mut parent_id = None;
+        for name in path {
+            let existing = folders
+                .iter()
+                .find(|f| f.parent_id == parent_id && f.name.eq_ignore_ascii_case(name));
+            let id = match existing {
+                Some(folder) => folder.id,
+                None => {
+                    let folder = self.db.create_folder(name, parent_id).await?;
+                    let id = folder.id;
+                    folders.push(folder);
+                    id
+                }
+            };
+            parent_id = Some(id);
+        }
+        Ok(parent_id)
+    }
+
     pub async fn remove_feed(&self, feed_id: i64) -> DbResult<()> {
-        self.db.delete_feed(feed_id).await
+        self.db.delete_feed(feed_id).await?;
+        self.publish(AppEvent::FeedsChanged {
+            feed_id: Some(feed_id),
+        });
+        Ok(())
     }
 
+    /// Change a feed's title, URLs or refresh behaviour, returning the updated feed.
+    ///
+    /// An archived feed's settings are frozen; only its status can change.
+    pub async fn update_feed(&self, feed_id: i64, update: &FeedUpdate) -> DbResult<FeedWithMeta> {
+        let update = normalize_update(update)?;
+        let feed = self
+            .db
+            .get_feed(feed_id)
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("feed {}", feed_id)))?;
+        let changes_settings = FeedUpdate {
+            status: None,
+            ..update.clone()
+        } != FeedUpdate::default();
+        if feed.status == FeedStatus::Archived && changes_settings {
+            return Err(DbError::InvalidInput(
+                "archived feeds cannot be edited; unarchive it first".to_string(),
+            ));
+        }
+        self.db.update_feed(feed_id, &update).await?;
+        self.publish(AppEvent::FeedsChanged {
+            feed_id: Some(feed_id),
+        });
+
+        self.db
+            .get_feeds()
+            .await?
+            .into_iter()
+            .find(|f| f.id == feed_id)
+            .ok_or_else(|| DbError::NotFound(format!("feed {}", feed_id)))
+    }
+
+    /// Move a feed between active, paused, muted and archived.
+    pub async fn set_feed_status(
+        &self,
+        feed_id: i64,
+        status: FeedStatus,
+    ) -> DbResult<FeedWithMeta> {
+        let update = FeedUpdate {
+            status: Some(status),
+            ..Default::default()
+        };
+        self.update_feed(feed_id, &update).await
+    }
+
+    /// Fetch a feed now, regardless of its pause state and refresh interval.
+    ///
+    /// Archived feeds are never fetched.
     pub async fn refresh_feed(&self, feed_id: i64) -> anyhow::Result<RefreshResult> {
-        let feed_url = self
+        let feed = self
             .db
-            .get_feed_url(feed_id)
+            .get_feed(feed_id)
             .await?
-            .ok_or_else(|| anyhow::anyhow!("Feed not found"))?;
+            .ok_or_else(|| DbError::NotFound(format!("feed {}", feed_id)))?;
+        if feed.status == FeedStatus::Archived {
+            return Err(DbError::InvalidInput(format!("feed {} is archived", feed_id)).into());
+        }
 
-        let response = self.http_client.get(&feed_url).send().await?;
-        let body = response.text().await?;
-        let parsed = FeedParser::parse(&body, &feed_url)?;
+        let subscribers = Audience::FeedSubscribers(feed_id);
+        self.events
+            .publish(subscribers, AppEvent::RefreshStarted { feed_id });
 
-        let mut new_count = 0;
+        let mut fetch = NewFeedFetch {
+            feed_id,
+            ..Default::default()
+        };
+        let started = Instant::now();
+        let parsed = self.fetch_parsed(&feed.feed_url, &mut fetch).await;
+        fetch.duration_ms = started.elapsed().as_millis() as i64;
+        let parsed = match parsed {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                fetch.error = Some(e.to_string());
+                self.record_fetch(&fetch).await;
+                self.events.publish(
+                    subscribers,
+                    AppEvent::RefreshFailed {
+                        feed_id,
+                        error: e.to_string(),
+                    },
+                );
+                return Err(e);
+            }
+        };
+
+        let mut new_ids = Vec::new();
         for entry in parsed.items() {
-            let new_article = NewArticle {
-                feed_id,
-                guid: entry.guid().to_string(),
-                title: entry.title.clone(),
-                link: if entry.link.is_empty() {
-                    None
-                } else {
-                    Some(entry.link.clone())
-                },
-                author: entry.author.clone(),
-                summary: entry.summary.clone(),
-                content: entry.content.clone(),
-                image_url: entry.image_url.clone(),
-                published_at: entry.published_at(),
-            };
-            if let InsertResult::Inserted(_) = self.db.insert_article(&new_article).await? {
-                new_count += 1;
+            if let InsertResult::Inserted(id) = self.insert_entry(feed_id, entry).await? {
+                new_ids.push(id);
+                if feed.fetch_full_content
+                    && !entry.link.is_empty()
+                    && let Err(e) = self.fetch_full_content(id, &entry.link).await
+                {
+                    log::warn!("Failed to fetch full content of article {}: {}", id, e);
+                }
             }
         }
 
+        let new_count = new_ids.len() as i64;
         self.db.update_feed_last_fetched(feed_id).await?;
+        fetch.success = true;
+        fetch.new_articles = new_count;
+        self.record_fetch(&fetch).await;
+
+        if new_count > 0 {
+            self.events.publish(
+                subscribers,
+                AppEvent::NewArticles {
+                    feed_id,
+                    count: new_count,
+                    article_ids: new_ids,
+                },
+            );
+        }
+        self.events.publish(
+            subscribers,
+            AppEvent::RefreshFinished {
+                feed_id,
+                new_articles: new_count,
+            },
+        );
 
         Ok(RefreshResult {
             feed_id,
             new_articles: new_count,
+            // Muted feeds keep fetching quietly
+            notify: feed.notify && feed.status != FeedStatus::Muted,
         })
     }
 
+    /// Refresh every active or muted feed whose refresh interval has elapsed.
     pub async fn refresh_all_feeds(&self) -> anyhow::Result<Vec<RefreshResult>> {
         let feeds = self.db.get_feeds().await?;
+        let now = Utc::now();
         let mut results = Vec::new();
 
         for feed in feeds {
+            if !feed.status.is_refreshed()
+                || !is_due(feed.refresh_interval_minutes, feed.last_fetched_at, now)
+            {
+                continue;
+            }
+
             match self.refresh_feed(feed.id).await {
                 Ok(result) => results.push(result),
                 Err(e) => {
@@ -141,6 +405,7 @@ impl<D: Database> FeedService<D> {
                     results.push(RefreshResult {
                         feed_id: feed.id,
                         new_articles: 0,
+                        notify: feed.notify,
                     });
                 }
             }
@@ -149,6 +414,102 @@ impl<D: Database> FeedService<D> {
         Ok(results)
     }
 
+    /// Download and parse a feed, noting the response status and size in `fetch`.
+    async fn fetch_parsed(
+        &self,
+        url: &str,
+        fetch: &mut NewFeedFetch,
+    ) -> anyhow::Result<ParsedFeed> {
+        let response = self.http_client.get(url).send().await?;
+        fetch.status_code = Some(response.status().as_u16() as i64);
+        let response = response.error_for_status()?;
+        let body = response.text().await?;
+        fetch.bytes = Some(body.len() as i64);
+        FeedParser::parse(&body, url)
+    }
+
+    /// Add a fetch to the feed's history; failing to do so doesn't fail the refresh.
+    async fn record_fetch(&self, fetch: &NewFeedFetch) {
+        if let Err(e) = self.db.record_feed_fetch(fetch).await {
+            log::warn!("Failed to record fetch of feed {}: {}", fetch.feed_id, e);
+        }
+    }
+
+    /// Replace an article's content with the main content of the page it links to.
+    async fn fetch_full_content(&self, article_id: i64, link: &str) -> anyhow::Result<()> {
+        let response = self
+            .http_client
+            .get(link)
+            .send()
+            .await?
+            .error_for_status()?;
+        let html = response.text().await?;
+        let content = extract_article_content(&html);
+        if !content.trim().is_empty() {
+            self.db
+                .update_article_content(article_id, &resolve_relative_urls(&content, link))
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Store a parsed entry and link it to copies of the same story in other feeds.
+    async fn insert_entry(&self, feed_id: i64, entry: &ParsedFeedEntry) -> DbResult<InsertResult> {
+        let link = if entry.link.is_empty() {
+            None
+        } else {
+            Some(entry.link.clone())
+        };
+        let new_article = NewArticle {
+            feed_id,
+            guid: entry.guid().to_string(),
+            title: entry.title.clone(),
+            normalized_link: link.as_deref().and_then(dedup::normalize_link),
+            link,
+            author: entry.author.clone(),
+            summary: entry.summary.clone(),
+            content: entry.content.clone(),
+            image_url: entry.image_url.clone(),
+            enclosure_url: entry.enclosure_url.clone(),
+            enclosure_type: entry.enclosure_type.clone(),
+            published_at: entry.published_at(),
+            categories: entry
+                .categories
+                .iter()
+                .map(|c| c.trim().to_string())
+                .filter(|c| !c.is_empty())
+                .collect(),
+        };
+
+        let result = self.db.insert_article(&new_article).await?;
+        if let InsertResult::Inserted(id) = result
+            && let Err(e) = self.link_duplicates(id, &new_article).await
+        {
+            log::warn!("Failed to check article {} for duplicates: {}", id, e);
+        }
+
+        Ok(result)
+    }
+
+    async fn link_duplicates(&self, article_id: i64, article: &NewArticle) -> DbResult<()> {
+        let candidates = self.db.find_duplicate_candidates(article).await?;
+        let duplicate = candidates.iter().find(|c| {
+            dedup::is_duplicate(
+                article.normalized_link.as_deref(),
+                &article.guid,
+                &article.title,
+                c,
+            )
+        });
+
+        if let Some(candidate) = duplicate {
+            let group_id = candidate.group_id.unwrap_or(candidate.id);
+            self.db.set_duplicate_group(article_id, group_id).await?;
+        }
+
+        Ok(())
+    }
+
     async fn fetch_favicon(&self, site_url: &str) -> anyhow::Result<String> {
         let url = url::Url::parse(site_url)?;
         let favicon_url = format!(
@@ -193,10 +554,173 @@ impl<D: Database> FeedService<D> {
 
         Err(anyhow::anyhow!("Favicon not found"))
     }
+
+    /// Download a favicon, returning its content type and bytes.
+    pub async fn fetch_favicon_image(
+        &self,
+        favicon_url: &str,
+    ) -> anyhow::Result<(String, Vec<u8>)> {
+        let response = self
+            .http_client
+            .get(favicon_url)
+            .send()
+            .await?
+            .error_for_status()?;
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(';').next())
+            .map(|value| value.trim().to_string())
+            .filter(|value| value.starts_with("image/"))
+            .unwrap_or_else(|| "image/x-icon".to_string());
+        let bytes = response.bytes().await?;
+        Ok((content_type, bytes.to_vec()))
+    }
+}
+
+/// Longest refresh interval a feed can be given, a year.
+const MAX_REFRESH_INTERVAL_MINUTES: i64 = 525_600;
+
+/// Whether a feed with the given refresh interval is due for another fetch.
+fn is_due(
+    interval_minutes: Option<i64>,
+    last_fetched_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> bool {
+    match (interval_minutes, last_fetched_at) {
+        // An interval too long to represent has never elapsed
+        (Some(minutes), Some(last)) => {
+            Duration::try_minutes(minutes).is_some_and(|interval| now - last >= interval)
+        }
+        _ => true,
+    }
+}
+
+/// Trim the text fields of an update and check that the URLs are usable.
+fn normalize_update(update: &FeedUpdate) -> DbResult<FeedUpdate> {
+    let feed_url = update.feed_url.as_deref().map(str::trim);
+    if let Some(url) = feed_url
+        && !is_http_url(url)
+    {
+        return Err(DbError::InvalidInput(format!(
+            "feed URL must be an http or https URL: {}",
+            url
+        )));
+    }
+
+    let site_url = update.site_url.as_deref().map(str::trim);
+    if let Some(url) = site_url
+        && !url.is_empty()
+        && !is_http_url(url)
+    {
+        return Err(DbError::InvalidInput(format!(
+            "site URL must be an http or https URL: {}",
+            url
+        )));
+    }
+
+    if update.refresh_interval_minutes.is_some_and(|m| m < 0) {
+        return Err(DbError::InvalidInput(
+            "refresh interval must not be negative".to_string(),
+        ));
+    }
+    if update
+        .refresh_interval_minutes
+        .is_some_and(|m| m > MAX_REFRESH_INTERVAL_MINUTES)
+    {
+        return Err(DbError::InvalidInput(format!(
+            "refresh interval must be at most {} minutes",
+            MAX_REFRESH_INTERVAL_MINUTES
+        )));
+    }
+
+    Ok(FeedUpdate {
+        title: update.title.as_deref().map(|t| t.trim().to_string()),
+        feed_url: feed_url.map(str::to_string),
+        site_url: site_url.map(str::to_string),
+        ..update.clone()
+    })
+}
+
+fn is_http_url(url: &str) -> bool {
+    url::Url::parse(url).is_ok_and(|u| matches!(u.scheme(), "http" | "https"))
+}
+
+/// A feed fetched for subscribing, before anything is stored.
+struct FeedDownload {
+    parsed: ParsedFeed,
+    favicon: Option<String>,
+}
+
+/// A feed from an OPML file, ready to import.
+pub(crate) struct PlannedFeed {
+    pub feed: OpmlFeed,
+    pub folder_id: Option<i64>,
+    /// Already subscribed, or listed earlier in the file.
+    pub duplicate: bool,
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct RefreshResult {
     pub feed_id: i64,
     pub new_articles: i64,
+    /// Whether the feed wants its new articles announced.
+    pub notify: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn feeds_without_interval_are_always_due() {
+        let now = Utc::now();
+        assert!(is_due(None, Some(now), now));
+        assert!(is_due(Some(60), None, now));
+    }
+
+    #[test]
+    fn interval_delays_next_refresh() {
+        let now = Utc::now();
+        assert!(!is_due(Some(60), Some(now - Duration::minutes(59)), now));
+        assert!(is_due(Some(60), Some(now - Duration::minutes(60)), now));
+    }
+
+    #[test]
+    fn huge_intervals_are_never_due() {
+        let now = Utc::now();
+        assert!(!is_due(
+            Some(i64::MAX),
+            Some(now - Duration::days(3650)),
+            now
+        ));
+    }
+
+    #[test]
+    fn update_rejects_intervals_over_a_year() {
+        let update = |minutes| FeedUpdate {
+            refresh_interval_minutes: Some(minutes),
+            ..Default::default()
+        };
+        assert!(normalize_update(&update(MAX_REFRESH_INTERVAL_MINUTES)).is_ok());
+        for minutes in [MAX_REFRESH_INTERVAL_MINUTES + 1, i64::MAX, -1] {
+            assert!(matches!(
+                normalize_update(&update(minutes)),
+                Err(DbError::InvalidInput(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn update_rejects_non_http_urls() {
+        let update = FeedUpdate {
+            feed_url: Some("ftp://example.com/feed".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            normalize_update(&update),
+            Err(DbError::InvalidInput(_))
+        ));
+    }
 }
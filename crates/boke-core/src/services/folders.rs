@@ -1,33 +1,132 @@
-use crate::db::{Database, DbResult};
-use crate::models::Folder;
+use crate::db::{Database, DbError, DbResult};
+use crate::events::{AppEvent, Audience, EventBus};
+use crate::folder_tree;
+use crate::models::{Folder, FolderDeleteMode, FolderTree};
+use crate::opml;
+use chrono::Utc;
 use std::sync::Arc;
 
 pub struct FolderService<D: Database> {
     db: Arc<D>,
+    events: EventBus,
 }
 
 impl<D: Database> FolderService<D> {
     pub fn new(db: Arc<D>) -> Self {
-        Self { db }
+        Self {
+            db,
+            events: EventBus::default(),
+        }
+    }
+
+    /// Publish changes to `events` instead of a bus nobody listens to.
+    pub fn with_events(mut self, events: EventBus) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// The same service acting for another user, sharing the event bus.
+    pub fn for_user(&self, user_id: i64) -> Self {
+        Self {
+            db: Arc::new(self.db.for_user(user_id)),
+            events: self.events.clone(),
+        }
+    }
+
+    fn publish(&self, event: AppEvent) {
+        self.events
+            .publish(Audience::User(self.db.user_id()), event);
     }
 
     pub async fn get_folders(&self) -> DbResult<Vec<Folder>> {
         self.db.get_folders().await
     }
 
-    pub async fn create_folder(&self, name: &str) -> DbResult<Folder> {
-        self.db.create_folder(name).await
+    /// Folders and feeds nested as in the sidebar, with unread counts summed per folder.
+    pub async fn get_folder_tree(&self) -> DbResult<FolderTree> {
+        let folders = self.db.get_folders().await?;
+        let feeds = self.db.get_feeds().await?;
+        Ok(folder_tree::build_tree(folders, feeds))
+    }
+
+    /// The subscription list as an OPML 2.0 document, for backups or other readers.
+    pub async fn export_opml(&self) -> DbResult<String> {
+        let tree = self.get_folder_tree().await?;
+        Ok(opml::write_opml(&tree, "Boke subscriptions", Utc::now()))
+    }
+
+    pub async fn create_folder(&self, name: &str, parent_id: Option<i64>) -> DbResult<Folder> {
+        if let Some(parent_id) = parent_id {
+            ensure_exists(&self.db.get_folders().await?, parent_id)?;
+        }
+        let folder = self.db.create_folder(name, parent_id).await?;
+        self.publish(AppEvent::FoldersChanged);
+        Ok(folder)
     }
 
     pub async fn rename_folder(&self, id: i64, name: &str) -> DbResult<()> {
-        self.db.rename_folder(id, name).await
+        self.db.rename_folder(id, name).await?;
+        self.publish(AppEvent::FoldersChanged);
+        Ok(())
     }
 
-    pub async fn delete_folder(&self, id: i64) -> DbResult<()> {
-        self.db.delete_folder(id).await
+    /// Delete a folder; its contents move up to its parent or go with it, depending on `mode`.
+    pub async fn delete_folder(&self, id: i64, mode: FolderDeleteMode) -> DbResult<()> {
+        self.db.delete_folder(id, mode).await?;
+        self.publish(AppEvent::FoldersChanged);
+        self.publish(AppEvent::FeedsChanged { feed_id: None });
+        Ok(())
+    }
+
+    /// Move a folder under another one, or to the top level with `None`.
+    pub async fn move_folder(&self, id: i64, parent_id: Option<i64>) -> DbResult<()> {
+        if let Some(parent_id) = parent_id {
+            let folders = self.db.get_folders().await?;
+            ensure_exists(&folders, id)?;
+            ensure_exists(&folders, parent_id)?;
+            if folder_tree::creates_cycle(&folders, id, parent_id) {
+                return Err(DbError::InvalidInput(
+                    "a folder cannot be moved into itself or one of its sub-folders".to_string(),
+                ));
+            }
+        }
+        self.db.move_folder(id, parent_id).await?;
+        self.publish(AppEvent::FoldersChanged);
+        Ok(())
+    }
+
+    pub async fn reorder_folders(
+        &self,
+        parent_id: Option<i64>,
+        folder_ids: &[i64],
+    ) -> DbResult<()> {
+        self.db.reorder_folders(parent_id, folder_ids).await?;
+        self.publish(AppEvent::FoldersChanged);
+        Ok(())
     }
 
     pub async fn move_feed_to_folder(&self, feed_id: i64, folder_id: Option<i64>) -> DbResult<()> {
-        self.db.move_feed_to_folder(feed_id, folder_id).await
+        if let Some(folder_id) = folder_id {
+            ensure_exists(&self.db.get_folders().await?, folder_id)?;
+        }
+        self.db.move_feed_to_folder(feed_id, folder_id).await?;
+        self.publish(AppEvent::FeedsChanged {
+            feed_id: Some(feed_id),
+        });
+        Ok(())
+    }
+
+    pub async fn reorder_feeds(&self, folder_id: Option<i64>, feed_ids: &[i64]) -> DbResult<()> {
+        self.db.reorder_feeds(folder_id, feed_ids).await?;
+        self.publish(AppEvent::FeedsChanged { feed_id: None });
+        Ok(())
+    }
+}
+
+fn ensure_exists(folders: &[Folder], id: i64) -> DbResult<()> {
+    if folders.iter().any(|f| f.id == id) {
+        Ok(())
+    } else {
+        Err(DbError::NotFound(format!("folder {}", id)))
     }
 }
@@ -0,0 +1,164 @@
+use crate::db::{Database, DbError, DbResult};
+use crate::markdown;
+use crate::models::{Highlight, HighlightQuery, NewHighlight, UpdateHighlight};
+use crate::search::SearchQuery;
+use std::sync::Arc;
+
+pub struct HighlightService<D: Database> {
+    db: Arc<D>,
+}
+
+impl<D: Database> HighlightService<D> {
+    pub fn new(db: Arc<D>) -> Self {
+        Self { db }
+    }
+
+    /// The same service acting for another user.
+    pub fn for_user(&self, user_id: i64) -> Self {
+        Self::new(Arc::new(self.db.for_user(user_id)))
+    }
+
+    pub async fn get_highlights(&self, query: &HighlightQuery) -> DbResult<Vec<Highlight>> {
+        self.db.get_highlights(query).await
+    }
+
+    pub async fn get_article_highlights(&self, article_id: i64) -> DbResult<Vec<Highlight>> {
+        let mut highlights = self
+            .db
+            .get_highlights(&HighlightQuery {
+                article_id: Some(article_id),
+                ..Default::default()
+            })
+            .await?;
+        sort_in_reading_order(&mut highlights);
+        Ok(highlights)
+    }
+
+    /// Search quotes and notes; filters such as `feed:` or `label:` apply to the article.
+    pub async fn search_highlights(
+        &self,
+        query: &str,
+        limit: i64,
+        offset: i64,
+    ) -> DbResult<Vec<Highlight>> {
+        let search = SearchQuery::parse(query);
+        if search.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.db
+            .get_highlights(&HighlightQuery {
+                search: Some(search),
+                offset,
+                limit: Some(limit),
+                ..Default::default()
+            })
+            .await
+    }
+
+    pub async fn get_highlight(&self, id: i64) -> DbResult<Option<Highlight>> {
+        self.db.get_highlight(id).await
+    }
+
+    pub async fn create_highlight(&self, highlight: &NewHighlight) -> DbResult<Highlight> {
+        if self.db.get_article(highlight.article_id).await?.is_none() {
+            return Err(DbError::NotFound(format!(
+                "article {}",
+                highlight.article_id
+            )));
+        }
+
+        self.db
+            .create_highlight(&NewHighlight {
+                note: non_empty(highlight.note.as_deref()),
+                color: non_empty(highlight.color.as_deref()),
+                ..highlight.clone()
+            })
+            .await
+    }
+
+    pub async fn update_highlight(&self, id: i64, highlight: &UpdateHighlight) -> DbResult<()> {
+        self.db
+            .update_highlight(
+                id,
+                &UpdateHighlight {
+                    note: non_empty(highlight.note.as_deref()),
+                    color: non_empty(highlight.color.as_deref()),
+                },
+            )
+            .await
+    }
+
+    pub async fn delete_highlight(&self, id: i64) -> DbResult<()> {
+        self.db.delete_highlight(id).await
+    }
+
+    /// All highlights of an article as a Markdown document.
+    pub async fn export_article(&self, article_id: i64) -> DbResult<String> {
+        let article = self
+            .db
+            .get_article(article_id)
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("article {}", article_id)))?;
+        let highlights = self.get_article_highlights(article_id).await?;
+        Ok(markdown::article_highlights(&article, &highlights))
+    }
+
+    /// All highlights on articles carrying a label as one Markdown document, newest
+    /// article first.
+    pub async fn export_label(&self, label_id: i64) -> DbResult<String> {
+        let label = self
+            .db
+            .get_labels()
+            .await?
+            .into_iter()
+            .find(|l| l.id == label_id)
+            .ok_or_else(|| DbError::NotFound(format!("label {}", label_id)))?;
+
+        let highlights = self
+            .db
+            .get_highlights(&HighlightQuery {
+                label_id: Some(label_id),
+                ..Default::default()
+            })
+            .await?;
+
+        let mut article_ids: Vec<i64> = highlights.iter().map(|h| h.article_id).collect();
+        article_ids.sort_unstable();
+        article_ids.dedup();
+
+        let mut articles = Vec::with_capacity(article_ids.len());
+        for article_id in article_ids {
+            let Some(article) = self.db.get_article(article_id).await? else {
+                continue;
+            };
+            let mut own: Vec<Highlight> = highlights
+                .iter()
+                .filter(|h| h.article_id == article_id)
+                .cloned()
+                .collect();
+            sort_in_reading_order(&mut own);
+            articles.push((article, own));
+        }
+        articles.sort_by(|(a, _), (b, _)| {
+            b.published_at
+                .or(b.created_at)
+                .cmp(&a.published_at.or(a.created_at))
+                .then(b.id.cmp(&a.id))
+        });
+
+        Ok(markdown::collection_highlights(&label.name, &articles))
+    }
+}
+
+/// Order highlights as they appear in the article; unanchored ones go last.
+fn sort_in_reading_order(highlights: &mut [Highlight]) {
+    highlights.sort_by_key(|h| (h.start_offset.is_none(), h.start_offset, h.id));
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
@@ -0,0 +1,374 @@
+//! OPML imports as background jobs, reported feed by feed.
+
+use crate::auth::generate_token;
+use crate::db::{Database, DbResult};
+use crate::events::AppEvent;
+use crate::opml::OpmlOutline;
+use crate::services::FeedService;
+use crate::services::feeds::PlannedFeed;
+use chrono::{DateTime, Duration, Utc};
+use futures_util::StreamExt;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+
+/// Feeds an import job fetches at the same time.
+pub const IMPORT_CONCURRENCY: usize = 6;
+
+/// How long a finished job stays around for clients to collect.
+const FINISHED_JOB_TTL_MINUTES: i64 = 60;
+
+/// Where one feed of an import job has got to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportStatus {
+    Queued,
+    Fetching,
+    Added,
+    /// Already subscribed, or listed earlier in the file.
+    Duplicate,
+    Failed,
+    /// The job was cancelled before this feed was fetched.
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportItem {
+    pub url: String,
+    pub title: Option<String>,
+    pub status: ImportStatus,
+    /// Why the feed failed.
+    pub error: Option<String>,
+    /// The subscribed feed, once added.
+    pub feed_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportJob {
+    pub id: String,
+    #[serde(skip)]
+    pub user_id: i64,
+    pub items: Vec<ImportItem>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub cancelled: bool,
+}
+
+impl ImportJob {
+    pub fn is_finished(&self) -> bool {
+        self.finished_at.is_some()
+    }
+
+    /// Counts so far, in the shape of a finished import.
+    pub fn result(&self) -> ImportResult {
+        let count = |status| self.items.iter().filter(|i| i.status == status).count() as i64;
+        ImportResult {
+            added: count(ImportStatus::Added),
+            skipped: count(ImportStatus::Duplicate),
+            errors: self
+                .items
+                .iter()
+                .filter(|i| i.status == ImportStatus::Failed)
+                .map(|i| format!("{}: {}", i.url, i.error.as_deref().unwrap_or_default()))
+                .collect(),
+        }
+    }
+}
+
+/// Outcome of an OPML import.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ImportResult {
+    pub added: i64,
+    /// Feeds already subscribed, or listed twice in the file.
+    pub skipped: i64,
+    pub errors: Vec<String>,
+}
+
+/// Progress of an import job, as sent to subscribers.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ImportEvent {
+    /// A feed moved to a new status.
+    Item {
+        job_id: String,
+        index: usize,
+        item: ImportItem,
+    },
+    /// The job is done or cancelled; nothing follows.
+    Finished {
+        job_id: String,
+        cancelled: bool,
+        result: ImportResult,
+    },
+}
+
+struct JobHandle {
+    user_id: i64,
+    job: Mutex<ImportJob>,
+    cancel: AtomicBool,
+    events: broadcast::Sender<ImportEvent>,
+}
+
+impl JobHandle {
+    /// Apply a change to the job and announce it, under one lock so a subscriber's snapshot
+    /// and its events never overlap or leave a gap.
+    fn update(&self, f: impl FnOnce(&mut ImportJob) -> ImportEvent) {
+        let mut job = self.job.lock().unwrap_or_else(|e| e.into_inner());
+        let event = f(&mut job);
+        let _ = self.events.send(event);
+    }
+
+    fn set_item(&self, index: usize, status: ImportStatus, error: Option<String>) {
+        self.update(|job| {
+            let item = &mut job.items[index];
+            item.status = status;
+            item.error = error;
+            ImportEvent::Item {
+                job_id: job.id.clone(),
+                index,
+                item: item.clone(),
+            }
+        });
+    }
+
+    fn snapshot(&self) -> ImportJob {
+        self.job.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+/// Running and recently finished import jobs, shared by every user of the app.
+///
+/// Jobs are only visible to the user who started them.
+#[derive(Clone, Default)]
+pub struct ImportJobs {
+    jobs: Arc<Mutex<HashMap<String, Arc<JobHandle>>>>,
+    /// Held while a job stores a fetched feed.
+    writes: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl ImportJobs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create the file's folders, then subscribe to its feeds in the background.
+    ///
+    /// Returns the job as it starts, with duplicates already marked, and a receiver for its
+    /// progress events.
+    pub async fn start<D: Database>(
+        &self,
+        feeds: Arc<FeedService<D>>,
+        outlines: &[OpmlOutline],
+    ) -> DbResult<(ImportJob, broadcast::Receiver<ImportEvent>)> {
+        let planned = feeds.plan_import(outlines).await?;
+
+        let job = ImportJob {
+            id: generate_token()[..16].to_string(),
+            user_id: feeds.user_id(),
+            items: planned
+                .iter()
+                .map(|p| ImportItem {
+                    url: p.feed.url.clone(),
+                    title: p.feed.title.clone(),
+                    status: if p.duplicate {
+                        ImportStatus::Duplicate
+                    } else {
+                        ImportStatus::Queued
+                    },
+                    error: None,
+                    feed_id: None,
+                })
+                .collect(),
+            started_at: Utc::now(),
+            finished_at: None,
+            cancelled: false,
+        };
+        // Room for every event the job can send, so a subscriber that keeps up never lags
+        let (events, receiver) = broadcast::channel(2 * job.items.len() + 1);
+        let handle = Arc::new(JobHandle {
+            user_id: job.user_id,
+            job: Mutex::new(job.clone()),
+            cancel: AtomicBool::new(false),
+            events,
+        });
+
+        {
+            let mut jobs = self.lock();
+            let cutoff = Utc::now() - Duration::minutes(FINISHED_JOB_TTL_MINUTES);
+            jobs.retain(|_, h| h.snapshot().finished_at.is_none_or(|at| at > cutoff));
+            jobs.insert(job.id.clone(), handle.clone());
+        }
+
+        tokio::spawn(run(handle, feeds, planned, self.writes.clone()));
+        Ok((job, receiver))
+    }
+
+    /// The user's job as it stands.
+    pub fn get(&self, user_id: i64, job_id: &str) -> Option<ImportJob> {
+        self.handle(user_id, job_id).map(|h| h.snapshot())
+    }
+
+    /// The user's job as it stands, and a receiver for the events that follow.
+    pub fn subscribe(
+        &self,
+        user_id: i64,
+        job_id: &str,
+    ) -> Option<(ImportJob, broadcast::Receiver<ImportEvent>)> {
+        let handle = self.handle(user_id, job_id)?;
+        let job = handle.job.lock().unwrap_or_else(|e| e.into_inner());
+        Some((job.clone(), handle.events.subscribe()))
+    }
+
+    /// Stop the user's job from starting any more feeds; those being fetched still finish.
+    ///
+    /// Returns false if there is no such job.
+    pub fn cancel(&self, user_id: i64, job_id: &str) -> bool {
+        match self.handle(user_id, job_id) {
+            Some(handle) => {
+                handle.cancel.store(true, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn handle(&self, user_id: i64, job_id: &str) -> Option<Arc<JobHandle>> {
+        self.lock()
+            .get(job_id)
+            .filter(|h| h.user_id == user_id)
+            .cloned()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Arc<JobHandle>>> {
+        self.jobs.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+async fn run<D: Database>(
+    handle: Arc<JobHandle>,
+    feeds: Arc<FeedService<D>>,
+    planned: Vec<PlannedFeed>,
+    writes: Arc<tokio::sync::Mutex<()>>,
+) {
+    futures_util::stream::iter(planned.iter().enumerate().filter(|(_, p)| !p.duplicate))
+        .for_each_concurrent(IMPORT_CONCURRENCY, |(index, planned)| {
+            let handle = &handle;
+            let feeds = &feeds;
+            let writes = &writes;
+            async move {
+                if handle.cancel.load(Ordering::Relaxed) {
+                    handle.set_item(index, ImportStatus::Cancelled, None);
+                    return;
+                }
+                handle.set_item(index, ImportStatus::Fetching, None);
+                match feeds
+                    .import_feed(&planned.feed, planned.folder_id, writes)
+                    .await
+                {
+                    Ok(feed) => handle.update(|job| {
+                        let item = &mut job.items[index];
+                        item.status = ImportStatus::Added;
+                        item.feed_id = Some(feed.id);
+                        ImportEvent::Item {
+                            job_id: job.id.clone(),
+                            index,
+                            item: item.clone(),
+                        }
+                    }),
+                    Err(e) => handle.set_item(index, ImportStatus::Failed, Some(e.to_string())),
+                }
+            }
+        })
+        .await;
+
+    let mut added_by_folder: Vec<(Option<i64>, Vec<i64>)> = Vec::new();
+    for (planned, item) in planned.iter().zip(handle.snapshot().items) {
+        let Some(feed_id) = item.feed_id else {
+            continue;
+        };
+        match added_by_folder
+            .iter_mut()
+            .find(|(folder_id, _)| *folder_id == planned.folder_id)
+        {
+            Some((_, ids)) => ids.push(feed_id),
+            None => added_by_folder.push((planned.folder_id, vec![feed_id])),
+        }
+    }
+    if !added_by_folder.is_empty() {
+        let _writing = writes.lock().await;
+        for (folder_id, ids) in added_by_folder {
+            if let Err(e) = feeds.order_imported(folder_id, &ids).await {
+                log::warn!("Failed to order imported feeds: {}", e);
+            }
+        }
+        feeds.publish(AppEvent::FeedsChanged { feed_id: None });
+    }
+
+    handle.update(|job| {
+        job.finished_at = Some(Utc::now());
+        job.cancelled = handle.cancel.load(Ordering::Relaxed);
+        ImportEvent::Finished {
+            job_id: job.id.clone(),
+            cancelled: job.cancelled,
+            result: job.result(),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(url: &str, status: ImportStatus, error: Option<&str>) -> ImportItem {
+        ImportItem {
+            url: url.to_string(),
+            title: None,
+            status,
+            error: error.map(str::to_string),
+            feed_id: None,
+        }
+    }
+
+    #[test]
+    fn result_counts_item_statuses() {
+        let job = ImportJob {
+            id: "job".to_string(),
+            user_id: 1,
+            items: vec![
+                item("https://a/feed", ImportStatus::Added, None),
+                item("https://b/feed", ImportStatus::Duplicate, None),
+                item("https://c/feed", ImportStatus::Failed, Some("timed out")),
+                item("https://d/feed", ImportStatus::Cancelled, None),
+                item("https://e/feed", ImportStatus::Added, None),
+            ],
+            started_at: Utc::now(),
+            finished_at: None,
+            cancelled: true,
+        };
+
+        assert_eq!(
+            job.result(),
+            ImportResult {
+                added: 2,
+                skipped: 1,
+                errors: vec!["https://c/feed: timed out".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn events_are_tagged_by_type() {
+        let event = ImportEvent::Item {
+            job_id: "job".to_string(),
+            index: 2,
+            item: item("https://a/feed", ImportStatus::Failed, Some("404")),
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "item");
+        assert_eq!(json["index"], 2);
+        assert_eq!(json["item"]["status"], "failed");
+        assert_eq!(json["item"]["error"], "404");
+    }
+}
@@ -0,0 +1,100 @@
+use crate::db::{Database, DbResult};
+use crate::events::{AppEvent, Audience, EventBus};
+use crate::models::{Label, NewLabel};
+use std::sync::Arc;
+
+pub struct LabelService<D: Database> {
+    db: Arc<D>,
+    events: EventBus,
+}
+
+impl<D: Database> LabelService<D> {
+    pub fn new(db: Arc<D>) -> Self {
+        Self {
+            db,
+            events: EventBus::default(),
+        }
+    }
+
+    /// Publish changes to `events` instead of a bus nobody listens to.
+    pub fn with_events(mut self, events: EventBus) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// The same service acting for another user, sharing the event bus.
+    pub fn for_user(&self, user_id: i64) -> Self {
+        Self {
+            db: Arc::new(self.db.for_user(user_id)),
+            events: self.events.clone(),
+        }
+    }
+
+    pub async fn get_labels(&self) -> DbResult<Vec<Label>> {
+        self.db.get_labels().await
+    }
+
+    pub async fn create_label(&self, label: &NewLabel) -> DbResult<Label> {
+        self.db.create_label(&normalize(label)).await
+    }
+
+    pub async fn update_label(&self, id: i64, label: &NewLabel) -> DbResult<()> {
+        self.db.update_label(id, &normalize(label)).await
+    }
+
+    pub async fn delete_label(&self, id: i64) -> DbResult<()> {
+        self.db.delete_label(id).await
+    }
+
+    pub async fn add_label(&self, label_id: i64, article_ids: &[i64]) -> DbResult<()> {
+        let had_label = self.labelled(label_id, article_ids).await?;
+        self.db.add_label_to_articles(label_id, article_ids).await?;
+
+        let added: Vec<i64> = self
+            .labelled(label_id, article_ids)
+            .await?
+            .into_iter()
+            .filter(|id| !had_label.contains(id))
+            .collect();
+        if !added.is_empty() {
+            self.events.publish(
+                Audience::User(self.db.user_id()),
+                AppEvent::ArticlesLabelled {
+                    label_id,
+                    article_ids: added,
+                },
+            );
+        }
+        Ok(())
+    }
+
+    /// Which of the articles have the label.
+    async fn labelled(&self, label_id: i64, article_ids: &[i64]) -> DbResult<Vec<i64>> {
+        Ok(self
+            .db
+            .get_article_labels(article_ids)
+            .await?
+            .into_iter()
+            .filter(|(_, id)| *id == label_id)
+            .map(|(article_id, _)| article_id)
+            .collect())
+    }
+
+    pub async fn remove_label(&self, label_id: i64, article_ids: &[i64]) -> DbResult<()> {
+        self.db
+            .remove_label_from_articles(label_id, article_ids)
+            .await
+    }
+}
+
+fn normalize(label: &NewLabel) -> NewLabel {
+    NewLabel {
+        name: label.name.trim().to_string(),
+        color: label
+            .color
+            .as_deref()
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .map(str::to_string),
+    }
+}
@@ -1,7 +1,23 @@
 mod articles;
 mod feeds;
 mod folders;
+mod highlights;
+mod import;
+mod labels;
+mod smart_folders;
+mod stats;
+mod users;
+mod webhooks;
 
 pub use articles::ArticleService;
 pub use feeds::{FeedService, RefreshResult};
 pub use folders::FolderService;
+pub use highlights::HighlightService;
+pub use import::{
+    IMPORT_CONCURRENCY, ImportEvent, ImportItem, ImportJob, ImportJobs, ImportResult, ImportStatus,
+};
+pub use labels::LabelService;
+pub use smart_folders::SmartFolderService;
+pub use stats::StatsService;
+pub use users::{SESSION_LIFETIME, UserService};
+pub use webhooks::{WEBHOOK_MAX_ATTEMPTS, WebhookService};
@@ -0,0 +1,85 @@
+use crate::db::{Database, DbError, DbResult};
+use crate::models::{ArticleQuery, NewSmartFolder, SmartFolder};
+use crate::search::SearchQuery;
+use std::sync::Arc;
+
+pub struct SmartFolderService<D: Database> {
+    db: Arc<D>,
+}
+
+impl<D: Database> SmartFolderService<D> {
+    pub fn new(db: Arc<D>) -> Self {
+        Self { db }
+    }
+
+    /// The same service acting for another user.
+    pub fn for_user(&self, user_id: i64) -> Self {
+        Self::new(Arc::new(self.db.for_user(user_id)))
+    }
+
+    pub async fn get_smart_folders(&self) -> DbResult<Vec<SmartFolder>> {
+        let mut folders = self.db.get_smart_folders().await?;
+        for folder in folders.iter_mut() {
+            self.fill_unread_count(folder).await?;
+        }
+        Ok(folders)
+    }
+
+    pub async fn get_smart_folder(&self, id: i64) -> DbResult<Option<SmartFolder>> {
+        let Some(mut folder) = self.db.get_smart_folder(id).await? else {
+            return Ok(None);
+        };
+        self.fill_unread_count(&mut folder).await?;
+        Ok(Some(folder))
+    }
+
+    pub async fn create_smart_folder(&self, folder: &NewSmartFolder) -> DbResult<SmartFolder> {
+        let mut folder = self.db.create_smart_folder(&normalize(folder)).await?;
+        self.fill_unread_count(&mut folder).await?;
+        Ok(folder)
+    }
+
+    pub async fn update_smart_folder(&self, id: i64, folder: &NewSmartFolder) -> DbResult<()> {
+        self.db.update_smart_folder(id, &normalize(folder)).await
+    }
+
+    pub async fn delete_smart_folder(&self, id: i64) -> DbResult<()> {
+        self.db.delete_smart_folder(id).await
+    }
+
+    /// The search a smart folder stands for, for use as an `ArticleQuery` scope.
+    pub async fn search_query(&self, id: i64) -> DbResult<SearchQuery> {
+        let folder = self
+            .db
+            .get_smart_folder(id)
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("smart folder {}", id)))?;
+        Ok(SearchQuery::parse(&folder.query))
+    }
+
+    /// Mark everything currently matching a smart folder as read.
+    pub async fn mark_read(&self, id: i64) -> DbResult<()> {
+        let query = ArticleQuery {
+            search: Some(self.search_query(id).await?),
+            ..Default::default()
+        };
+        self.db.mark_articles_read(&query).await
+    }
+
+    async fn fill_unread_count(&self, folder: &mut SmartFolder) -> DbResult<()> {
+        let query = ArticleQuery {
+            unread_only: true,
+            search: Some(SearchQuery::parse(&folder.query)),
+            ..Default::default()
+        };
+        folder.unread_count = self.db.count_articles(&query).await?;
+        Ok(())
+    }
+}
+
+fn normalize(folder: &NewSmartFolder) -> NewSmartFolder {
+    NewSmartFolder {
+        name: folder.name.trim().to_string(),
+        query: folder.query.trim().to_string(),
+    }
+}
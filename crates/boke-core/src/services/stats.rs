@@ -0,0 +1,58 @@
+use crate::db::{Database, DbError, DbResult};
+use crate::models::{FeedHealth, ReadingStats};
+use chrono::{Duration, Months, Utc};
+use std::sync::Arc;
+
+pub struct StatsService<D: Database> {
+    db: Arc<D>,
+}
+
+impl<D: Database> StatsService<D> {
+    pub fn new(db: Arc<D>) -> Self {
+        Self { db }
+    }
+
+    /// The same service acting for another user.
+    pub fn for_user(&self, user_id: i64) -> Self {
+        Self::new(Arc::new(self.db.for_user(user_id)))
+    }
+
+    /// Collect reading statistics, with daily counts covering the last `days` days.
+    pub async fn get_reading_stats(&self, days: i64) -> DbResult<ReadingStats> {
+        let since = Utc::now() - Duration::days(days.max(1));
+        let reads_per_day = self.db.get_reads_per_day(since).await?;
+        let feeds = self.db.get_feed_read_stats().await?;
+        let avg_seconds_to_read = self.db.get_average_time_to_read().await?;
+
+        let never_read_feeds = feeds
+            .iter()
+            .filter(|f| f.total_articles > 0 && f.read_articles == 0)
+            .cloned()
+            .collect();
+
+        Ok(ReadingStats {
+            reads_per_day,
+            feeds,
+            avg_seconds_to_read,
+            never_read_feeds,
+        })
+    }
+
+    /// Activity over the last 90 days and fetch reliability of a feed. It counts as
+    /// dead when nothing was published in the last `dead_after_months` months.
+    pub async fn get_feed_health(
+        &self,
+        feed_id: i64,
+        dead_after_months: u32,
+    ) -> DbResult<FeedHealth> {
+        let now = Utc::now();
+        let active_since = now - Duration::days(90);
+        let dead_before = now
+            .checked_sub_months(Months::new(dead_after_months))
+            .unwrap_or(now);
+        self.db
+            .get_feed_health(feed_id, active_since, dead_before)
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("feed {}", feed_id)))
+    }
+}
@@ -0,0 +1,192 @@
+use crate::auth::{self, MIN_PASSWORD_LEN};
+use crate::db::{Database, DbError, DbResult};
+use crate::models::{ApiToken, CreatedApiToken, NewUser, User};
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+
+/// How long a login session stays valid.
+pub const SESSION_LIFETIME: Duration = Duration::days(30);
+
+pub struct UserService<D: Database> {
+    db: Arc<D>,
+}
+
+impl<D: Database> UserService<D> {
+    pub fn new(db: Arc<D>) -> Self {
+        Self { db }
+    }
+
+    /// The same service acting for another user.
+    pub fn for_user(&self, user_id: i64) -> Self {
+        Self::new(Arc::new(self.db.for_user(user_id)))
+    }
+
+    pub async fn get_users(&self) -> DbResult<Vec<User>> {
+        self.db.get_users().await
+    }
+
+    pub async fn get_user(&self, id: i64) -> DbResult<Option<User>> {
+        self.db.get_user(id).await
+    }
+
+    pub async fn get_user_by_name(&self, username: &str) -> DbResult<Option<User>> {
+        self.db.get_user_by_name(username.trim()).await
+    }
+
+    pub async fn create_user(&self, user: &NewUser) -> DbResult<User> {
+        let username = user.username.trim();
+        if username.is_empty() {
+            return Err(DbError::InvalidInput(
+                "username must not be empty".to_string(),
+            ));
+        }
+        if self.db.get_user_by_name(username).await?.is_some() {
+            return Err(DbError::InvalidInput(format!(
+                "username {} is taken",
+                username
+            )));
+        }
+        let password_hash = match &user.password {
+            Some(password) => Some(hash_password(password)?),
+            None => None,
+        };
+        self.db
+            .create_user(
+                &NewUser {
+                    username: username.to_string(),
+                    is_admin: user.is_admin,
+                    password: None,
+                },
+                password_hash.as_deref(),
+            )
+            .await
+    }
+
+    pub async fn set_user_disabled(&self, id: i64, disabled: bool) -> DbResult<()> {
+        self.db.set_user_disabled(id, disabled).await
+    }
+
+    /// Delete a user with their subscriptions and state, and any feeds nobody else follows.
+    pub async fn delete_user(&self, id: i64) -> DbResult<()> {
+        self.db.delete_user(id).await
+    }
+
+    /// Set a user's password, or clear it with `None`. Either way the user is signed out
+    /// of every session.
+    pub async fn set_password(&self, id: i64, password: Option<&str>) -> DbResult<()> {
+        let password_hash = match password {
+            Some(password) => Some(hash_password(password)?),
+            None => None,
+        };
+        self.db
+            .set_password_hash(id, password_hash.as_deref())
+            .await
+    }
+
+    /// The user with this username and password. Disabled users are returned as well, so
+    /// callers can tell them apart from a wrong password.
+    pub async fn verify_login(&self, username: &str, password: &str) -> DbResult<Option<User>> {
+        let Some(user) = self.get_user_by_name(username).await? else {
+            return Ok(None);
+        };
+        let valid = self
+            .db
+            .get_password_hash(user.id)
+            .await?
+            .is_some_and(|hash| auth::verify_password(password, &hash));
+        Ok(valid.then_some(user))
+    }
+
+    /// Start a session for a user, returning its token.
+    pub async fn create_session(&self, user_id: i64) -> DbResult<String> {
+        let token = auth::generate_token();
+        self.db
+            .create_session(
+                user_id,
+                &auth::hash_token(&token),
+                Utc::now() + SESSION_LIFETIME,
+            )
+            .await?;
+        Ok(token)
+    }
+
+    pub async fn get_session_user(&self, token: &str) -> DbResult<Option<User>> {
+        self.db.get_session_user(&auth::hash_token(token)).await
+    }
+
+    pub async fn delete_session(&self, token: &str) -> DbResult<()> {
+        self.db.delete_session(&auth::hash_token(token)).await
+    }
+
+    pub async fn get_api_tokens(&self) -> DbResult<Vec<ApiToken>> {
+        self.db.get_api_tokens().await
+    }
+
+    /// Create an API token for the acting user. Its secret is only available here.
+    pub async fn create_api_token(&self, name: &str) -> DbResult<CreatedApiToken> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(DbError::InvalidInput(
+                "token name must not be empty".to_string(),
+            ));
+        }
+        let secret = auth::generate_token();
+        let token = self
+            .db
+            .create_api_token(name, &auth::hash_token(&secret))
+            .await?;
+        Ok(CreatedApiToken { token, secret })
+    }
+
+    pub async fn delete_api_token(&self, id: i64) -> DbResult<()> {
+        self.db.delete_api_token(id).await
+    }
+
+    pub async fn get_token_user(&self, token: &str) -> DbResult<Option<User>> {
+        self.db.get_token_user(&auth::hash_token(token)).await
+    }
+
+    /// Set the password Fever clients log in to the acting user's account with, or turn
+    /// Fever access off with `None`. It is separate from the account password because the
+    /// Fever API key is an unsalted MD5 of the username and password.
+    pub async fn set_fever_password(&self, password: Option<&str>) -> DbResult<()> {
+        let key_hash = match password {
+            Some(password) => {
+                check_password(password)?;
+                let user_id = self.db.user_id();
+                let user = self
+                    .db
+                    .get_user(user_id)
+                    .await?
+                    .ok_or_else(|| DbError::NotFound(format!("user {}", user_id)))?;
+                Some(auth::hash_token(&auth::fever_api_key(
+                    &user.username,
+                    password,
+                )))
+            }
+            None => None,
+        };
+        self.db.set_fever_key_hash(key_hash.as_deref()).await
+    }
+
+    /// The user a Fever `api_key` belongs to.
+    pub async fn get_fever_user(&self, api_key: &str) -> DbResult<Option<User>> {
+        let api_key = api_key.trim().to_ascii_lowercase();
+        self.db.get_fever_user(&auth::hash_token(&api_key)).await
+    }
+}
+
+fn check_password(password: &str) -> DbResult<()> {
+    if password.chars().count() < MIN_PASSWORD_LEN {
+        return Err(DbError::InvalidInput(format!(
+            "password must be at least {} characters",
+            MIN_PASSWORD_LEN
+        )));
+    }
+    Ok(())
+}
+
+fn hash_password(password: &str) -> DbResult<String> {
+    check_password(password)?;
+    auth::hash_password(password).map_err(|e| DbError::InvalidInput(e.to_string()))
+}
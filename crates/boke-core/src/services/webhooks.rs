@@ -0,0 +1,571 @@
+//! Outgoing webhooks: signed POSTs to user-chosen URLs when articles arrive, are
+//! favorited or are labelled.
+//!
+//! Each call carries a JSON body and three headers: `X-Boke-Event` with the event,
+//! `X-Boke-Delivery` with an id shared by every attempt at that call, and
+//! `X-Boke-Signature` with `sha256=` and the hex HMAC-SHA256 of the body keyed with the
+//! webhook's secret. Calls are made in the background and retried with growing delays
+//! until the receiver answers with a 2xx status; every attempt is logged.
+
+use crate::auth::generate_token;
+use crate::db::{Database, DbError, DbResult};
+use crate::events::{AppEvent, Audience, BusEvent, EventBus};
+use crate::folder_tree;
+use crate::models::{
+    Article, CreatedWebhook, Folder, Label, NewWebhook, NewWebhookDelivery, Webhook,
+    WebhookDelivery, WebhookEvent,
+};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast::error::RecvError;
+
+/// Tries at each call, including the first.
+pub const WEBHOOK_MAX_ATTEMPTS: i64 = 5;
+
+/// Wait before the first retry; it doubles for every one after.
+const FIRST_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Delivery attempts listed per webhook.
+const DELIVERIES_LISTED: i64 = 50;
+
+/// The event of a test call.
+const PING_EVENT: &str = "ping";
+
+/// The body of a webhook call.
+#[derive(Serialize)]
+struct Payload<'a> {
+    /// Same as `X-Boke-Delivery`.
+    id: &'a str,
+    event: &'a str,
+    webhook_id: i64,
+    created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    article: Option<&'a Article>,
+    /// The label that was put on the article.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<&'a Label>,
+}
+
+/// One call to make, the same body for every attempt.
+struct Call {
+    webhook: Webhook,
+    delivery_id: String,
+    event: &'static str,
+    body: Vec<u8>,
+}
+
+impl Call {
+    fn new(
+        webhook: Webhook,
+        event: &'static str,
+        article: Option<&Article>,
+        label: Option<&Label>,
+    ) -> Self {
+        let delivery_id = generate_token()[..16].to_string();
+        let body = serde_json::to_vec(&Payload {
+            id: &delivery_id,
+            event,
+            webhook_id: webhook.id,
+            created_at: Utc::now(),
+            article,
+            label,
+        })
+        .expect("webhook payloads serialize");
+        Self {
+            webhook,
+            delivery_id,
+            event,
+            body,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct WebhookService<D: Database> {
+    db: Arc<D>,
+    http_client: reqwest::Client,
+}
+
+impl<D: Database> WebhookService<D> {
+    pub fn new(db: Arc<D>) -> Self {
+        let http_client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(10))
+            .user_agent("Boke RSS Reader")
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self { db, http_client }
+    }
+
+    /// The same service acting for another user, sharing the HTTP client.
+    pub fn for_user(&self, user_id: i64) -> Self {
+        Self {
+            db: Arc::new(self.db.for_user(user_id)),
+            http_client: self.http_client.clone(),
+        }
+    }
+
+    pub async fn get_webhooks(&self) -> DbResult<Vec<Webhook>> {
+        self.db.get_webhooks().await
+    }
+
+    pub async fn get_webhook(&self, id: i64) -> DbResult<Option<Webhook>> {
+        self.db.get_webhook(id).await
+    }
+
+    /// Add a webhook, signing its calls with the given secret or a generated one.
+    pub async fn create_webhook(&self, webhook: &NewWebhook) -> DbResult<CreatedWebhook> {
+        let webhook = self.validate(webhook).await?;
+        let secret = webhook
+            .secret
+            .clone()
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(generate_token);
+        let created = self.db.create_webhook(&webhook, &secret).await?;
+        Ok(CreatedWebhook {
+            webhook: created,
+            secret,
+        })
+    }
+
+    pub async fn update_webhook(&self, id: i64, webhook: &NewWebhook) -> DbResult<()> {
+        let webhook = self.validate(webhook).await?;
+        self.db.update_webhook(id, &webhook).await
+    }
+
+    pub async fn delete_webhook(&self, id: i64) -> DbResult<()> {
+        self.db.delete_webhook(id).await
+    }
+
+    /// The webhook's latest delivery attempts, newest first.
+    pub async fn get_deliveries(&self, id: i64) -> DbResult<Vec<WebhookDelivery>> {
+        self.require(id).await?;
+        self.db.get_webhook_deliveries(id, DELIVERIES_LISTED).await
+    }
+
+    /// Send the webhook a `ping` call right away, once, and return the logged attempt.
+    pub async fn test_webhook(&self, id: i64) -> DbResult<WebhookDelivery> {
+        let webhook = self.require(id).await?;
+        let call = Call::new(webhook, PING_EVENT, None, None);
+        let attempt = self.send(&call, 1).await;
+        self.db.record_webhook_delivery(&attempt).await?;
+        self.db
+            .get_webhook_deliveries(id, 1)
+            .await?
+            .pop()
+            .ok_or_else(|| DbError::NotFound(format!("webhook {}", id)))
+    }
+
+    /// Deliver webhooks for the events published on `events`, until the bus is dropped.
+    pub fn dispatch(&self, events: &EventBus) -> tokio::task::JoinHandle<()> {
+        let mut receiver = events.subscribe();
+        let service = self.clone();
+        tokio::spawn(async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => {
+                        if let Err(e) = service.handle(event).await {
+                            log::warn!("Failed to dispatch webhooks: {}", e);
+                        }
+                    }
+                    Err(RecvError::Lagged(missed)) => {
+                        log::warn!("Webhooks fell behind and missed {} events", missed);
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    async fn require(&self, id: i64) -> DbResult<Webhook> {
+        self.db
+            .get_webhook(id)
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("webhook {}", id)))
+    }
+
+    /// Check a webhook's URL and filters, trimming what the user typed.
+    async fn validate(&self, webhook: &NewWebhook) -> DbResult<NewWebhook> {
+        let url = webhook.url.trim();
+        let parsed = url::Url::parse(url)
+            .map_err(|e| DbError::InvalidInput(format!("invalid webhook URL: {}", e)))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(DbError::InvalidInput(
+                "webhook URL must be http or https".to_string(),
+            ));
+        }
+        if let Some(feed_id) = webhook.feed_id
+            && self.db.get_feed(feed_id).await?.is_none()
+        {
+            return Err(DbError::InvalidInput(format!("no feed {}", feed_id)));
+        }
+        if let Some(folder_id) = webhook.folder_id
+            && !self
+                .db
+                .get_folders()
+                .await?
+                .iter()
+                .any(|f| f.id == folder_id)
+        {
+            return Err(DbError::InvalidInput(format!("no folder {}", folder_id)));
+        }
+
+        Ok(NewWebhook {
+            url: url.to_string(),
+            keyword: webhook
+                .keyword
+                .as_deref()
+                .map(str::trim)
+                .filter(|k| !k.is_empty())
+                .map(str::to_string),
+            ..webhook.clone()
+        })
+    }
+
+    /// Start the calls an event asks for, to each owner's matching webhooks.
+    async fn handle(&self, bus_event: BusEvent) -> DbResult<()> {
+        let (event, article_ids, label_id) = match bus_event.event {
+            AppEvent::NewArticles { article_ids, .. } => {
+                (WebhookEvent::NewArticle, article_ids, None)
+            }
+            AppEvent::ArticleFavorited { article_id } => {
+                (WebhookEvent::Favorited, vec![article_id], None)
+            }
+            AppEvent::ArticlesLabelled {
+                label_id,
+                article_ids,
+            } => (WebhookEvent::Labelled, article_ids, Some(label_id)),
+            _ => return Ok(()),
+        };
+
+        let mut by_owner: HashMap<i64, Vec<Webhook>> = HashMap::new();
+        for webhook in self.db.get_enabled_webhooks(event).await? {
+            if let Audience::User(user_id) = bus_event.audience
+                && webhook.user_id != user_id
+            {
+                continue;
+            }
+            by_owner.entry(webhook.user_id).or_default().push(webhook);
+        }
+
+        for (owner, webhooks) in by_owner {
+            // Everything as the owner sees it, which also leaves out feeds of a refresh
+            // they do not subscribe to
+            let db = self.db.for_user(owner);
+            let folders = db.get_folders().await?;
+            let label = match label_id {
+                Some(label_id) => db
+                    .get_labels()
+                    .await?
+                    .into_iter()
+                    .find(|l| l.id == label_id),
+                None => None,
+            };
+            let mut feed_folders: HashMap<i64, Option<i64>> = HashMap::new();
+
+            for &article_id in &article_ids {
+                let Some(mut article) = db.get_article(article_id).await? else {
+                    continue;
+                };
+                article.label_ids = db
+                    .get_article_labels(&[article_id])
+                    .await?
+                    .into_iter()
+                    .map(|(_, label_id)| label_id)
+                    .collect();
+                let folder_id = match feed_folders.get(&article.feed_id) {
+                    Some(folder_id) => *folder_id,
+                    None => {
+                        let folder_id = db
+                            .get_feed(article.feed_id)
+                            .await?
+                            .and_then(|f| f.folder_id);
+                        feed_folders.insert(article.feed_id, folder_id);
+                        folder_id
+                    }
+                };
+
+                for webhook in &webhooks {
+                    if passes_filters(webhook, &article, folder_id, &folders) {
+                        let call = Call::new(
+                            webhook.clone(),
+                            event.as_str(),
+                            Some(&article),
+                            label.as_ref(),
+                        );
+                        tokio::spawn(self.clone().deliver(call));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Make a call until it succeeds, runs out of attempts, or its webhook is removed or
+    /// disabled.
+    async fn deliver(self, call: Call) {
+        let db = self.db.for_user(call.webhook.user_id);
+        for attempt in 1..=WEBHOOK_MAX_ATTEMPTS {
+            if attempt > 1 {
+                tokio::time::sleep(retry_delay(attempt)).await;
+                match db.get_webhook(call.webhook.id).await {
+                    Ok(Some(webhook)) if webhook.enabled => {}
+                    Ok(_) => return,
+                    Err(e) => log::warn!("Failed to look up webhook {}: {}", call.webhook.id, e),
+                }
+            }
+
+            let result = self.send(&call, attempt).await;
+            let success = result.success;
+            if let Err(e) = db.record_webhook_delivery(&result).await {
+                log::warn!(
+                    "Failed to log delivery of webhook {}: {}",
+                    call.webhook.id,
+                    e
+                );
+            }
+            if success {
+                return;
+            }
+        }
+        log::warn!(
+            "Gave up delivering {} to webhook {} after {} attempts",
+            call.delivery_id,
+            call.webhook.id,
+            WEBHOOK_MAX_ATTEMPTS
+        );
+    }
+
+    /// Make one attempt at a call.
+    async fn send(&self, call: &Call, attempt: i64) -> NewWebhookDelivery {
+        let mut delivery = NewWebhookDelivery {
+            webhook_id: call.webhook.id,
+            delivery_id: call.delivery_id.clone(),
+            event: call.event.to_string(),
+            attempt,
+            ..Default::default()
+        };
+
+        let started = Instant::now();
+        let response = self
+            .http_client
+            .post(&call.webhook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header("X-Boke-Event", call.event)
+            .header("X-Boke-Delivery", &call.delivery_id)
+            .header("X-Boke-Signature", sign(&call.webhook.secret, &call.body))
+            .body(call.body.clone())
+            .send()
+            .await;
+        delivery.duration_ms = started.elapsed().as_millis() as i64;
+
+        match response {
+            Ok(response) => {
+                let status = response.status();
+                delivery.status_code = Some(status.as_u16() as i64);
+                delivery.success = status.is_success();
+                if !delivery.success {
+                    delivery.error = Some(format!("HTTP {}", status));
+                }
+            }
+            Err(e) => delivery.error = Some(e.to_string()),
+        }
+        delivery
+    }
+}
+
+/// The `X-Boke-Signature` of a body.
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC takes keys of any size");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// How long to wait before the given attempt; attempts start at 1.
+fn retry_delay(attempt: i64) -> Duration {
+    FIRST_RETRY_DELAY * 2u32.pow((attempt - 2).clamp(0, 16) as u32)
+}
+
+/// Whether an article passes a webhook's filters. `folder_id` is where the owner keeps
+/// the article's feed, among their `folders`.
+fn passes_filters(
+    webhook: &Webhook,
+    article: &Article,
+    folder_id: Option<i64>,
+    folders: &[Folder],
+) -> bool {
+    if webhook.feed_id.is_some_and(|id| id != article.feed_id) {
+        return false;
+    }
+    if let Some(wanted) = webhook.folder_id
+        && !folder_id.is_some_and(|id| folder_tree::is_within(folders, id, wanted))
+    {
+        return false;
+    }
+    if let Some(keyword) = &webhook.keyword {
+        let keyword = keyword.to_lowercase();
+        let found = [
+            Some(&article.title),
+            article.summary.as_ref(),
+            article.content.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .any(|text| text.to_lowercase().contains(&keyword));
+        if !found {
+            return false;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn webhook() -> Webhook {
+        Webhook {
+            id: 1,
+            user_id: 1,
+            url: "http://localhost/hook".to_string(),
+            event: WebhookEvent::NewArticle,
+            feed_id: None,
+            folder_id: None,
+            keyword: None,
+            enabled: true,
+            secret: "secret".to_string(),
+            created_at: None,
+        }
+    }
+
+    fn article(feed_id: i64, title: &str, content: Option<&str>) -> Article {
+        Article {
+            id: 7,
+            feed_id,
+            guid: "guid".to_string(),
+            title: title.to_string(),
+            link: None,
+            author: None,
+            summary: None,
+            content: content.map(str::to_string),
+            image_url: None,
+            enclosure_url: None,
+            enclosure_type: None,
+            published_at: None,
+            is_read: false,
+            is_favorite: false,
+            created_at: None,
+            read_at: None,
+            favorited_at: None,
+            dedup_group_id: None,
+            feed_title: None,
+            feed_favicon_url: None,
+            duplicates: Vec::new(),
+            label_ids: Vec::new(),
+        }
+    }
+
+    fn folder(id: i64, parent_id: Option<i64>) -> Folder {
+        Folder {
+            id,
+            name: format!("folder {id}"),
+            parent_id,
+            position: 0,
+            feed_count: 0,
+            unread_count: 0,
+        }
+    }
+
+    #[test]
+    fn signs_with_hmac_sha256() {
+        // RFC 4231, test case 2
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn retries_back_off_exponentially() {
+        assert_eq!(retry_delay(2), Duration::from_secs(5));
+        assert_eq!(retry_delay(3), Duration::from_secs(10));
+        assert_eq!(retry_delay(5), Duration::from_secs(40));
+    }
+
+    #[test]
+    fn no_filters_match_everything() {
+        assert!(passes_filters(
+            &webhook(),
+            &article(3, "Anything", None),
+            None,
+            &[]
+        ));
+    }
+
+    #[test]
+    fn feed_filter_matches_only_that_feed() {
+        let webhook = Webhook {
+            feed_id: Some(3),
+            ..webhook()
+        };
+        assert!(passes_filters(&webhook, &article(3, "A", None), None, &[]));
+        assert!(!passes_filters(&webhook, &article(4, "A", None), None, &[]));
+    }
+
+    #[test]
+    fn folder_filter_includes_sub_folders() {
+        let folders = vec![folder(1, None), folder(2, Some(1)), folder(3, None)];
+        let webhook = Webhook {
+            folder_id: Some(1),
+            ..webhook()
+        };
+        let article = article(3, "A", None);
+        assert!(passes_filters(&webhook, &article, Some(1), &folders));
+        assert!(passes_filters(&webhook, &article, Some(2), &folders));
+        assert!(!passes_filters(&webhook, &article, Some(3), &folders));
+        assert!(!passes_filters(&webhook, &article, None, &folders));
+    }
+
+    #[test]
+    fn keyword_filter_ignores_case_and_reads_content() {
+        let webhook = Webhook {
+            keyword: Some("Rust".to_string()),
+            ..webhook()
+        };
+        assert!(passes_filters(
+            &webhook,
+            &article(3, "Why rust?", None),
+            None,
+            &[]
+        ));
+        assert!(passes_filters(
+            &webhook,
+            &article(3, "Release notes", Some("<p>RUST 2.0 is out</p>")),
+            None,
+            &[]
+        ));
+        assert!(!passes_filters(
+            &webhook,
+            &article(3, "Go 2.0", None),
+            None,
+            &[]
+        ));
+    }
+
+    #[test]
+    fn payload_leaves_out_what_the_event_lacks() {
+        let call = Call::new(webhook(), PING_EVENT, None, None);
+        let json: serde_json::Value = serde_json::from_slice(&call.body).unwrap();
+        assert_eq!(json["id"], call.delivery_id.as_str());
+        assert_eq!(json["event"], "ping");
+        assert_eq!(json["webhook_id"], 1);
+        assert!(json.get("article").is_none());
+        assert!(json.get("label").is_none());
+    }
+}
@@ -0,0 +1,242 @@
+//! Helpers shared by the database-backed integration tests.
+//!
+//! Each test gets a fresh SQLite file, or a fresh Postgres database on the server
+//! named by `BOKE_TEST_POSTGRES` (e.g. `postgres://postgres@127.0.0.1:5432`).
+
+#![allow(dead_code)]
+
+use boke_core::db::{Database, DatabasePool, InsertResult};
+use boke_core::models::{NewArticle, NewFeed, NewUser};
+use chrono::{DateTime, Utc};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NEXT_DB: AtomicUsize = AtomicUsize::new(0);
+
+/// A unique name for a test database, so tests can run in parallel.
+fn db_name() -> String {
+    format!(
+        "boke_test_{}_{}",
+        std::process::id(),
+        NEXT_DB.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+pub fn sqlite_path() -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("{}.db", db_name()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+pub async fn test_db() -> DatabasePool {
+    if let Ok(server) = std::env::var("BOKE_TEST_POSTGRES") {
+        #[cfg(feature = "postgres")]
+        return postgres_db(&server).await;
+        #[cfg(not(feature = "postgres"))]
+        panic!("BOKE_TEST_POSTGRES is set but PostgreSQL support is not compiled in: {server}");
+    }
+    DatabasePool::from_url(&format!("sqlite://{}", sqlite_path().display()))
+        .await
+        .unwrap()
+}
+
+#[cfg(feature = "postgres")]
+async fn postgres_db(server: &str) -> DatabasePool {
+    let url = empty_postgres_db(server).await;
+    DatabasePool::from_url(&url).await.unwrap()
+}
+
+/// Create a database with no tables on the Postgres `server`, returning its URL.
+#[cfg(feature = "postgres")]
+pub async fn empty_postgres_db(server: &str) -> String {
+    use sqlx::Executor;
+
+    let name = db_name();
+    let admin = sqlx::PgPool::connect(&format!("{}/postgres", server))
+        .await
+        .unwrap();
+    admin
+        .execute(format!("DROP DATABASE IF EXISTS {}", name).as_str())
+        .await
+        .unwrap();
+    admin
+        .execute(
+            format!(
+                "CREATE DATABASE {} ENCODING 'UTF8' TEMPLATE template0",
+                name
+            )
+            .as_str(),
+        )
+        .await
+        .unwrap();
+    format!("{}/{}", server, name)
+}
+
+/// A second account on `db`, acting as itself.
+pub async fn other_user(db: &DatabasePool, username: &str) -> DatabasePool {
+    let user = db
+        .create_user(
+            &NewUser {
+                username: username.to_string(),
+                is_admin: false,
+                password: None,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    db.for_user(user.id)
+}
+
+/// Store `article`, which must be new, and return its id.
+pub async fn insert(db: &DatabasePool, article: &NewArticle) -> i64 {
+    match db.insert_article(article).await.unwrap() {
+        InsertResult::Inserted(id) => id,
+        InsertResult::Ignored => panic!("{} was already stored", article.guid),
+    }
+}
+
+pub fn feed(url: &str) -> NewFeed {
+    NewFeed {
+        title: url.to_string(),
+        folder_id: None,
+        feed_url: url.to_string(),
+        site_url: None,
+        description: None,
+        language: None,
+        favicon_url: None,
+        last_build_date: None,
+    }
+}
+
+pub fn article(feed_id: i64, guid: &str, title: &str, link: &str) -> NewArticle {
+    article_at(feed_id, guid, title, link, Utc::now())
+}
+
+pub fn article_at(
+    feed_id: i64,
+    guid: &str,
+    title: &str,
+    link: &str,
+    published_at: DateTime<Utc>,
+) -> NewArticle {
+    NewArticle {
+        feed_id,
+        guid: guid.to_string(),
+        title: title.to_string(),
+        link: Some(link.to_string()),
+        author: None,
+        summary: None,
+        content: Some(format!("<p>{}</p>", title)),
+        image_url: None,
+        enclosure_url: None,
+        enclosure_type: None,
+        published_at: Some(published_at),
+        normalized_link: boke_core::dedup::normalize_link(link),
+        categories: Vec::new(),
+    }
+}
+
+/// An HTTP request as seen by [`serve`].
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    /// Names in lowercase.
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Serve HTTP on a free local port, answering every request with what `respond` gives
+/// for it: a status code and a body. Returns the base URL.
+pub async fn serve<F>(respond: F) -> String
+where
+    F: Fn(&Request) -> (u16, String) + Send + Sync + 'static,
+{
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let respond = std::sync::Arc::new(respond);
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut stream, _)) = listener.accept().await else {
+                return;
+            };
+            let respond = respond.clone();
+            tokio::spawn(async move {
+                let mut data = Vec::new();
+                let mut buf = [0; 4096];
+                let head_len = loop {
+                    if let Some(end) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+                        break end + 4;
+                    }
+                    match stream.read(&mut buf).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(n) => data.extend_from_slice(&buf[..n]),
+                    }
+                };
+
+                let head = String::from_utf8_lossy(&data[..head_len]).into_owned();
+                let mut lines = head.lines();
+                let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+                let method = request_line.next().unwrap_or_default().to_string();
+                let path = request_line.next().unwrap_or("/").to_string();
+                let headers: Vec<_> = lines
+                    .filter_map(|line| line.split_once(':'))
+                    .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+                    .collect();
+                let content_length = headers
+                    .iter()
+                    .find(|(name, _)| name == "content-length")
+                    .and_then(|(_, value)| value.parse().ok())
+                    .unwrap_or(0);
+                while data.len() < head_len + content_length {
+                    match stream.read(&mut buf).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(n) => data.extend_from_slice(&buf[..n]),
+                    }
+                }
+
+                let request = Request {
+                    method,
+                    path,
+                    headers,
+                    body: data[head_len..].to_vec(),
+                };
+                let (status, body) = respond(&request);
+                let response = format!(
+                    "HTTP/1.1 {} X\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            });
+        }
+    });
+    base
+}
+
+/// A minimal RSS document with one item per `(guid, title)`.
+pub fn rss(title: &str, items: &[(&str, &str)]) -> String {
+    let items: String = items
+        .iter()
+        .map(|(guid, title)| {
+            format!(
+                "<item><guid>{guid}</guid><title>{title}</title><link>https://example.com/{guid}</link></item>"
+            )
+        })
+        .collect();
+    format!(
+        r#"<?xml version="1.0"?><rss version="2.0"><channel><title>{title}</title>{items}</channel></rss>"#
+    )
+}
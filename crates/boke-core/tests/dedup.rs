@@ -0,0 +1,244 @@
+mod common;
+
+use boke_core::db::{Database, DatabasePool};
+use boke_core::dedup::MAX_TITLE_CANDIDATES;
+use boke_core::models::{ArticleQuery, FeedStatus};
+use boke_core::{ArticleService, FeedService};
+use chrono::{Duration, Utc};
+use common::{article, article_at, feed, insert, test_db};
+use std::sync::Arc;
+
+#[tokio::test]
+async fn link_and_guid_matches_survive_a_busy_window() {
+    let db = test_db().await;
+    let busy = db
+        .insert_feed(&feed("https://busy.example/feed"))
+        .await
+        .unwrap();
+    let origin = db
+        .insert_feed(&feed("https://origin.example/feed"))
+        .await
+        .unwrap();
+    let proxy = db
+        .insert_feed(&feed("https://proxy.example/feed"))
+        .await
+        .unwrap();
+    let reader = db
+        .insert_feed(&feed("https://reader.example/feed"))
+        .await
+        .unwrap();
+
+    for i in 0..MAX_TITLE_CANDIDATES + 50 {
+        let link = format!("https://busy.example/{}", i);
+        insert(
+            &db,
+            &article(busy, &link, &format!("Busy story {}", i), &link),
+        )
+        .await;
+    }
+    let scoop = "https://origin.example/scoop";
+    let original = insert(&db, &article(origin, scoop, "A scoop", scoop)).await;
+    // Proxied links hide the original, but the GUID still names it
+    let proxied = insert(
+        &db,
+        &article(proxy, scoop, "A scoop", "https://proxy.example/r/42"),
+    )
+    .await;
+
+    let same_link = article(
+        reader,
+        "reader-1",
+        "Something else",
+        "https://origin.example/scoop?utm_source=rss",
+    );
+    let ids: Vec<_> = db
+        .find_duplicate_candidates(&same_link)
+        .await
+        .unwrap()
+        .iter()
+        .map(|c| c.id)
+        .collect();
+    assert!(ids.contains(&original));
+
+    let same_guid = article(
+        reader,
+        scoop,
+        "Something else",
+        "https://reader.example/r/1",
+    );
+    let ids: Vec<_> = db
+        .find_duplicate_candidates(&same_guid)
+        .await
+        .unwrap()
+        .iter()
+        .map(|c| c.id)
+        .collect();
+    assert!(ids.contains(&original));
+    assert!(ids.contains(&proxied));
+}
+
+#[tokio::test]
+async fn title_candidates_are_the_most_recent_in_the_window() {
+    let db = test_db().await;
+    let busy = db
+        .insert_feed(&feed("https://busy.example/feed"))
+        .await
+        .unwrap();
+    let other = db
+        .insert_feed(&feed("https://other.example/feed"))
+        .await
+        .unwrap();
+    let reader = db
+        .insert_feed(&feed("https://reader.example/feed"))
+        .await
+        .unwrap();
+    let now = Utc::now();
+
+    for i in 0..MAX_TITLE_CANDIDATES + 50 {
+        let link = format!("https://busy.example/{}", i);
+        let published_at = now - Duration::hours(1) - Duration::seconds(i);
+        insert(
+            &db,
+            &article_at(busy, &link, "Busy story", &link, published_at),
+        )
+        .await;
+    }
+    let recent = insert(
+        &db,
+        &article_at(
+            other,
+            "recent",
+            "Rust 1.80 released with LazyLock",
+            "https://other.example/recent",
+            now - Duration::minutes(1),
+        ),
+    )
+    .await;
+
+    let copy = article_at(
+        reader,
+        "copy",
+        "Rust 1.80 released with LazyLock!",
+        "https://reader.example/copy",
+        now,
+    );
+    let candidates = db.find_duplicate_candidates(&copy).await.unwrap();
+    assert_eq!(candidates.len() as i64, MAX_TITLE_CANDIDATES);
+    assert_eq!(candidates[0].id, recent);
+}
+
+/// Three feeds carrying the same story, grouped as a refresh would, and one other story
+/// in the first feed. Returns the feed ids, the story's copies earliest first, and the
+/// other story.
+async fn shared_story(db: &DatabasePool) -> ([i64; 3], [i64; 3], i64) {
+    let mut feeds = [0; 3];
+    let mut copies = [0; 3];
+    for (n, host) in ["a.example", "b.example", "c.example"].iter().enumerate() {
+        feeds[n] = db
+            .insert_feed(&feed(&format!("https://{}/feed", host)))
+            .await
+            .unwrap();
+        let link = format!("https://{}/story", host);
+        copies[n] = insert(db, &article(feeds[n], &link, "The story", &link)).await;
+    }
+    for &copy in &copies[1..] {
+        db.set_duplicate_group(copy, copies[0]).await.unwrap();
+    }
+    let link = "https://a.example/other";
+    let other = insert(db, &article(feeds[0], link, "Another story", link)).await;
+    (feeds, copies, other)
+}
+
+fn collapsed() -> ArticleQuery {
+    ArticleQuery {
+        limit: 50,
+        collapse_duplicates: true,
+        ..Default::default()
+    }
+}
+
+/// The rows of a collapsed list as (article, feeds of its other copies), by id.
+async fn rows(
+    articles: &ArticleService<DatabasePool>,
+    query: ArticleQuery,
+) -> Vec<(i64, Vec<i64>)> {
+    let mut rows: Vec<_> = articles
+        .get_articles(query)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|a| (a.id, a.duplicates.iter().map(|d| d.feed_id).collect()))
+        .collect();
+    rows.sort();
+    rows
+}
+
+#[tokio::test]
+async fn collapsed_lists_show_one_copy_with_the_others_as_sources() {
+    let db = test_db().await;
+    let articles = ArticleService::new(Arc::new(db.clone()));
+    let ([a, b, c], [first, second, _], other) = shared_story(&db).await;
+
+    let all = rows(&articles, collapsed()).await;
+    assert_eq!(all, vec![(first, vec![b, c]), (other, vec![])]);
+    assert_eq!(db.count_articles(&collapsed()).await.unwrap(), 2);
+
+    // A feed's own view keeps its copy even though an older one lives elsewhere
+    let in_b = ArticleQuery {
+        feed_id: Some(b),
+        ..collapsed()
+    };
+    assert_eq!(rows(&articles, in_b).await, vec![(second, vec![a, c])]);
+}
+
+#[tokio::test]
+async fn hidden_or_muted_copies_do_not_stand_in_for_the_group() {
+    let db = test_db().await;
+    let articles = ArticleService::new(Arc::new(db.clone()));
+    let feeds = FeedService::new(Arc::new(db.clone()));
+    let ([_, b, c], [first, second, third], other) = shared_story(&db).await;
+
+    // The next copy represents the group, and the hidden one is no longer a source
+    db.hide_article(first).await.unwrap();
+    let expected = vec![(second, vec![c]), (other, vec![])];
+    assert_eq!(rows(&articles, collapsed()).await, expected);
+    let unread = ArticleQuery {
+        unread_only: true,
+        ..collapsed()
+    };
+    assert_eq!(rows(&articles, unread).await, expected);
+
+    feeds.set_feed_status(b, FeedStatus::Muted).await.unwrap();
+    let expected = vec![(third, vec![b]), (other, vec![])];
+    assert_eq!(rows(&articles, collapsed()).await, expected);
+}
+
+#[tokio::test]
+async fn reading_any_copy_marks_the_whole_group() {
+    let db = test_db().await;
+    let ([_, _, c], copies, other) = shared_story(&db).await;
+    let is_read = |id| {
+        let db = db.clone();
+        async move { db.get_article(id).await.unwrap().unwrap().is_read }
+    };
+
+    db.set_read(copies[1], true).await.unwrap();
+    for id in copies {
+        assert!(is_read(id).await);
+    }
+    db.set_read(copies[0], false).await.unwrap();
+    for id in copies {
+        assert!(!is_read(id).await);
+    }
+
+    // Marking one feed read reaches the copies in the others, but not their other stories
+    let in_c = ArticleQuery {
+        feed_id: Some(c),
+        ..Default::default()
+    };
+    db.mark_articles_read(&in_c).await.unwrap();
+    for id in copies {
+        assert!(is_read(id).await);
+    }
+    assert!(!is_read(other).await);
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use boke_core::StatsService;
+use boke_core::db::{Database, DbError};
+use boke_core::models::NewFeedFetch;
+use chrono::{Duration, Utc};
+use common::{article_at, feed, insert, other_user, test_db};
+use std::sync::Arc;
+
+#[tokio::test]
+async fn health_reports_activity_and_reliability() {
+    let db = test_db().await;
+    let stats = StatsService::new(Arc::new(db.clone()));
+    let lively = db
+        .insert_feed(&feed("http://health.test/lively"))
+        .await
+        .unwrap();
+    let quiet = db
+        .insert_feed(&feed("http://health.test/quiet"))
+        .await
+        .unwrap();
+    let now = Utc::now();
+    // 13 posts in the 90-day window is about one a week; older ones don't count
+    for n in 0..13 {
+        let at = now - Duration::days(n * 7 + 1);
+        let link = format!("http://health.test/lively/{}", n);
+        insert(
+            &db,
+            &article_at(lively, &link, &format!("Post {}", n), &link, at),
+        )
+        .await;
+    }
+    let old = "http://health.test/lively/old";
+    insert(
+        &db,
+        &article_at(lively, old, "Old", old, now - Duration::days(200)),
+    )
+    .await;
+    let last = "http://health.test/quiet/last";
+    insert(
+        &db,
+        &article_at(quiet, last, "Last", last, now - Duration::days(200)),
+    )
+    .await;
+
+    // Every fourth fetch fails; only the latest 100 are kept
+    for n in 0..105 {
+        let failed = n % 4 == 0;
+        db.record_feed_fetch(&NewFeedFetch {
+            feed_id: lively,
+            success: !failed,
+            status_code: Some(if failed { 503 } else { 200 }),
+            duration_ms: 100 + n % 2 * 100,
+            bytes: Some(1000),
+            new_articles: 0,
+            error: failed.then(|| "HTTP 503".to_string()),
+        })
+        .await
+        .unwrap();
+    }
+
+    let health = stats.get_feed_health(lively, 6).await.unwrap();
+    assert!((health.posts_per_week - 13.0 * 7.0 / 90.0).abs() < 0.01);
+    assert!(health.last_entry_at.unwrap() > now - Duration::days(2));
+    assert_eq!(health.fetch_count, 100);
+    assert_eq!(health.success_rate, Some(0.75));
+    assert_eq!(health.avg_response_ms, Some(150.0));
+    assert_eq!(health.avg_response_bytes, Some(1000.0));
+    assert!(!health.is_dead);
+    assert_eq!(health.recent_fetches.len(), 20);
+    let newest = &health.recent_fetches[0];
+    assert_eq!((newest.success, newest.status_code), (false, Some(503)));
+    assert_eq!(newest.error.as_deref(), Some("HTTP 503"));
+
+    let health = stats.get_feed_health(quiet, 6).await.unwrap();
+    assert_eq!(health.posts_per_week, 0.0);
+    assert!(health.is_dead);
+    assert_eq!((health.fetch_count, health.success_rate), (0, None));
+    assert!(health.recent_fetches.is_empty());
+    // A longer cutoff keeps it alive
+    assert!(!stats.get_feed_health(quiet, 12).await.unwrap().is_dead);
+}
+
+#[tokio::test]
+async fn health_of_unknown_or_unsubscribed_feeds_is_not_found() {
+    let db = test_db().await;
+    let mine = db
+        .insert_feed(&feed("http://health.test/mine"))
+        .await
+        .unwrap();
+    let other = other_user(&db, "other").await;
+    let stats = StatsService::new(Arc::new(other));
+
+    assert!(matches!(
+        stats.get_feed_health(mine, 6).await,
+        Err(DbError::NotFound(_))
+    ));
+    assert!(matches!(
+        stats.get_feed_health(9999, 6).await,
+        Err(DbError::NotFound(_))
+    ));
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use boke_core::FeedService;
+use boke_core::db::{Database, DbError};
+use boke_core::models::{ArticleQuery, FeedStatus, FeedUpdate};
+use common::{rss, serve, test_db};
+use std::sync::Arc;
+
+fn all() -> ArticleQuery {
+    ArticleQuery {
+        limit: 50,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn refreshes_and_lists_follow_feed_status() {
+    let base = serve(|request| (200, rss(&request.path, &[(&request.path, "Post")]))).await;
+    let db = test_db().await;
+    let feeds = FeedService::new(Arc::new(db.clone()));
+    let mut ids = Vec::new();
+    for status in [
+        FeedStatus::Active,
+        FeedStatus::Paused,
+        FeedStatus::Muted,
+        FeedStatus::Archived,
+    ] {
+        let id = db
+            .insert_feed(&common::feed(&format!("{}/{}", base, status.as_str())))
+            .await
+            .unwrap();
+        feeds.set_feed_status(id, status).await.unwrap();
+        ids.push(id);
+    }
+    let [active, paused, muted, archived] = ids[..] else {
+        unreachable!()
+    };
+
+    // Paused and archived feeds are left alone; muted ones keep fetching, quietly
+    let mut refreshed = feeds.refresh_all_feeds().await.unwrap();
+    refreshed.sort_by_key(|r| r.feed_id);
+    let refreshed: Vec<_> = refreshed
+        .iter()
+        .map(|r| (r.feed_id, r.new_articles, r.notify))
+        .collect();
+    assert_eq!(refreshed, vec![(active, 1, true), (muted, 1, false)]);
+    // Asked for directly, a paused feed is fetched all the same
+    assert_eq!(feeds.refresh_feed(paused).await.unwrap().new_articles, 1);
+
+    // Muted articles stay out of "All" and unread counts, but not their own feed
+    let listed: Vec<_> = db
+        .get_articles(&all())
+        .await
+        .unwrap()
+        .iter()
+        .map(|a| a.feed_id)
+        .collect();
+    assert!(listed.contains(&active) && listed.contains(&paused) && !listed.contains(&muted));
+    let own = ArticleQuery {
+        feed_id: Some(muted),
+        ..all()
+    };
+    assert_eq!(db.get_articles(&own).await.unwrap().len(), 1);
+    let muted_feed = db
+        .get_feeds()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|f| f.id == muted)
+        .unwrap();
+    assert_eq!(
+        (muted_feed.status, muted_feed.unread_count),
+        (FeedStatus::Muted, 0)
+    );
+
+    let unmuted = feeds
+        .set_feed_status(muted, FeedStatus::Active)
+        .await
+        .unwrap();
+    assert_eq!(unmuted.unread_count, 1);
+    assert!(
+        db.get_feed(archived)
+            .await
+            .unwrap()
+            .unwrap()
+            .last_fetched_at
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn archived_feeds_are_read_only() {
+    let db = test_db().await;
+    let feeds = FeedService::new(Arc::new(db.clone()));
+    let id = db
+        .insert_feed(&common::feed("https://example.com/feed"))
+        .await
+        .unwrap();
+    feeds
+        .set_feed_status(id, FeedStatus::Archived)
+        .await
+        .unwrap();
+
+    let rename = FeedUpdate {
+        title: Some("Renamed".to_string()),
+        ..Default::default()
+    };
+    assert!(matches!(
+        feeds.update_feed(id, &rename).await,
+        Err(DbError::InvalidInput(_))
+    ));
+    let refresh = feeds.refresh_feed(id).await.unwrap_err();
+    assert!(matches!(
+        refresh.downcast_ref::<DbError>(),
+        Some(DbError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        feeds.set_feed_status(id + 1, FeedStatus::Paused).await,
+        Err(DbError::NotFound(_))
+    ));
+
+    // Bringing it back makes it editable again
+    feeds.set_feed_status(id, FeedStatus::Active).await.unwrap();
+    assert_eq!(
+        feeds.update_feed(id, &rename).await.unwrap().title,
+        "Renamed"
+    );
+}
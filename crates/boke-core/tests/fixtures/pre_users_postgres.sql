@@ -0,0 +1,215 @@
+-- The PostgreSQL schema from just before multi-user support
+
+
+CREATE TABLE IF NOT EXISTS folders (
+    id        BIGSERIAL PRIMARY KEY,
+    name      TEXT NOT NULL UNIQUE,
+    parent_id BIGINT REFERENCES folders(id) ON DELETE SET NULL,
+    position  BIGINT NOT NULL DEFAULT 0
+);
+
+CREATE TABLE IF NOT EXISTS feeds (
+    id              BIGSERIAL PRIMARY KEY,
+    title           TEXT NOT NULL,
+    folder_id       BIGINT REFERENCES folders(id) ON DELETE SET NULL,
+    feed_url        TEXT NOT NULL UNIQUE,
+    site_url        TEXT,
+    description     TEXT,
+    language        TEXT,
+    favicon_url     TEXT,
+    last_fetched_at TIMESTAMPTZ,
+    last_build_date TIMESTAMPTZ,
+    created_at      TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at      TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    custom_title    TEXT,
+    refresh_interval_minutes INTEGER,
+    fetch_full_content BOOLEAN NOT NULL DEFAULT FALSE,
+    notify          BOOLEAN NOT NULL DEFAULT TRUE,
+    status          TEXT NOT NULL DEFAULT 'active',
+    position        BIGINT NOT NULL DEFAULT 0
+);
+
+CREATE TABLE IF NOT EXISTS articles (
+    id           BIGSERIAL PRIMARY KEY,
+    feed_id      BIGINT NOT NULL REFERENCES feeds(id) ON DELETE CASCADE,
+    guid         TEXT NOT NULL,
+    title        TEXT NOT NULL,
+    link         TEXT,
+    author       TEXT,
+    summary      TEXT,
+    content      TEXT,
+    image_url    TEXT,
+    enclosure_url  TEXT,
+    enclosure_type TEXT,
+    published_at TIMESTAMPTZ,
+    is_read      BOOLEAN DEFAULT FALSE,
+    is_favorite  BOOLEAN DEFAULT FALSE,
+    created_at   TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    normalized_link TEXT,
+    dedup_group_id  BIGINT,
+    read_at         TIMESTAMPTZ,
+    favorited_at    TIMESTAMPTZ,
+    UNIQUE(feed_id, guid)
+);
+
+-- Columns added after the first release
+ALTER TABLE articles ADD COLUMN IF NOT EXISTS normalized_link TEXT;
+ALTER TABLE articles ADD COLUMN IF NOT EXISTS dedup_group_id BIGINT;
+ALTER TABLE articles ADD COLUMN IF NOT EXISTS read_at TIMESTAMPTZ;
+ALTER TABLE articles ADD COLUMN IF NOT EXISTS favorited_at TIMESTAMPTZ;
+ALTER TABLE articles ADD COLUMN IF NOT EXISTS enclosure_url TEXT;
+ALTER TABLE articles ADD COLUMN IF NOT EXISTS enclosure_type TEXT;
+ALTER TABLE feeds ADD COLUMN IF NOT EXISTS custom_title TEXT;
+ALTER TABLE feeds ADD COLUMN IF NOT EXISTS refresh_interval_minutes INTEGER;
+ALTER TABLE feeds ADD COLUMN IF NOT EXISTS fetch_full_content BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE feeds ADD COLUMN IF NOT EXISTS notify BOOLEAN NOT NULL DEFAULT TRUE;
+ALTER TABLE feeds ADD COLUMN IF NOT EXISTS status TEXT NOT NULL DEFAULT 'active';
+ALTER TABLE feeds ADD COLUMN IF NOT EXISTS position BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE folders ADD COLUMN IF NOT EXISTS parent_id BIGINT REFERENCES folders(id) ON DELETE SET NULL;
+ALTER TABLE folders ADD COLUMN IF NOT EXISTS position BIGINT NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_articles_feed_id ON articles(feed_id);
+CREATE INDEX IF NOT EXISTS idx_articles_published ON articles(published_at DESC NULLS LAST);
+CREATE INDEX IF NOT EXISTS idx_articles_unread ON articles(feed_id, is_read);
+CREATE INDEX IF NOT EXISTS idx_articles_favorite ON articles(is_favorite) WHERE is_favorite = TRUE;
+
+CREATE TABLE IF NOT EXISTS article_categories (
+    article_id BIGINT NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    name       TEXT NOT NULL,
+    PRIMARY KEY (article_id, name)
+);
+CREATE INDEX IF NOT EXISTS idx_articles_normalized_link ON articles(normalized_link);
+CREATE INDEX IF NOT EXISTS idx_articles_dedup_group ON articles(dedup_group_id);
+CREATE INDEX IF NOT EXISTS idx_articles_read_at ON articles(read_at) WHERE read_at IS NOT NULL;
+
+-- Full-text search: create search_vector column if not exists
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns
+        WHERE table_name = 'articles' AND column_name = 'search_vector'
+    ) THEN
+        ALTER TABLE articles ADD COLUMN search_vector TSVECTOR;
+    END IF;
+END $$;
+
+CREATE INDEX IF NOT EXISTS idx_articles_search ON articles USING GIN(search_vector);
+
+-- Text search configuration for a feed language code such as 'de' or 'pt-BR'.
+-- Feeds without a language are assumed to be English; languages without a
+-- built-in configuration fall back to 'simple' (no stemming or stop words).
+CREATE OR REPLACE FUNCTION search_config_for(language TEXT) RETURNS regconfig AS $$
+    SELECT CASE split_part(replace(lower(COALESCE(NULLIF(trim(language), ''), 'en')), '_', '-'), '-', 1)
+        WHEN 'ar' THEN 'arabic'::regconfig
+        WHEN 'da' THEN 'danish'::regconfig
+        WHEN 'de' THEN 'german'::regconfig
+        WHEN 'el' THEN 'greek'::regconfig
+        WHEN 'en' THEN 'english'::regconfig
+        WHEN 'es' THEN 'spanish'::regconfig
+        WHEN 'fi' THEN 'finnish'::regconfig
+        WHEN 'fr' THEN 'french'::regconfig
+        WHEN 'ga' THEN 'irish'::regconfig
+        WHEN 'hu' THEN 'hungarian'::regconfig
+        WHEN 'id' THEN 'indonesian'::regconfig
+        WHEN 'it' THEN 'italian'::regconfig
+        WHEN 'lt' THEN 'lithuanian'::regconfig
+        WHEN 'nb' THEN 'norwegian'::regconfig
+        WHEN 'ne' THEN 'nepali'::regconfig
+        WHEN 'nl' THEN 'dutch'::regconfig
+        WHEN 'nn' THEN 'norwegian'::regconfig
+        WHEN 'no' THEN 'norwegian'::regconfig
+        WHEN 'pt' THEN 'portuguese'::regconfig
+        WHEN 'ro' THEN 'romanian'::regconfig
+        WHEN 'ru' THEN 'russian'::regconfig
+        WHEN 'sv' THEN 'swedish'::regconfig
+        WHEN 'ta' THEN 'tamil'::regconfig
+        WHEN 'tr' THEN 'turkish'::regconfig
+        ELSE 'simple'::regconfig
+    END
+$$ LANGUAGE sql IMMUTABLE;
+
+-- Update search_vector on insert/update, using the feed's language
+CREATE OR REPLACE FUNCTION articles_search_vector_update() RETURNS TRIGGER AS $$
+DECLARE
+    config regconfig := search_config_for((SELECT language FROM feeds WHERE id = NEW.feed_id));
+BEGIN
+    NEW.search_vector :=
+        setweight(to_tsvector(config, COALESCE(NEW.title, '')), 'A') ||
+        setweight(to_tsvector(config, COALESCE(NEW.summary, '')), 'B') ||
+        setweight(to_tsvector(config, COALESCE(NEW.author, '')), 'C') ||
+        setweight(to_tsvector(config, COALESCE(NEW.content, '')), 'D');
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS articles_search_vector_trigger ON articles;
+CREATE TRIGGER articles_search_vector_trigger
+    BEFORE INSERT OR UPDATE OF title, summary, author, content ON articles
+    FOR EACH ROW
+    EXECUTE FUNCTION articles_search_vector_update();
+
+CREATE TABLE IF NOT EXISTS smart_folders (
+    id         BIGSERIAL PRIMARY KEY,
+    name       TEXT NOT NULL,
+    query      TEXT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS labels (
+    id         BIGSERIAL PRIMARY KEY,
+    name       TEXT NOT NULL UNIQUE,
+    color      TEXT,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS article_labels (
+    article_id BIGINT NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    label_id   BIGINT NOT NULL REFERENCES labels(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    PRIMARY KEY (article_id, label_id)
+);
+CREATE INDEX IF NOT EXISTS idx_article_labels_label ON article_labels(label_id);
+
+CREATE TABLE IF NOT EXISTS highlights (
+    id           BIGSERIAL PRIMARY KEY,
+    article_id   BIGINT NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    quote        TEXT NOT NULL,
+    prefix       TEXT,
+    suffix       TEXT,
+    start_offset BIGINT,
+    end_offset   BIGINT,
+    note         TEXT,
+    color        TEXT,
+    created_at   TIMESTAMPTZ DEFAULT NOW(),
+    updated_at   TIMESTAMPTZ DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS idx_highlights_article ON highlights(article_id);
+
+-- Hidden and deleted articles, so refreshes do not insert them again
+CREATE TABLE IF NOT EXISTS article_tombstones (
+    id         BIGSERIAL PRIMARY KEY,
+    feed_id    BIGINT NOT NULL REFERENCES feeds(id) ON DELETE CASCADE,
+    guid       TEXT NOT NULL,
+    title      TEXT NOT NULL,
+    article_id BIGINT REFERENCES articles(id) ON DELETE SET NULL,
+    hidden_at  TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE(feed_id, guid)
+);
+
+CREATE TABLE IF NOT EXISTS feed_fetches (
+    id           BIGSERIAL PRIMARY KEY,
+    feed_id      BIGINT NOT NULL REFERENCES feeds(id) ON DELETE CASCADE,
+    fetched_at   TIMESTAMPTZ DEFAULT NOW(),
+    success      BOOLEAN NOT NULL,
+    status_code  BIGINT,
+    duration_ms  BIGINT NOT NULL,
+    bytes        BIGINT,
+    new_articles BIGINT NOT NULL DEFAULT 0,
+    error        TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_feed_fetches_feed ON feed_fetches(feed_id, id);
+
+CREATE TABLE IF NOT EXISTS settings (
+    key   TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
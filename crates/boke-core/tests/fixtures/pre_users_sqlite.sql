@@ -0,0 +1,134 @@
+-- The SQLite schema from just before multi-user support
+
+
+CREATE TABLE IF NOT EXISTS folders (
+    id        INTEGER PRIMARY KEY AUTOINCREMENT,
+    name      TEXT NOT NULL UNIQUE,
+    parent_id INTEGER REFERENCES folders(id) ON DELETE SET NULL,
+    position  INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE IF NOT EXISTS feeds (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    title           TEXT NOT NULL,
+    folder_id       INTEGER REFERENCES folders(id) ON DELETE SET NULL,
+    feed_url        TEXT NOT NULL UNIQUE,
+    site_url        TEXT,
+    description     TEXT,
+    language        TEXT,
+    favicon_url     TEXT,
+    last_fetched_at DATETIME,
+    last_build_date DATETIME,
+    created_at      DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at      DATETIME DEFAULT CURRENT_TIMESTAMP,
+    custom_title    TEXT,
+    refresh_interval_minutes INTEGER,
+    fetch_full_content INTEGER NOT NULL DEFAULT 0,
+    notify          INTEGER NOT NULL DEFAULT 1,
+    status          TEXT NOT NULL DEFAULT 'active',
+    position        INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE IF NOT EXISTS articles (
+    id           INTEGER PRIMARY KEY AUTOINCREMENT,
+    feed_id      INTEGER NOT NULL REFERENCES feeds(id) ON DELETE CASCADE,
+    guid         TEXT NOT NULL,
+    title        TEXT NOT NULL,
+    link         TEXT,
+    author       TEXT,
+    summary      TEXT,
+    content      TEXT,
+    image_url    TEXT,
+    enclosure_url  TEXT,
+    enclosure_type TEXT,
+    published_at DATETIME,
+    is_read      INTEGER DEFAULT 0,
+    is_favorite  INTEGER DEFAULT 0,
+    created_at   DATETIME DEFAULT CURRENT_TIMESTAMP,
+    normalized_link TEXT,
+    dedup_group_id  INTEGER,
+    read_at         DATETIME,
+    favorited_at    DATETIME,
+    UNIQUE(feed_id, guid)
+);
+
+CREATE INDEX IF NOT EXISTS idx_articles_feed_id ON articles(feed_id);
+CREATE INDEX IF NOT EXISTS idx_articles_published ON articles(published_at DESC);
+CREATE INDEX IF NOT EXISTS idx_articles_unread ON articles(feed_id, is_read);
+CREATE INDEX IF NOT EXISTS idx_articles_favorite ON articles(is_favorite) WHERE is_favorite = 1;
+
+CREATE TABLE IF NOT EXISTS article_categories (
+    article_id INTEGER NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    name       TEXT NOT NULL,
+    PRIMARY KEY (article_id, name)
+);
+
+CREATE TABLE IF NOT EXISTS smart_folders (
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    name       TEXT NOT NULL,
+    query      TEXT NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS labels (
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    name       TEXT NOT NULL UNIQUE,
+    color      TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS article_labels (
+    article_id INTEGER NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    label_id   INTEGER NOT NULL REFERENCES labels(id) ON DELETE CASCADE,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (article_id, label_id)
+);
+CREATE INDEX IF NOT EXISTS idx_article_labels_label ON article_labels(label_id);
+
+CREATE TABLE IF NOT EXISTS highlights (
+    id           INTEGER PRIMARY KEY AUTOINCREMENT,
+    article_id   INTEGER NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    quote        TEXT NOT NULL,
+    prefix       TEXT,
+    suffix       TEXT,
+    start_offset INTEGER,
+    end_offset   INTEGER,
+    note         TEXT,
+    color        TEXT,
+    created_at   DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at   DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+CREATE INDEX IF NOT EXISTS idx_highlights_article ON highlights(article_id);
+
+-- Hidden and deleted articles, so refreshes do not insert them again
+CREATE TABLE IF NOT EXISTS article_tombstones (
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    feed_id    INTEGER NOT NULL REFERENCES feeds(id) ON DELETE CASCADE,
+    guid       TEXT NOT NULL,
+    title      TEXT NOT NULL,
+    article_id INTEGER REFERENCES articles(id) ON DELETE SET NULL,
+    hidden_at  DATETIME DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(feed_id, guid)
+);
+
+CREATE TABLE IF NOT EXISTS feed_fetches (
+    id           INTEGER PRIMARY KEY AUTOINCREMENT,
+    feed_id      INTEGER NOT NULL REFERENCES feeds(id) ON DELETE CASCADE,
+    fetched_at   DATETIME DEFAULT CURRENT_TIMESTAMP,
+    success      INTEGER NOT NULL,
+    status_code  INTEGER,
+    duration_ms  INTEGER NOT NULL,
+    bytes        INTEGER,
+    new_articles INTEGER NOT NULL DEFAULT 0,
+    error        TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_feed_fetches_feed ON feed_fetches(feed_id, id);
+
+CREATE TABLE IF NOT EXISTS settings (
+    key   TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_articles_normalized_link ON articles(normalized_link);
+CREATE INDEX IF NOT EXISTS idx_articles_dedup_group ON articles(dedup_group_id);
+CREATE INDEX IF NOT EXISTS idx_articles_read_at ON articles(read_at) WHERE read_at IS NOT NULL;
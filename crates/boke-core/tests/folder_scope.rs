@@ -0,0 +1,130 @@
+mod common;
+
+use boke_core::db::{Database, DatabasePool};
+use boke_core::models::{ArticleQuery, ArticleScope, FolderId};
+use common::{article, feed, other_user, test_db};
+
+fn in_folder(folder_id: FolderId) -> ArticleQuery {
+    ArticleQuery {
+        folder_id: Some(folder_id),
+        limit: 50,
+        ..Default::default()
+    }
+}
+
+async fn unread_in(db: &DatabasePool, folder_id: i64) -> i64 {
+    let folders = db.get_folders().await.unwrap();
+    folders
+        .iter()
+        .find(|f| f.id == folder_id)
+        .unwrap()
+        .unread_count
+}
+
+#[tokio::test]
+async fn folders_scope_queries_counts_and_marking() {
+    let db = test_db().await;
+    let top = db.create_folder("Top", None).await.unwrap();
+    let sub = db.create_folder("Sub", Some(top.id)).await.unwrap();
+    let other = db.create_folder("Other", None).await.unwrap();
+    let mut feeds = Vec::new();
+    for (i, folder_id) in [Some(top.id), Some(sub.id), Some(other.id), None]
+        .into_iter()
+        .enumerate()
+    {
+        let feed_id = db
+            .insert_feed(&feed(&format!("https://example.com/{}", i)))
+            .await
+            .unwrap();
+        db.move_feed_to_folder(feed_id, folder_id).await.unwrap();
+        feeds.push(feed_id);
+    }
+    for (feed_id, guid) in [
+        (feeds[0], "a"),
+        (feeds[1], "b"),
+        (feeds[1], "c"),
+        (feeds[2], "d"),
+        (feeds[3], "e"),
+    ] {
+        let link = format!("https://example.com/{}", guid);
+        db.insert_article(&article(feed_id, guid, guid, &link))
+            .await
+            .unwrap();
+    }
+
+    // A folder takes in its sub-folders
+    assert_eq!(
+        db.count_articles(&in_folder(FolderId::Folder(top.id)))
+            .await
+            .unwrap(),
+        3
+    );
+    assert_eq!(
+        db.count_articles(&in_folder(FolderId::Folder(sub.id)))
+            .await
+            .unwrap(),
+        2
+    );
+    assert_eq!(
+        db.count_articles(&in_folder(FolderId::Unfiled))
+            .await
+            .unwrap(),
+        1
+    );
+    assert_eq!(unread_in(&db, top.id).await, 3);
+    assert_eq!(unread_in(&db, sub.id).await, 2);
+
+    db.mark_all_read(ArticleScope::Folder(FolderId::Folder(sub.id)))
+        .await
+        .unwrap();
+    assert_eq!(
+        (unread_in(&db, top.id).await, unread_in(&db, sub.id).await),
+        (1, 0)
+    );
+    db.mark_all_read(ArticleScope::Folder(FolderId::Unfiled))
+        .await
+        .unwrap();
+    let unread = ArticleQuery {
+        unread_only: true,
+        ..Default::default()
+    };
+    assert_eq!(db.count_articles(&unread).await.unwrap(), 2);
+    db.mark_all_unread(ArticleScope::Folder(FolderId::Folder(top.id)))
+        .await
+        .unwrap();
+    assert_eq!(unread_in(&db, top.id).await, 3);
+    assert_eq!(unread_in(&db, other.id).await, 1);
+
+    // Another user's folder scopes nothing
+    let bob = other_user(&db, "bob").await;
+    bob.insert_feed(&feed("https://example.com/0"))
+        .await
+        .unwrap();
+    assert_eq!(
+        bob.get_articles(&in_folder(FolderId::Folder(top.id)))
+            .await
+            .unwrap()
+            .len(),
+        0
+    );
+    bob.mark_all_read(ArticleScope::Folder(FolderId::Folder(top.id)))
+        .await
+        .unwrap();
+    assert_eq!(bob.count_articles(&unread).await.unwrap(), 1);
+}
+
+#[test]
+fn scopes_parse_from_json_and_reject_bad_folder_ids() {
+    let parse = |json| serde_json::from_str::<ArticleScope>(json);
+    assert_eq!(
+        parse(r#"{"type":"folder","id":"unfiled"}"#).unwrap(),
+        ArticleScope::Folder(FolderId::Unfiled)
+    );
+    assert_eq!(
+        parse(r#"{"type":"folder","id":7}"#).unwrap(),
+        ArticleScope::Folder(FolderId::Folder(7))
+    );
+    assert_eq!(parse(r#"{"type":"all"}"#).unwrap(), ArticleScope::All);
+    assert!(parse(r#"{"type":"folder","id":"news"}"#).is_err());
+    assert!("news".parse::<FolderId>().is_err());
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use boke_core::FolderService;
+use boke_core::db::{Database, DatabasePool};
+use common::test_db;
+use std::sync::Arc;
+
+#[tokio::test]
+async fn sibling_folders_need_distinct_names() {
+    let db = test_db().await;
+    let service = FolderService::new(Arc::new(db));
+
+    let news = service.create_folder("News", None).await.unwrap();
+    let blogs = service.create_folder("Blogs", None).await.unwrap();
+    let news_tech = service.create_folder("Tech", Some(news.id)).await.unwrap();
+    let blogs_tech = service.create_folder("Tech", Some(blogs.id)).await.unwrap();
+    assert_ne!(news_tech.id, blogs_tech.id);
+
+    assert!(service.create_folder("Tech", Some(news.id)).await.is_err());
+    assert!(service.create_folder("News", None).await.is_err());
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn sqlite_folders_unique_per_user_are_migrated() {
+    let path = common::sqlite_path();
+    let url = format!("sqlite://{}", path.display());
+    let db = DatabasePool::from_url(&url).await.unwrap();
+    let news = db.create_folder("News", None).await.unwrap();
+    drop(db);
+
+    // Put back the folders table as it was when names were unique across a user's tree
+    let pool = sqlx::SqlitePool::connect(&url).await.unwrap();
+    sqlx::raw_sql(
+        r#"
+        PRAGMA foreign_keys = OFF;
+        DROP INDEX idx_folders_user_parent_name;
+        ALTER TABLE folders RENAME TO new_folders;
+        CREATE TABLE folders (
+            id        INTEGER PRIMARY KEY AUTOINCREMENT,
+            user_id   INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+            name      TEXT NOT NULL,
+            parent_id INTEGER REFERENCES folders(id) ON DELETE SET NULL,
+            position  INTEGER NOT NULL DEFAULT 0,
+            UNIQUE(user_id, name)
+        );
+        INSERT INTO folders SELECT * FROM new_folders;
+        DROP TABLE new_folders;
+        "#,
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    pool.close().await;
+
+    let db = DatabasePool::from_url(&url).await.unwrap();
+    let folders = db.get_folders().await.unwrap();
+    assert_eq!(folders.len(), 1);
+    assert_eq!((folders[0].id, folders[0].name.as_str()), (news.id, "News"));
+
+    let blogs = db.create_folder("Blogs", None).await.unwrap();
+    db.create_folder("Tech", Some(news.id)).await.unwrap();
+    db.create_folder("Tech", Some(blogs.id)).await.unwrap();
+    assert!(db.create_folder("News", None).await.is_err());
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use boke_core::SearchQuery;
+use boke_core::db::{Database, DbError, InsertResult};
+use boke_core::models::ArticleQuery;
+use common::{article, feed, insert, test_db};
+
+fn all() -> ArticleQuery {
+    ArticleQuery {
+        limit: 50,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn hidden_and_deleted_articles_stay_out_until_unhidden() {
+    let db = test_db().await;
+    let f = db
+        .insert_feed(&feed("http://hidden.test/feed"))
+        .await
+        .unwrap();
+    let keep = insert(&db, &article(f, "keep", "Keep", "http://hidden.test/keep")).await;
+    let spam = insert(&db, &article(f, "spam", "Spam", "http://hidden.test/spam")).await;
+    let gone = insert(&db, &article(f, "gone", "Gone", "http://hidden.test/gone")).await;
+    db.set_favorite(spam, true).await.unwrap();
+
+    let hidden = db.hide_article(spam).await.unwrap();
+    assert_eq!(hidden.article_id, Some(spam));
+    let deleted = db.delete_article(gone).await.unwrap();
+    assert_eq!(deleted.article_id, None);
+    assert!(db.get_article(gone).await.unwrap().is_none());
+
+    // Neither shows up in lists, counts or search
+    let ids: Vec<i64> = db
+        .get_articles(&all())
+        .await
+        .unwrap()
+        .iter()
+        .map(|a| a.id)
+        .collect();
+    assert_eq!(ids, vec![keep]);
+    assert_eq!(db.count_articles(&all()).await.unwrap(), 1);
+    assert_eq!(db.get_favorites_count().await.unwrap(), 0);
+    let listed = db.get_feeds().await.unwrap();
+    assert_eq!(listed.iter().find(|x| x.id == f).unwrap().unread_count, 1);
+    let search = SearchQuery::parse("spam");
+    assert!(db.search_articles(&search, 10, 0).await.unwrap().is_empty());
+
+    // Refreshes skip both guids
+    for (guid, title) in [("spam", "Spam"), ("gone", "Gone")] {
+        let again = article(f, guid, title, &format!("http://hidden.test/{}", guid));
+        assert!(matches!(
+            db.insert_article(&again).await.unwrap(),
+            InsertResult::Ignored
+        ));
+    }
+    let mut tombstones: Vec<_> = db
+        .get_hidden_articles(Some(f))
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|h| h.guid)
+        .collect();
+    tombstones.sort();
+    assert_eq!(tombstones, vec!["gone", "spam"]);
+
+    // Unhiding brings the hidden article back as it was; the deleted one returns on refresh
+    db.unhide_article(hidden.id).await.unwrap();
+    db.unhide_article(deleted.id).await.unwrap();
+    assert_eq!(db.count_articles(&all()).await.unwrap(), 2);
+    assert_eq!(db.get_favorites_count().await.unwrap(), 1);
+    assert!(db.get_hidden_articles(None).await.unwrap().is_empty());
+    let refreshed = article(f, "gone", "Gone", "http://hidden.test/gone");
+    assert!(matches!(
+        db.insert_article(&refreshed).await.unwrap(),
+        InsertResult::Inserted(_)
+    ));
+    assert_eq!(db.count_articles(&all()).await.unwrap(), 3);
+}
+
+#[tokio::test]
+async fn missing_articles_and_tombstones_are_rejected() {
+    let db = test_db().await;
+    let f = db
+        .insert_feed(&feed("http://hidden.test/feed"))
+        .await
+        .unwrap();
+    let gone = insert(&db, &article(f, "gone", "Gone", "http://hidden.test/gone")).await;
+    let deleted = db.delete_article(gone).await.unwrap();
+
+    assert!(db.hide_article(gone).await.is_err());
+    assert!(db.delete_article(gone).await.is_err());
+    db.unhide_article(deleted.id).await.unwrap();
+    assert!(matches!(
+        db.unhide_article(deleted.id).await,
+        Err(DbError::NotFound(_))
+    ));
+    assert!(matches!(
+        db.unhide_article(9999).await,
+        Err(DbError::NotFound(_))
+    ));
+}
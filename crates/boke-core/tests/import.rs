@@ -0,0 +1,70 @@
+mod common;
+
+use boke_core::db::Database;
+use boke_core::{FeedService, ImportEvent, ImportJobs, ImportResult, parse_opml};
+use common::{rss, serve, test_db};
+use std::sync::Arc;
+
+#[tokio::test]
+async fn sub_folders_with_the_same_name_import_side_by_side() {
+    let base = serve(|request| (200, rss(&request.path, &[(&request.path, "Hello")]))).await;
+    let opml = format!(
+        r#"<?xml version="1.0"?>
+        <opml version="2.0"><body>
+            <outline text="News">
+                <outline text="Tech"><outline type="rss" text="Wire" xmlUrl="{base}/news-tech"/></outline>
+            </outline>
+            <outline text="Blogs">
+                <outline text="Tech"><outline type="rss" text="Diary" xmlUrl="{base}/blogs-tech"/></outline>
+            </outline>
+        </body></opml>"#
+    );
+    let db = test_db().await;
+    let feeds = Arc::new(FeedService::new(Arc::new(db.clone())));
+
+    let (_, mut events) = ImportJobs::new()
+        .start(feeds, &parse_opml(&opml).unwrap())
+        .await
+        .unwrap();
+    let result = loop {
+        if let ImportEvent::Finished { result, .. } = events.recv().await.unwrap() {
+            break result;
+        }
+    };
+    assert_eq!(
+        result,
+        ImportResult {
+            added: 2,
+            skipped: 0,
+            errors: Vec::new(),
+        }
+    );
+
+    let folders = db.get_folders().await.unwrap();
+    let path_of = |folder_id: Option<i64>| {
+        let folder = folders.iter().find(|f| Some(f.id) == folder_id).unwrap();
+        let parent = folders.iter().find(|f| Some(f.id) == folder.parent_id);
+        format!("{}/{}", parent.unwrap().name, folder.name)
+    };
+    let mut filed: Vec<_> = db
+        .get_feeds()
+        .await
+        .unwrap()
+        .iter()
+        .map(|f| {
+            (
+                f.feed_url.trim_start_matches(&base).to_string(),
+                path_of(f.folder_id),
+            )
+        })
+        .collect();
+    filed.sort();
+    assert_eq!(
+        filed,
+        vec![
+            ("/blogs-tech".to_string(), "Blogs/Tech".to_string()),
+            ("/news-tech".to_string(), "News/Tech".to_string()),
+        ]
+    );
+    assert_eq!(folders.len(), 4);
+}
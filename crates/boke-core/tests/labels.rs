@@ -0,0 +1,91 @@
+mod common;
+
+use boke_core::db::{Database, DbError};
+use boke_core::models::{ArticleQuery, NewLabel};
+use boke_core::{ArticleService, LabelService};
+use common::{article, feed, insert, test_db};
+use std::sync::Arc;
+
+fn label(name: &str) -> NewLabel {
+    NewLabel {
+        name: name.to_string(),
+        color: None,
+    }
+}
+
+#[tokio::test]
+async fn labels_filter_articles_and_scope_bulk_marking() {
+    let db = test_db().await;
+    let feed_id = db
+        .insert_feed(&feed("https://example.com/feed"))
+        .await
+        .unwrap();
+    let first = insert(&db, &article(feed_id, "a", "One", "https://example.com/a")).await;
+    let second = insert(&db, &article(feed_id, "b", "Two", "https://example.com/b")).await;
+    let labels = LabelService::new(Arc::new(db.clone()));
+    let articles = ArticleService::new(Arc::new(db.clone()));
+
+    let later = labels
+        .create_label(&NewLabel {
+            name: " Later ".to_string(),
+            color: Some(" ".to_string()),
+        })
+        .await
+        .unwrap();
+    assert_eq!((later.name.as_str(), later.color), ("Later", None));
+
+    labels.add_label(later.id, &[first, second]).await.unwrap();
+    // Adding a label twice is harmless
+    labels.add_label(later.id, &[first]).await.unwrap();
+    assert_eq!(labels.get_labels().await.unwrap()[0].article_count, 2);
+    labels.remove_label(later.id, &[second]).await.unwrap();
+
+    let labelled = ArticleQuery {
+        limit: 10,
+        label_id: Some(later.id),
+        ..Default::default()
+    };
+    let found = articles.get_articles(labelled.clone()).await.unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(
+        (found[0].id, found[0].label_ids.clone()),
+        (first, vec![later.id])
+    );
+    assert_eq!(
+        articles
+            .search_articles("label:later", 10, 0)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+
+    articles.mark_articles_read(&labelled).await.unwrap();
+    assert!(articles.get_article(first).await.unwrap().unwrap().is_read);
+    assert!(!articles.get_article(second).await.unwrap().unwrap().is_read);
+
+    labels.delete_label(later.id).await.unwrap();
+    assert!(
+        articles
+            .get_article(first)
+            .await
+            .unwrap()
+            .unwrap()
+            .label_ids
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn label_names_are_unique_and_missing_labels_not_found() {
+    let db = test_db().await;
+    let labels = LabelService::new(Arc::new(db));
+    let keep = labels.create_label(&label("keep")).await.unwrap();
+    assert!(labels.create_label(&label("keep")).await.is_err());
+
+    labels.delete_label(keep.id).await.unwrap();
+    assert!(matches!(
+        labels.update_label(keep.id, &label("kept")).await,
+        Err(DbError::NotFound(_))
+    ));
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use boke_core::db::{Database, DatabasePool};
+use boke_core::models::{HighlightQuery, NewUser};
+
+/// A single-user library: a feed in a folder, one read and starred article that is
+/// labelled and highlighted, one unread article, and a hidden one.
+const SEED: &str = r#"
+INSERT INTO folders (id, name) VALUES (5, 'Tech');
+INSERT INTO feeds (id, title, feed_url, folder_id, custom_title) VALUES (3, 'Old', 'https://old.example/feed', 5, 'Mine');
+INSERT INTO articles (id, feed_id, guid, title, is_read, is_favorite) VALUES (7, 3, 'a', 'Read', TRUE, TRUE);
+INSERT INTO articles (id, feed_id, guid, title) VALUES (8, 3, 'b', 'Unread');
+INSERT INTO article_tombstones (feed_id, guid, title) VALUES (3, 'c', 'Hidden');
+INSERT INTO smart_folders (name, query) VALUES ('Rust', 'rust');
+INSERT INTO labels (id, name) VALUES (2, 'keep');
+INSERT INTO article_labels (article_id, label_id) VALUES (7, 2);
+INSERT INTO highlights (article_id, quote) VALUES (7, 'quoted');
+"#;
+
+/// Open a database created before multi-user support and holding `SEED`.
+async fn pre_users_db() -> DatabasePool {
+    #[cfg(feature = "postgres")]
+    if let Ok(server) = std::env::var("BOKE_TEST_POSTGRES") {
+        let url = common::empty_postgres_db(&server).await;
+        let pool = sqlx::PgPool::connect(&url).await.unwrap();
+        sqlx::raw_sql(include_str!("fixtures/pre_users_postgres.sql"))
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::raw_sql(SEED).execute(&pool).await.unwrap();
+        // Rows above were given explicit ids, which sequences don't see
+        sqlx::raw_sql(
+            "SELECT setval('folders_id_seq', 10); SELECT setval('feeds_id_seq', 10); \
+             SELECT setval('articles_id_seq', 10); SELECT setval('labels_id_seq', 10);",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool.close().await;
+        return DatabasePool::from_url(&url).await.unwrap();
+    }
+
+    let url = format!("sqlite://{}?mode=rwc", common::sqlite_path().display());
+    let pool = sqlx::SqlitePool::connect(&url).await.unwrap();
+    sqlx::raw_sql(include_str!("fixtures/pre_users_sqlite.sql"))
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::raw_sql(SEED).execute(&pool).await.unwrap();
+    pool.close().await;
+    DatabasePool::from_url(&url).await.unwrap()
+}
+
+#[tokio::test]
+async fn single_user_data_goes_to_the_default_user() {
+    let db = pre_users_db().await;
+    assert_eq!(db.get_users().await.unwrap().len(), 1);
+
+    let feeds = db.get_feeds().await.unwrap();
+    assert_eq!(feeds.len(), 1);
+    assert_eq!((feeds[0].id, feeds[0].title.as_str()), (3, "Mine"));
+    assert_eq!((feeds[0].folder_id, feeds[0].unread_count), (Some(5), 1));
+    let folders = db.get_folders().await.unwrap();
+    assert_eq!((folders[0].id, folders[0].name.as_str()), (5, "Tech"));
+
+    let read = db.get_article(7).await.unwrap().unwrap();
+    assert!(read.is_read && read.is_favorite);
+    assert!(!db.get_article(8).await.unwrap().unwrap().is_read);
+    assert_eq!(db.get_article_labels(&[7]).await.unwrap(), vec![(7, 2)]);
+    let highlights = db.get_highlights(&HighlightQuery::default()).await.unwrap();
+    assert_eq!(highlights[0].quote, "quoted");
+    assert_eq!(db.get_smart_folders().await.unwrap()[0].query, "rust");
+    assert_eq!(
+        db.get_hidden_articles(None).await.unwrap()[0].title,
+        "Hidden"
+    );
+
+    // Newcomers start from scratch, even on the same feed
+    let carol = db
+        .create_user(
+            &NewUser {
+                username: "carol".to_string(),
+                is_admin: false,
+                password: None,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let carol = db.for_user(carol.id);
+    assert!(carol.get_feeds().await.unwrap().is_empty());
+    carol
+        .insert_feed(&common::feed("https://old.example/feed"))
+        .await
+        .unwrap();
+    assert!(!carol.get_article(7).await.unwrap().unwrap().is_read);
+    assert!(carol.get_labels().await.unwrap().is_empty());
+    carol.create_folder("Tech", None).await.unwrap();
+}
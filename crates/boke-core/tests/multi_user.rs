@@ -0,0 +1,237 @@
+mod common;
+
+use boke_core::db::{Database, DbError};
+use boke_core::models::{
+    ArticleQuery, ArticleScope, FolderDeleteMode, HighlightQuery, NewHighlight, NewLabel,
+    UpdateHighlight,
+};
+use boke_core::{HighlightService, SearchQuery};
+use common::{article, feed, insert, other_user, test_db};
+use std::sync::Arc;
+
+fn all() -> ArticleQuery {
+    ArticleQuery {
+        limit: 100,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn articles_and_their_states_are_per_user() {
+    let alice = test_db().await;
+    let bob = other_user(&alice, "bob").await;
+    let shared = alice
+        .insert_feed(&feed("https://shared.example/feed"))
+        .await
+        .unwrap();
+    assert_eq!(
+        bob.insert_feed(&feed("https://shared.example/feed"))
+            .await
+            .unwrap(),
+        shared
+    );
+    let private = alice
+        .insert_feed(&feed("https://private.example/feed"))
+        .await
+        .unwrap();
+    let story = insert(
+        &alice,
+        &article(shared, "1", "Shared story", "https://shared.example/1"),
+    )
+    .await;
+    let secret = insert(
+        &alice,
+        &article(private, "2", "Secret story", "https://private.example/2"),
+    )
+    .await;
+
+    assert!(bob.get_feed(private).await.unwrap().is_none());
+    assert!(bob.get_article(secret).await.unwrap().is_none());
+    let ids: Vec<_> = bob
+        .get_articles(&all())
+        .await
+        .unwrap()
+        .iter()
+        .map(|a| a.id)
+        .collect();
+    assert_eq!(ids, vec![story]);
+    let hits = bob
+        .search_articles(&SearchQuery::parse("secret"), 10, 0)
+        .await
+        .unwrap();
+    assert!(hits.is_empty());
+
+    // Read and favorite state belongs to whoever set it
+    alice.set_read(story, true).await.unwrap();
+    alice.set_favorite(story, true).await.unwrap();
+    let seen_by_bob = bob.get_article(story).await.unwrap().unwrap();
+    assert!(!seen_by_bob.is_read && !seen_by_bob.is_favorite);
+    assert_eq!(bob.get_favorites_count().await.unwrap(), 0);
+    bob.mark_all_read(ArticleScope::All).await.unwrap();
+    assert!(!alice.get_article(secret).await.unwrap().unwrap().is_read);
+    alice.mark_all_unread(ArticleScope::All).await.unwrap();
+    assert!(bob.get_article(story).await.unwrap().unwrap().is_read);
+
+    // Nor can bob change the state of articles he doesn't subscribe to
+    bob.toggle_read(secret).await.unwrap();
+    bob.toggle_favorite(secret).await.unwrap();
+    let secret_article = alice.get_article(secret).await.unwrap().unwrap();
+    assert!(!secret_article.is_read && !secret_article.is_favorite);
+    assert!(matches!(
+        bob.hide_article(secret).await,
+        Err(DbError::NotFound(_))
+    ));
+
+    let hidden = alice.hide_article(secret).await.unwrap();
+    assert!(bob.get_hidden_articles(None).await.unwrap().is_empty());
+    assert!(matches!(
+        bob.unhide_article(hidden.id).await,
+        Err(DbError::NotFound(_))
+    ));
+    assert_eq!(alice.get_hidden_articles(None).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn folders_labels_and_highlights_are_per_user() {
+    let alice = test_db().await;
+    let bob = other_user(&alice, "bob").await;
+    let shared = alice
+        .insert_feed(&feed("https://shared.example/feed"))
+        .await
+        .unwrap();
+    bob.insert_feed(&feed("https://shared.example/feed"))
+        .await
+        .unwrap();
+    let private = alice
+        .insert_feed(&feed("https://private.example/feed"))
+        .await
+        .unwrap();
+    let story = insert(
+        &alice,
+        &article(shared, "1", "Shared story", "https://shared.example/1"),
+    )
+    .await;
+    let secret = insert(
+        &alice,
+        &article(private, "2", "Secret story", "https://private.example/2"),
+    )
+    .await;
+
+    let news = alice.create_folder("News", None).await.unwrap();
+    alice
+        .move_feed_to_folder(shared, Some(news.id))
+        .await
+        .unwrap();
+    assert!(bob.get_folders().await.unwrap().is_empty());
+    assert_eq!(bob.get_feed(shared).await.unwrap().unwrap().folder_id, None);
+    bob.rename_folder(news.id, "Mine now").await.unwrap();
+    assert!(matches!(
+        bob.move_folder(news.id, None).await,
+        Err(DbError::NotFound(_))
+    ));
+    assert!(matches!(
+        bob.delete_folder(news.id, FolderDeleteMode::Cascade).await,
+        Err(DbError::NotFound(_))
+    ));
+    let folders = alice.get_folders().await.unwrap();
+    assert_eq!((folders.len(), folders[0].name.as_str()), (1, "News"));
+    assert_eq!(
+        alice.get_feed(shared).await.unwrap().unwrap().folder_id,
+        Some(news.id)
+    );
+    // Each user names their folders independently
+    bob.create_folder("News", None).await.unwrap();
+
+    let keep = alice
+        .create_label(&NewLabel {
+            name: "keep".to_string(),
+            color: None,
+        })
+        .await
+        .unwrap();
+    alice
+        .add_label_to_articles(keep.id, &[story, secret])
+        .await
+        .unwrap();
+    assert!(bob.get_labels().await.unwrap().is_empty());
+    assert!(
+        bob.get_article_labels(&[story, secret])
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    let renamed = NewLabel {
+        name: "taken".to_string(),
+        color: None,
+    };
+    assert!(matches!(
+        bob.update_label(keep.id, &renamed).await,
+        Err(DbError::NotFound(_))
+    ));
+    bob.remove_label_from_articles(keep.id, &[story])
+        .await
+        .unwrap();
+    bob.delete_label(keep.id).await.unwrap();
+    assert_eq!(alice.get_labels().await.unwrap()[0].name, "keep");
+    assert_eq!(
+        alice
+            .get_article_labels(&[story, secret])
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+    let labelled = ArticleQuery {
+        label_id: Some(keep.id),
+        ..all()
+    };
+    assert!(bob.get_articles(&labelled).await.unwrap().is_empty());
+
+    let quote = alice
+        .create_highlight(&NewHighlight {
+            article_id: story,
+            quote: "Shared".to_string(),
+            prefix: None,
+            suffix: None,
+            start_offset: None,
+            end_offset: None,
+            note: Some("mine".to_string()),
+            color: None,
+        })
+        .await
+        .unwrap();
+    assert!(
+        bob.get_highlights(&HighlightQuery::default())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(bob.get_highlight(quote.id).await.unwrap().is_none());
+    let edit = UpdateHighlight {
+        note: Some("bob's".to_string()),
+        color: None,
+    };
+    assert!(matches!(
+        bob.update_highlight(quote.id, &edit).await,
+        Err(DbError::NotFound(_))
+    ));
+    bob.delete_highlight(quote.id).await.unwrap();
+    let kept = alice.get_highlight(quote.id).await.unwrap().unwrap();
+    assert_eq!(kept.note.as_deref(), Some("mine"));
+
+    let highlights = HighlightService::new(Arc::new(bob.clone()));
+    let on_secret = NewHighlight {
+        article_id: secret,
+        quote: "Secret".to_string(),
+        prefix: None,
+        suffix: None,
+        start_offset: None,
+        end_offset: None,
+        note: None,
+        color: None,
+    };
+    assert!(matches!(
+        highlights.create_highlight(&on_secret).await,
+        Err(DbError::NotFound(_))
+    ));
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use boke_core::SmartFolderService;
+use boke_core::db::{Database, DbError};
+use boke_core::models::{ArticleQuery, NewSmartFolder};
+use common::{article, feed, insert, test_db};
+use std::sync::Arc;
+
+#[tokio::test]
+async fn smart_folders_count_scope_and_mark_read() {
+    let db = test_db().await;
+    let tech_feed = db
+        .insert_feed(&feed("https://tech.example/feed"))
+        .await
+        .unwrap();
+    let tech = db.create_folder("Tech", None).await.unwrap();
+    db.move_feed_to_folder(tech_feed, Some(tech.id))
+        .await
+        .unwrap();
+    let other_feed = db
+        .insert_feed(&feed("https://other.example/feed"))
+        .await
+        .unwrap();
+    let rust_in_tech = insert(
+        &db,
+        &article(tech_feed, "a", "Rust news", "https://tech.example/a"),
+    )
+    .await;
+    insert(
+        &db,
+        &article(tech_feed, "b", "Go news", "https://tech.example/b"),
+    )
+    .await;
+    insert(
+        &db,
+        &article(other_feed, "c", "Rust elsewhere", "https://other.example/c"),
+    )
+    .await;
+
+    let service = SmartFolderService::new(Arc::new(db.clone()));
+    let folder = service
+        .create_smart_folder(&NewSmartFolder {
+            name: " Rust in Tech ".to_string(),
+            query: "rust folder:tech is:unread after:7d".to_string(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        (folder.name.as_str(), folder.unread_count),
+        ("Rust in Tech", 1)
+    );
+
+    let scoped = ArticleQuery {
+        limit: 10,
+        search: Some(service.search_query(folder.id).await.unwrap()),
+        ..Default::default()
+    };
+    let ids: Vec<_> = db
+        .get_articles(&scoped)
+        .await
+        .unwrap()
+        .iter()
+        .map(|a| a.id)
+        .collect();
+    assert_eq!(ids, vec![rust_in_tech]);
+
+    service.mark_read(folder.id).await.unwrap();
+    assert_eq!(
+        service.get_smart_folders().await.unwrap()[0].unread_count,
+        0
+    );
+    let unread = ArticleQuery {
+        unread_only: true,
+        ..Default::default()
+    };
+    assert_eq!(db.count_articles(&unread).await.unwrap(), 2);
+
+    // The query is re-run on every use, so edits apply at once
+    service
+        .update_smart_folder(
+            folder.id,
+            &NewSmartFolder {
+                name: "All Rust".to_string(),
+                query: "rust".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    let updated = service.get_smart_folder(folder.id).await.unwrap().unwrap();
+    assert_eq!(
+        (updated.name.as_str(), updated.unread_count),
+        ("All Rust", 1)
+    );
+}
+
+#[tokio::test]
+async fn missing_smart_folders_are_not_found() {
+    let db = test_db().await;
+    let service = SmartFolderService::new(Arc::new(db));
+    let folder = service
+        .create_smart_folder(&NewSmartFolder {
+            name: "Rust".to_string(),
+            query: "rust".to_string(),
+        })
+        .await
+        .unwrap();
+    service.delete_smart_folder(folder.id).await.unwrap();
+
+    assert!(service.get_smart_folder(folder.id).await.unwrap().is_none());
+    assert!(matches!(
+        service.search_query(folder.id).await,
+        Err(DbError::NotFound(_))
+    ));
+    assert!(matches!(
+        service.mark_read(folder.id).await,
+        Err(DbError::NotFound(_))
+    ));
+    let renamed = NewSmartFolder {
+        name: "Go".to_string(),
+        query: "go".to_string(),
+    };
+    assert!(matches!(
+        service.update_smart_folder(folder.id, &renamed).await,
+        Err(DbError::NotFound(_))
+    ));
+}
@@ -0,0 +1,108 @@
+mod common;
+
+use boke_core::db::Database;
+use boke_core::{ArticleService, StatsService};
+use chrono::{Duration, Utc};
+use common::{article, article_at, feed, insert, other_user, test_db};
+use std::sync::Arc;
+
+#[tokio::test]
+async fn reading_is_timed_and_counted() {
+    let db = test_db().await;
+    let read_feed = db
+        .insert_feed(&feed("https://read.example/feed"))
+        .await
+        .unwrap();
+    let ignored_feed = db
+        .insert_feed(&feed("https://ignored.example/feed"))
+        .await
+        .unwrap();
+    let two_hours_ago = Utc::now() - Duration::hours(2);
+    let read = insert(
+        &db,
+        &article_at(
+            read_feed,
+            "1",
+            "Read",
+            "https://read.example/1",
+            two_hours_ago,
+        ),
+    )
+    .await;
+    insert(
+        &db,
+        &article(read_feed, "2", "Unread", "https://read.example/2"),
+    )
+    .await;
+    insert(
+        &db,
+        &article(ignored_feed, "3", "Ignored", "https://ignored.example/3"),
+    )
+    .await;
+
+    let articles = ArticleService::new(Arc::new(db.clone()));
+    articles.set_read(read, true).await.unwrap();
+    articles.set_favorite(read, true).await.unwrap();
+    let stamped = articles.get_article(read).await.unwrap().unwrap();
+    assert!(stamped.read_at.is_some() && stamped.favorited_at.is_some());
+
+    let stats = StatsService::new(Arc::new(db.clone()))
+        .get_reading_stats(7)
+        .await
+        .unwrap();
+    let per_day: Vec<_> = stats.reads_per_day.iter().map(|d| d.count).collect();
+    assert_eq!(per_day, vec![1]);
+    let read_stats = stats.feeds.iter().find(|f| f.feed_id == read_feed).unwrap();
+    assert_eq!(
+        (read_stats.total_articles, read_stats.read_articles),
+        (2, 1)
+    );
+    assert_eq!(read_stats.read_ratio, 0.5);
+    let never: Vec<_> = stats.never_read_feeds.iter().map(|f| f.feed_id).collect();
+    assert_eq!(never, vec![ignored_feed]);
+    let seconds = stats.avg_seconds_to_read.unwrap();
+    assert!((7100.0..7300.0).contains(&seconds), "{seconds}");
+}
+
+#[tokio::test]
+async fn unreading_or_another_users_reads_do_not_count() {
+    let db = test_db().await;
+    let feed_id = db
+        .insert_feed(&feed("https://example.com/feed"))
+        .await
+        .unwrap();
+    let id = insert(
+        &db,
+        &article(feed_id, "1", "Story", "https://example.com/1"),
+    )
+    .await;
+    let bob = other_user(&db, "bob").await;
+    bob.insert_feed(&feed("https://example.com/feed"))
+        .await
+        .unwrap();
+
+    let articles = ArticleService::new(Arc::new(db.clone()));
+    articles.set_read(id, true).await.unwrap();
+    articles.set_read(id, false).await.unwrap();
+    assert!(
+        articles
+            .get_article(id)
+            .await
+            .unwrap()
+            .unwrap()
+            .read_at
+            .is_none()
+    );
+    ArticleService::new(Arc::new(bob))
+        .set_read(id, true)
+        .await
+        .unwrap();
+
+    let stats = StatsService::new(Arc::new(db))
+        .get_reading_stats(7)
+        .await
+        .unwrap();
+    assert!(stats.reads_per_day.is_empty());
+    assert_eq!(stats.avg_seconds_to_read, None);
+    assert_eq!(stats.never_read_feeds.len(), 1);
+}
@@ -0,0 +1,135 @@
+mod common;
+
+use boke_core::db::Database;
+use boke_core::models::{NewWebhook, WebhookEvent};
+use boke_core::{ArticleService, EventBus, WebhookService};
+use common::{Request, article, feed, insert, serve, test_db};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[tokio::test]
+async fn calls_are_signed_retried_and_logged() {
+    // The receiver fails the first call and accepts the retry
+    let received: Arc<Mutex<Vec<Request>>> = Arc::default();
+    let log = received.clone();
+    let base = serve(move |request| {
+        let mut received = log.lock().unwrap();
+        received.push(request.clone());
+        if received.len() == 1 {
+            (503, "try later".to_string())
+        } else {
+            (200, "ok".to_string())
+        }
+    })
+    .await;
+
+    let db = test_db().await;
+    let feed_id = db
+        .insert_feed(&feed("https://example.com/feed"))
+        .await
+        .unwrap();
+    let article_id = insert(
+        &db,
+        &article(feed_id, "1", "Story", "https://example.com/1"),
+    )
+    .await;
+    let webhooks = WebhookService::new(Arc::new(db.clone()));
+    let created = webhooks
+        .create_webhook(&NewWebhook {
+            url: format!("{}/hook", base),
+            event: WebhookEvent::Favorited,
+            feed_id: None,
+            folder_id: None,
+            keyword: None,
+            enabled: true,
+            secret: Some("shared secret".to_string()),
+        })
+        .await
+        .unwrap();
+    let webhook_id = created.webhook.id;
+
+    let events = EventBus::default();
+    webhooks.dispatch(&events);
+    ArticleService::new(Arc::new(db.clone()))
+        .with_events(events.clone())
+        .set_favorite(article_id, true)
+        .await
+        .unwrap();
+
+    // The retry waits for the first back-off delay
+    let mut deliveries = Vec::new();
+    for _ in 0..200 {
+        deliveries = webhooks.get_deliveries(webhook_id).await.unwrap();
+        if deliveries.len() == 2 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    deliveries.reverse();
+    let logged: Vec<_> = deliveries
+        .iter()
+        .map(|d| (d.attempt, d.status_code, d.success))
+        .collect();
+    assert_eq!(logged, vec![(1, Some(503), false), (2, Some(200), true)]);
+    assert_eq!(deliveries[0].delivery_id, deliveries[1].delivery_id);
+    assert_eq!(deliveries[0].event, "favorited");
+
+    let received = received.lock().unwrap();
+    assert_eq!(received.len(), 2);
+    for request in received.iter() {
+        assert_eq!(
+            (request.method.as_str(), request.path.as_str()),
+            ("POST", "/hook")
+        );
+        assert_eq!(request.header("x-boke-event"), Some("favorited"));
+        assert_eq!(
+            request.header("x-boke-delivery"),
+            Some(deliveries[0].delivery_id.as_str())
+        );
+
+        let signature = request.header("x-boke-signature").unwrap();
+        let signature = hex::decode(signature.strip_prefix("sha256=").unwrap()).unwrap();
+        let mut mac = Hmac::<Sha256>::new_from_slice(created.secret.as_bytes()).unwrap();
+        mac.update(&request.body);
+        mac.verify_slice(&signature).unwrap();
+
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        assert_eq!(body["webhook_id"], webhook_id);
+        assert_eq!(body["article"]["id"], article_id);
+    }
+}
+
+#[tokio::test]
+async fn test_calls_report_rejections() {
+    let base = serve(|_| (401, "who are you".to_string())).await;
+    let db = test_db().await;
+    let webhooks = WebhookService::new(Arc::new(db));
+    let created = webhooks
+        .create_webhook(&NewWebhook {
+            url: format!("{}/hook", base),
+            event: WebhookEvent::NewArticle,
+            feed_id: None,
+            folder_id: None,
+            keyword: None,
+            enabled: true,
+            secret: None,
+        })
+        .await
+        .unwrap();
+
+    let delivery = webhooks.test_webhook(created.webhook.id).await.unwrap();
+    assert_eq!((delivery.event.as_str(), delivery.attempt), ("ping", 1));
+    assert_eq!((delivery.status_code, delivery.success), (Some(401), false));
+    assert_eq!(delivery.error.as_deref(), Some("HTTP 401 Unauthorized"));
+    // Test calls are not retried
+    assert_eq!(
+        webhooks
+            .get_deliveries(created.webhook.id)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
@@ -0,0 +1,187 @@
+//! Authentication and the user a request acts for.
+//!
+//! [`require_user`] runs in front of every API route except login and logout. Requests
+//! authenticate with an API token as `Authorization: Bearer <token>` or with the session
+//! cookie set by `POST /api/auth/login`. With [`AuthMode::None`] there is no login:
+//! requests name their user in the `X-Boke-User` header, or act for the default user.
+
+use crate::AppState;
+use crate::config::{AuthMode, Config};
+use crate::error::ApiError;
+use axum::extract::{FromRequestParts, Request, State};
+use axum::http::request::Parts;
+use axum::http::{HeaderMap, header};
+use axum::middleware::Next;
+use axum::response::Response;
+use boke_core::DEFAULT_USER_ID;
+use boke_core::models::User;
+use boke_core::services::SESSION_LIFETIME;
+
+pub const USER_HEADER: &str = "x-boke-user";
+pub const SESSION_COOKIE: &str = "boke_session";
+
+/// The user a request was authenticated as.
+#[derive(Clone)]
+pub struct CurrentUser(pub User);
+
+/// App state whose services act for the request's user.
+pub struct UserState(pub AppState);
+
+/// The request's user, who must be an admin.
+pub struct AdminUser(pub User);
+
+/// Middleware that rejects requests without a valid, enabled user and records the user
+/// for the [`CurrentUser`], [`UserState`] and [`AdminUser`] extractors.
+pub async fn require_user(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let user = authenticate(request.headers(), &state).await?;
+    if user.disabled {
+        return Err(ApiError::Forbidden("User is disabled".to_string()));
+    }
+    request.extensions_mut().insert(CurrentUser(user));
+    Ok(next.run(request).await)
+}
+
+async fn authenticate(headers: &HeaderMap, state: &AppState) -> Result<User, ApiError> {
+    if state.auth_mode == AuthMode::None {
+        return header_user(headers, state).await;
+    }
+
+    if let Some(token) = bearer_token(headers)? {
+        return state
+            .user_service
+            .get_token_user(token)
+            .await?
+            .ok_or_else(|| ApiError::Unauthorized("Invalid API token".to_string()));
+    }
+    if let Some(token) = session_token(headers)
+        && let Some(user) = state.user_service.get_session_user(token).await?
+    {
+        return Ok(user);
+    }
+    Err(ApiError::Unauthorized("Login required".to_string()))
+}
+
+async fn header_user(headers: &HeaderMap, state: &AppState) -> Result<User, ApiError> {
+    let user = match headers.get(USER_HEADER) {
+        Some(value) => {
+            let username = value
+                .to_str()
+                .map_err(|_| ApiError::BadRequest("Invalid user header".to_string()))?;
+            state.user_service.get_user_by_name(username).await?
+        }
+        None => state.user_service.get_user(DEFAULT_USER_ID).await?,
+    };
+    user.ok_or_else(|| ApiError::Unauthorized("Unknown user".to_string()))
+}
+
+fn bearer_token(headers: &HeaderMap) -> Result<Option<&str>, ApiError> {
+    let Some(value) = headers.get(header::AUTHORIZATION) else {
+        return Ok(None);
+    };
+    value
+        .to_str()
+        .ok()
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|token| Some(token.trim()))
+        .ok_or_else(|| ApiError::Unauthorized("Expected a bearer token".to_string()))
+}
+
+/// The session token from the request's cookies.
+pub fn session_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|cookies| cookies.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(name, _)| *name == SESSION_COOKIE)
+        .map(|(_, value)| value)
+}
+
+/// A `Set-Cookie` value that stores a session token.
+pub fn session_cookie(token: &str, secure: bool) -> String {
+    format!(
+        "{}={}; Path=/; HttpOnly; SameSite=Lax; Max-Age={}{}",
+        SESSION_COOKIE,
+        token,
+        SESSION_LIFETIME.num_seconds(),
+        if secure { "; Secure" } else { "" }
+    )
+}
+
+/// A `Set-Cookie` value that removes the session cookie.
+pub fn expired_session_cookie(secure: bool) -> String {
+    format!(
+        "{}=; Path=/; HttpOnly; SameSite=Lax; Max-Age=0{}",
+        SESSION_COOKIE,
+        if secure { "; Secure" } else { "" }
+    )
+}
+
+/// Give the initial admin the password from `ADMIN_PASSWORD` if they have none yet, and
+/// warn about setups nobody can log in to.
+pub async fn bootstrap(state: &AppState, config: &Config) -> anyhow::Result<()> {
+    if config.auth_mode == AuthMode::None {
+        tracing::warn!(
+            "Authentication is disabled (AUTH_MODE=none); anyone who can reach the server has full access"
+        );
+        return Ok(());
+    }
+
+    if let Ok(password) = std::env::var("ADMIN_PASSWORD")
+        && !password.is_empty()
+        && let Some(admin) = state.user_service.get_user(DEFAULT_USER_ID).await?
+        && !admin.has_password
+    {
+        state
+            .user_service
+            .set_password(admin.id, Some(&password))
+            .await?;
+        tracing::info!("Set the password of user {}", admin.username);
+    }
+
+    let users = state.user_service.get_users().await?;
+    if !users.iter().any(|u| u.has_password && !u.disabled) {
+        tracing::warn!(
+            "No user has a password yet; set ADMIN_PASSWORD to log in as the initial admin"
+        );
+    }
+    Ok(())
+}
+
+impl FromRequestParts<AppState> for CurrentUser {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &AppState) -> Result<Self, ApiError> {
+        parts
+            .extensions
+            .get::<CurrentUser>()
+            .cloned()
+            .ok_or_else(|| ApiError::Unauthorized("Login required".to_string()))
+    }
+}
+
+impl FromRequestParts<AppState> for UserState {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, ApiError> {
+        let CurrentUser(user) = CurrentUser::from_request_parts(parts, state).await?;
+        Ok(UserState(state.for_user(user.id)))
+    }
+}
+
+impl FromRequestParts<AppState> for AdminUser {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, ApiError> {
+        let CurrentUser(user) = CurrentUser::from_request_parts(parts, state).await?;
+        if !user.is_admin {
+            return Err(ApiError::Forbidden("Admin access required".to_string()));
+        }
+        Ok(AdminUser(user))
+    }
+}
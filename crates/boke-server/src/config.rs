@@ -5,6 +5,21 @@ pub struct Config {
     pub database_url: String,
     pub bind_address: String,
     pub static_dir: String,
+    pub auth_mode: AuthMode,
+    /// Origins allowed to call the API from a browser; empty allows same-origin only.
+    pub cors_origins: Vec<String>,
+    /// Mark session cookies `Secure`, for servers behind HTTPS.
+    pub secure_cookies: bool,
+}
+
+/// How requests to the API are authenticated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthMode {
+    /// Every request needs a session cookie or an API token.
+    Required,
+    /// No login at all, for single-user LAN setups. Requests act for the user named in
+    /// the `X-Boke-User` header, or the default user.
+    None,
 }
 
 impl Config {
@@ -15,9 +30,29 @@ impl Config {
             database_url,
             bind_address: env::var("BIND_ADDRESS").unwrap_or_else(|_| "0.0.0.0:8080".to_string()),
             static_dir: env::var("STATIC_DIR").unwrap_or_else(|_| "./static".to_string()),
+            auth_mode: Self::auth_mode()?,
+            cors_origins: env::var("CORS_ORIGINS")
+                .unwrap_or_default()
+                .split(',')
+                .map(|origin| origin.trim().trim_end_matches('/').to_string())
+                .filter(|origin| !origin.is_empty())
+                .collect(),
+            secure_cookies: env::var("SECURE_COOKIES")
+                .is_ok_and(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes")),
         })
     }
 
+    fn auth_mode() -> Result<AuthMode, ConfigError> {
+        // Authentication is on unless explicitly turned off
+        let mode = env::var("AUTH_MODE").unwrap_or_else(|_| "required".to_string());
+
+        match mode.to_lowercase().as_str() {
+            "required" | "password" => Ok(AuthMode::Required),
+            "none" => Ok(AuthMode::None),
+            other => Err(ConfigError::InvalidAuthMode(other.to_string())),
+        }
+    }
+
     fn build_database_url() -> Result<String, ConfigError> {
         // Determine database type: "postgres" or "sqlite" (default: sqlite)
         let db_type = env::var("DB_TYPE").unwrap_or_else(|_| "sqlite".to_string());
@@ -51,6 +86,7 @@ impl Config {
 #[derive(Debug)]
 pub enum ConfigError {
     InvalidDbType(String),
+    InvalidAuthMode(String),
 }
 
 impl std::fmt::Display for ConfigError {
@@ -59,6 +95,9 @@ impl std::fmt::Display for ConfigError {
             ConfigError::InvalidDbType(t) => {
                 write!(f, "Invalid DB_TYPE '{}'. Use 'sqlite' or 'postgres'", t)
             }
+            ConfigError::InvalidAuthMode(m) => {
+                write!(f, "Invalid AUTH_MODE '{}'. Use 'required' or 'none'", m)
+            }
         }
     }
 }
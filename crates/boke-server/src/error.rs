@@ -11,6 +11,8 @@ pub enum ApiError {
     Internal(anyhow::Error),
     NotFound,
     BadRequest(String),
+    Unauthorized(String),
+    Forbidden(String),
 }
 
 impl IntoResponse for ApiError {
@@ -26,6 +28,8 @@ impl IntoResponse for ApiError {
             }
             ApiError::NotFound => (StatusCode::NOT_FOUND, "Resource not found".to_string()),
             ApiError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
+            ApiError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg),
+            ApiError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg),
         };
 
         (status, Json(json!({ "error": message }))).into_response()
@@ -34,12 +38,21 @@ impl IntoResponse for ApiError {
 
 impl From<boke_core::db::DbError> for ApiError {
     fn from(e: boke_core::db::DbError) -> Self {
-        ApiError::Database(e)
+        match e {
+            boke_core::db::DbError::NotFound(_) => ApiError::NotFound,
+            boke_core::db::DbError::InvalidInput(msg) => ApiError::BadRequest(msg),
+            e => ApiError::Database(e),
+        }
     }
 }
 
 impl From<anyhow::Error> for ApiError {
     fn from(e: anyhow::Error) -> Self {
-        ApiError::Internal(e)
+        // Services that mix network and database work still report missing rows and bad
+        // input as database errors
+        match e.downcast::<boke_core::db::DbError>() {
+            Ok(e) => e.into(),
+            Err(e) => ApiError::Internal(e),
+        }
     }
 }
@@ -1,24 +1,34 @@
 use axum::{
     Router,
-    routing::{delete, get, post, put},
+    http::{HeaderValue, Method, header},
+    middleware,
+    routing::{delete, get, patch, post, put},
 };
 use boke_core::{
-    db::DatabasePool,
-    services::{ArticleService, FeedService, FolderService},
+    EventBus,
+    db::{Database, DatabasePool},
+    services::{
+        ArticleService, FeedService, FolderService, HighlightService, ImportJobs, LabelService,
+        SmartFolderService, StatsService, UserService, WebhookService,
+    },
 };
 use std::{net::SocketAddr, sync::Arc};
 use tower_http::{
-    cors::{Any, CorsLayer},
+    cors::{AllowOrigin, CorsLayer},
     services::ServeDir,
     trace::TraceLayer,
 };
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+mod auth;
 mod config;
 mod error;
 mod routes;
+#[cfg(test)]
+mod testing;
 
-use config::Config;
+use config::{AuthMode, Config};
+use routes::fever::FaviconCache;
 
 #[derive(Clone)]
 pub struct AppState {
@@ -26,6 +36,66 @@ pub struct AppState {
     pub feed_service: Arc<FeedService<DatabasePool>>,
     pub article_service: Arc<ArticleService<DatabasePool>>,
     pub folder_service: Arc<FolderService<DatabasePool>>,
+    pub highlight_service: Arc<HighlightService<DatabasePool>>,
+    pub label_service: Arc<LabelService<DatabasePool>>,
+    pub smart_folder_service: Arc<SmartFolderService<DatabasePool>>,
+    pub stats_service: Arc<StatsService<DatabasePool>>,
+    pub user_service: Arc<UserService<DatabasePool>>,
+    pub webhook_service: Arc<WebhookService<DatabasePool>>,
+    pub import_jobs: ImportJobs,
+    pub fever_favicons: FaviconCache,
+    pub events: EventBus,
+    pub auth_mode: AuthMode,
+    pub secure_cookies: bool,
+}
+
+impl AppState {
+    pub fn new(db: DatabasePool, auth_mode: AuthMode, secure_cookies: bool) -> Self {
+        let db_arc = Arc::new(db.clone());
+        let events = EventBus::new();
+        Self {
+            db,
+            feed_service: Arc::new(FeedService::new(db_arc.clone()).with_events(events.clone())),
+            article_service: Arc::new(
+                ArticleService::new(db_arc.clone()).with_events(events.clone()),
+            ),
+            folder_service: Arc::new(
+                FolderService::new(db_arc.clone()).with_events(events.clone()),
+            ),
+            highlight_service: Arc::new(HighlightService::new(db_arc.clone())),
+            label_service: Arc::new(LabelService::new(db_arc.clone()).with_events(events.clone())),
+            smart_folder_service: Arc::new(SmartFolderService::new(db_arc.clone())),
+            stats_service: Arc::new(StatsService::new(db_arc.clone())),
+            user_service: Arc::new(UserService::new(db_arc.clone())),
+            webhook_service: Arc::new(WebhookService::new(db_arc)),
+            import_jobs: ImportJobs::new(),
+            fever_favicons: FaviconCache::default(),
+            events,
+            auth_mode,
+            secure_cookies,
+        }
+    }
+
+    /// The same state with every service acting for the given user.
+    pub fn for_user(&self, user_id: i64) -> Self {
+        Self {
+            db: self.db.for_user(user_id),
+            feed_service: Arc::new(self.feed_service.for_user(user_id)),
+            article_service: Arc::new(self.article_service.for_user(user_id)),
+            folder_service: Arc::new(self.folder_service.for_user(user_id)),
+            highlight_service: Arc::new(self.highlight_service.for_user(user_id)),
+            label_service: Arc::new(self.label_service.for_user(user_id)),
+            smart_folder_service: Arc::new(self.smart_folder_service.for_user(user_id)),
+            stats_service: Arc::new(self.stats_service.for_user(user_id)),
+            user_service: Arc::new(self.user_service.for_user(user_id)),
+            webhook_service: Arc::new(self.webhook_service.for_user(user_id)),
+            import_jobs: self.import_jobs.clone(),
+            fever_favicons: self.fever_favicons.clone(),
+            events: self.events.clone(),
+            auth_mode: self.auth_mode,
+            secure_cookies: self.secure_cookies,
+        }
+    }
 }
 
 #[tokio::main]
@@ -47,27 +117,58 @@ async fn main() -> anyhow::Result<()> {
     let db = DatabasePool::from_url(&config.database_url).await?;
     tracing::info!("Database connected");
 
-    // Initialize services
-    let db_arc = Arc::new(db.clone());
-    let state = AppState {
-        db: db.clone(),
-        feed_service: Arc::new(FeedService::new(db_arc.clone())),
-        article_service: Arc::new(ArticleService::new(db_arc.clone())),
-        folder_service: Arc::new(FolderService::new(db_arc)),
-    };
+    let state = AppState::new(db, config.auth_mode, config.secure_cookies);
+    auth::bootstrap(&state, &config).await?;
+    state.webhook_service.dispatch(&state.events);
+
+    let app = app(state, &config)?;
+
+    // Start server
+    let addr: SocketAddr = config.bind_address.parse()?;
+    tracing::info!("Boke server listening on {}", addr);
 
-    // Build router
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, app).await?;
+
+    Ok(())
+}
+
+/// Every route the server answers, with CORS and request tracing.
+fn app(state: AppState, config: &Config) -> anyhow::Result<Router> {
     let api_routes = Router::new()
+        .route("/events", get(routes::events::events))
         // Feed routes
         .route("/feeds", get(routes::feeds::get_feeds))
         .route("/feeds", post(routes::feeds::add_feed))
         .route("/feeds/import", post(routes::feeds::import_opml))
+        .route("/feeds/import/{id}", get(routes::feeds::get_import_job))
+        .route(
+            "/feeds/import/{id}",
+            delete(routes::feeds::cancel_import_job),
+        )
+        .route(
+            "/feeds/import/{id}/events",
+            get(routes::feeds::import_job_events),
+        )
+        .route("/feeds/export.opml", get(routes::feeds::export_opml))
         .route("/feeds/{id}", delete(routes::feeds::remove_feed))
+        .route("/feeds/{id}", patch(routes::feeds::update_feed))
+        .route("/feeds/{id}/status", put(routes::feeds::set_feed_status))
         .route("/feeds/{id}/refresh", post(routes::feeds::refresh_feed))
         .route("/feeds/refresh", post(routes::feeds::refresh_all_feeds))
         // Article routes
         .route("/articles", get(routes::articles::get_articles))
         .route("/articles/{id}", get(routes::articles::get_article))
+        .route("/articles/{id}", delete(routes::articles::delete_article))
+        .route("/articles/{id}/hide", post(routes::articles::hide_article))
+        .route(
+            "/articles/hidden",
+            get(routes::articles::get_hidden_articles),
+        )
+        .route(
+            "/articles/hidden/{id}",
+            delete(routes::articles::unhide_article),
+        )
         .route("/articles/{id}/read", post(routes::articles::toggle_read))
         .route(
             "/articles/{id}/favorite",
@@ -83,6 +184,10 @@ async fn main() -> anyhow::Result<()> {
             post(routes::articles::mark_all_unread),
         )
         .route("/articles/search", get(routes::articles::search_articles))
+        .route(
+            "/articles/search/reindex",
+            post(routes::articles::reindex_search),
+        )
         .route(
             "/articles/favorites/count",
             get(routes::articles::get_favorites_count),
@@ -90,31 +195,279 @@ async fn main() -> anyhow::Result<()> {
         // Folder routes
         .route("/folders", get(routes::folders::get_folders))
         .route("/folders", post(routes::folders::create_folder))
+        .route("/folders/tree", get(routes::folders::get_folder_tree))
+        .route("/folders/order", put(routes::folders::reorder_folders))
         .route("/folders/{id}", put(routes::folders::rename_folder))
         .route("/folders/{id}", delete(routes::folders::delete_folder))
+        .route("/folders/{id}/parent", put(routes::folders::move_folder))
+        .route("/feeds/order", put(routes::folders::reorder_feeds))
         .route(
             "/folders/{id}/feeds/{feed_id}",
             put(routes::folders::move_feed_to_folder),
-        );
+        )
+        // Highlight routes
+        .route("/highlights", get(routes::highlights::get_highlights))
+        .route("/highlights", post(routes::highlights::create_highlight))
+        .route("/highlights/{id}", get(routes::highlights::get_highlight))
+        .route(
+            "/highlights/{id}",
+            put(routes::highlights::update_highlight),
+        )
+        .route(
+            "/highlights/{id}",
+            delete(routes::highlights::delete_highlight),
+        )
+        .route(
+            "/articles/{id}/highlights",
+            get(routes::highlights::get_article_highlights),
+        )
+        .route(
+            "/articles/{id}/highlights/export",
+            get(routes::highlights::export_article_highlights),
+        )
+        .route(
+            "/labels/{id}/highlights/export",
+            get(routes::highlights::export_label_highlights),
+        )
+        // Label routes
+        .route("/labels", get(routes::labels::get_labels))
+        .route("/labels", post(routes::labels::create_label))
+        .route("/labels/{id}", put(routes::labels::update_label))
+        .route("/labels/{id}", delete(routes::labels::delete_label))
+        .route(
+            "/labels/{id}/articles",
+            post(routes::labels::add_label_to_articles),
+        )
+        .route(
+            "/labels/{id}/articles",
+            delete(routes::labels::remove_label_from_articles),
+        )
+        .route(
+            "/articles/{id}/labels/{label_id}",
+            put(routes::labels::add_article_label),
+        )
+        .route(
+            "/articles/{id}/labels/{label_id}",
+            delete(routes::labels::remove_article_label),
+        )
+        // Smart folder routes
+        .route(
+            "/smart-folders",
+            get(routes::smart_folders::get_smart_folders),
+        )
+        .route(
+            "/smart-folders",
+            post(routes::smart_folders::create_smart_folder),
+        )
+        .route(
+            "/smart-folders/{id}",
+            get(routes::smart_folders::get_smart_folder),
+        )
+        .route(
+            "/smart-folders/{id}",
+            put(routes::smart_folders::update_smart_folder),
+        )
+        .route(
+            "/smart-folders/{id}",
+            delete(routes::smart_folders::delete_smart_folder),
+        )
+        // Webhook routes
+        .route("/webhooks", get(routes::webhooks::get_webhooks))
+        .route("/webhooks", post(routes::webhooks::create_webhook))
+        .route("/webhooks/{id}", get(routes::webhooks::get_webhook))
+        .route("/webhooks/{id}", put(routes::webhooks::update_webhook))
+        .route("/webhooks/{id}", delete(routes::webhooks::delete_webhook))
+        .route(
+            "/webhooks/{id}/deliveries",
+            get(routes::webhooks::get_webhook_deliveries),
+        )
+        .route("/webhooks/{id}/test", post(routes::webhooks::test_webhook))
+        // Statistics routes
+        .route("/stats", get(routes::stats::get_reading_stats))
+        .route("/feeds/{id}/stats", get(routes::stats::get_feed_stats))
+        // User administration
+        .route("/admin/users", get(routes::users::get_users))
+        .route("/admin/users", post(routes::users::create_user))
+        .route(
+            "/admin/users/{id}/disabled",
+            put(routes::users::set_user_disabled),
+        )
+        .route(
+            "/admin/users/{id}/password",
+            put(routes::users::set_user_password),
+        )
+        .route("/admin/users/{id}", delete(routes::users::delete_user))
+        // Account routes
+        .route("/auth/me", get(routes::auth::get_current_user))
+        .route("/auth/password", put(routes::auth::change_password))
+        .route("/auth/tokens", get(routes::auth::get_api_tokens))
+        .route("/auth/tokens", post(routes::auth::create_api_token))
+        .route("/auth/tokens/{id}", delete(routes::auth::delete_api_token))
+        .route("/auth/fever", put(routes::auth::set_fever_password))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth::require_user,
+        ))
+        // Routes reachable without logging in
+        .route("/auth/login", post(routes::auth::login))
+        .route("/auth/logout", post(routes::auth::logout));
 
-    let app = Router::new()
-        .nest("/api", api_routes)
-        .fallback_service(ServeDir::new(&config.static_dir).append_index_html_on_directories(true))
-        .layer(
-            CorsLayer::new()
-                .allow_origin(Any)
-                .allow_methods(Any)
-                .allow_headers(Any),
+    // Google Reader API, authenticated with the token from ClientLogin
+    let reader_routes = Router::new()
+        .route("/user-info", get(routes::greader::user_info))
+        .route("/token", get(routes::greader::token))
+        .route(
+            "/subscription/list",
+            get(routes::greader::subscription_list),
         )
-        .layer(TraceLayer::new_for_http())
-        .with_state(state);
+        .route(
+            "/subscription/edit",
+            post(routes::greader::edit_subscription),
+        )
+        .route("/subscription/quickadd", post(routes::greader::quick_add))
+        .route("/tag/list", get(routes::greader::tag_list))
+        .route("/unread-count", get(routes::greader::unread_count))
+        .route("/stream/contents", get(routes::greader::stream_contents))
+        .route(
+            "/stream/contents/{*stream}",
+            get(routes::greader::stream_contents),
+        )
+        .route("/stream/items/ids", get(routes::greader::stream_item_ids))
+        .route(
+            "/stream/items/contents",
+            get(routes::greader::item_contents).post(routes::greader::item_contents),
+        )
+        .route("/edit-tag", post(routes::greader::edit_tag))
+        .route("/mark-all-as-read", post(routes::greader::mark_all_as_read))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            routes::greader::require_login,
+        ));
 
-    // Start server
-    let addr: SocketAddr = config.bind_address.parse()?;
-    tracing::info!("Boke server listening on {}", addr);
+    // Nextcloud News API, with Basic auth. Version 1-3 moved some updates from PUT to
+    // POST, so those accept both
+    let nextcloud_routes = Router::new()
+        .route("/version", get(routes::nextcloud::version))
+        .route("/status", get(routes::nextcloud::status))
+        .route("/user", get(routes::nextcloud::user))
+        .route("/folders", get(routes::nextcloud::get_folders))
+        .route("/folders", post(routes::nextcloud::create_folder))
+        .route("/folders/{id}", put(routes::nextcloud::rename_folder))
+        .route("/folders/{id}", delete(routes::nextcloud::delete_folder))
+        .route(
+            "/folders/{id}/read",
+            put(routes::nextcloud::mark_folder_read).post(routes::nextcloud::mark_folder_read),
+        )
+        .route("/feeds", get(routes::nextcloud::get_feeds))
+        .route("/feeds", post(routes::nextcloud::add_feed))
+        .route("/feeds/{id}", delete(routes::nextcloud::delete_feed))
+        .route(
+            "/feeds/{id}/move",
+            put(routes::nextcloud::move_feed).post(routes::nextcloud::move_feed),
+        )
+        .route(
+            "/feeds/{id}/rename",
+            put(routes::nextcloud::rename_feed).post(routes::nextcloud::rename_feed),
+        )
+        .route(
+            "/feeds/{id}/read",
+            put(routes::nextcloud::mark_feed_read).post(routes::nextcloud::mark_feed_read),
+        )
+        .route("/items", get(routes::nextcloud::get_items))
+        .route("/items/updated", get(routes::nextcloud::get_updated_items))
+        .route(
+            "/items/read",
+            put(routes::nextcloud::mark_all_read).post(routes::nextcloud::mark_all_read),
+        )
+        .route(
+            "/items/read/multiple",
+            put(routes::nextcloud::mark_items_read).post(routes::nextcloud::mark_items_read),
+        )
+        .route(
+            "/items/unread/multiple",
+            put(routes::nextcloud::mark_items_unread).post(routes::nextcloud::mark_items_unread),
+        )
+        .route(
+            "/items/star/multiple",
+            put(routes::nextcloud::star_items).post(routes::nextcloud::star_items),
+        )
+        .route(
+            "/items/unstar/multiple",
+            put(routes::nextcloud::unstar_items).post(routes::nextcloud::unstar_items),
+        )
+        .route(
+            "/items/{id}/read",
+            put(routes::nextcloud::mark_item_read).post(routes::nextcloud::mark_item_read),
+        )
+        .route(
+            "/items/{id}/unread",
+            put(routes::nextcloud::mark_item_unread).post(routes::nextcloud::mark_item_unread),
+        )
+        .route(
+            "/items/{id}/star",
+            put(routes::nextcloud::star_item).post(routes::nextcloud::star_item),
+        )
+        .route(
+            "/items/{id}/unstar",
+            put(routes::nextcloud::unstar_item).post(routes::nextcloud::unstar_item),
+        )
+        // API v1-2 stars items by feed id and guid hash
+        .route(
+            "/items/{id}/{guid_hash}/star",
+            put(routes::nextcloud::star_item_by_guid_hash)
+                .post(routes::nextcloud::star_item_by_guid_hash),
+        )
+        .route(
+            "/items/{id}/{guid_hash}/unstar",
+            put(routes::nextcloud::unstar_item_by_guid_hash)
+                .post(routes::nextcloud::unstar_item_by_guid_hash),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            routes::nextcloud::require_basic_auth,
+        ));
 
-    let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
+    // Browsers may only call the API from the configured origins
+    let cors_origins = config
+        .cors_origins
+        .iter()
+        .map(|origin| origin.parse::<HeaderValue>())
+        .collect::<Result<Vec<_>, _>>()?;
+    let cors = CorsLayer::new()
+        .allow_origin(AllowOrigin::list(cors_origins))
+        .allow_methods([
+            Method::GET,
+            Method::POST,
+            Method::PUT,
+            Method::PATCH,
+            Method::DELETE,
+        ])
+        .allow_headers([
+            header::AUTHORIZATION,
+            header::CONTENT_TYPE,
+            header::HeaderName::from_static(auth::USER_HEADER),
+        ])
+        .allow_credentials(true);
 
-    Ok(())
+    Ok(Router::new()
+        .nest("/api", api_routes)
+        // Fever clients authenticate with their own api_key
+        .route(
+            "/fever",
+            get(routes::fever::fever).post(routes::fever::fever),
+        )
+        .route(
+            "/fever/",
+            get(routes::fever::fever).post(routes::fever::fever),
+        )
+        .nest("/reader/api/0", reader_routes)
+        .nest("/index.php/apps/news/api/v1-3", nextcloud_routes)
+        .route(
+            "/accounts/ClientLogin",
+            get(routes::greader::client_login).post(routes::greader::client_login),
+        )
+        .fallback_service(ServeDir::new(&config.static_dir).append_index_html_on_directories(true))
+        .layer(cors)
+        .layer(TraceLayer::new_for_http())
+        .with_state(state))
 }
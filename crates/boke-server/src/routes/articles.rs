@@ -1,11 +1,12 @@
-use crate::AppState;
+use crate::auth::UserState;
 use crate::error::ApiError;
 use axum::{
-    extract::{Path, Query, State},
+    extract::{Path, Query},
     http::StatusCode,
     response::{IntoResponse, Json},
 };
-use boke_core::models::ArticleQuery;
+use boke_core::models::{ArticleQuery, ArticleScope, ArticleSort, FolderId, HiddenArticle};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 // Article handlers
@@ -13,29 +14,62 @@ use serde::{Deserialize, Serialize};
 #[derive(Deserialize)]
 pub struct GetArticlesQuery {
     feed_id: Option<i64>,
+    /// A folder id or `unfiled`.
+    folder_id: Option<FolderId>,
     offset: Option<i64>,
     limit: Option<i64>,
     unread_only: Option<bool>,
     favorites_only: Option<bool>,
+    collapse_duplicates: Option<bool>,
+    label_id: Option<i64>,
+    smart_folder_id: Option<i64>,
+    /// `published` (default) or `first_seen`.
+    sort: Option<ArticleSort>,
+    oldest_first: Option<bool>,
+    /// RFC 3339 timestamps.
+    published_after: Option<DateTime<Utc>>,
+    published_before: Option<DateTime<Utc>>,
+    created_after: Option<DateTime<Utc>>,
+    created_before: Option<DateTime<Utc>>,
+    author: Option<String>,
+    has_enclosure: Option<bool>,
+    min_content_length: Option<i64>,
 }
 
 pub async fn get_articles(
-    State(state): State<AppState>,
+    UserState(state): UserState,
     Query(params): Query<GetArticlesQuery>,
 ) -> Result<impl IntoResponse, ApiError> {
     let query = ArticleQuery {
         feed_id: params.feed_id,
+        folder_id: params.folder_id,
         offset: params.offset.unwrap_or(0),
         limit: params.limit.unwrap_or(50),
         unread_only: params.unread_only.unwrap_or(false),
         favorites_only: params.favorites_only.unwrap_or(false),
+        collapse_duplicates: params.collapse_duplicates.unwrap_or(false),
+        label_id: params.label_id,
+        search: match params.smart_folder_id {
+            Some(id) => Some(state.smart_folder_service.search_query(id).await?),
+            None => None,
+        },
+        sort: params.sort.unwrap_or_default(),
+        oldest_first: params.oldest_first.unwrap_or(false),
+        published_after: params.published_after,
+        published_before: params.published_before,
+        created_after: params.created_after,
+        created_before: params.created_before,
+        author: params.author.filter(|a| !a.trim().is_empty()),
+        has_enclosure: params.has_enclosure.unwrap_or(false),
+        min_content_length: params.min_content_length,
+        ..Default::default()
     };
     let articles = state.article_service.get_articles(query).await?;
     Ok(Json(articles))
 }
 
 pub async fn get_article(
-    State(state): State<AppState>,
+    UserState(state): UserState,
     Path(id): Path<i64>,
 ) -> Result<impl IntoResponse, ApiError> {
     let article = state
@@ -47,7 +81,7 @@ pub async fn get_article(
 }
 
 pub async fn toggle_read(
-    State(state): State<AppState>,
+    UserState(state): UserState,
     Path(id): Path<i64>,
 ) -> Result<StatusCode, ApiError> {
     state.article_service.toggle_read(id).await?;
@@ -55,7 +89,7 @@ pub async fn toggle_read(
 }
 
 pub async fn toggle_favorite(
-    State(state): State<AppState>,
+    UserState(state): UserState,
     Path(id): Path<i64>,
 ) -> Result<StatusCode, ApiError> {
     state.article_service.toggle_favorite(id).await?;
@@ -63,7 +97,7 @@ pub async fn toggle_favorite(
 }
 
 pub async fn fetch_article_content(
-    State(state): State<AppState>,
+    UserState(state): UserState,
     Path(id): Path<i64>,
 ) -> Result<Json<ContentResponse>, ApiError> {
     let content = state.article_service.fetch_article_content(id).await?;
@@ -75,24 +109,88 @@ pub struct ContentResponse {
     content: String,
 }
 
+pub async fn hide_article(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+) -> Result<Json<HiddenArticle>, ApiError> {
+    let hidden = state.article_service.hide_article(id).await?;
+    Ok(Json(hidden))
+}
+
+pub async fn delete_article(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+) -> Result<Json<HiddenArticle>, ApiError> {
+    let hidden = state.article_service.delete_article(id).await?;
+    Ok(Json(hidden))
+}
+
+#[derive(Deserialize)]
+pub struct HiddenArticlesQuery {
+    feed_id: Option<i64>,
+}
+
+pub async fn get_hidden_articles(
+    UserState(state): UserState,
+    Query(params): Query<HiddenArticlesQuery>,
+) -> Result<Json<Vec<HiddenArticle>>, ApiError> {
+    let hidden = state
+        .article_service
+        .get_hidden_articles(params.feed_id)
+        .await?;
+    Ok(Json(hidden))
+}
+
+pub async fn unhide_article(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    state.article_service.unhide_article(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
 #[derive(Deserialize)]
 pub struct MarkAllRequest {
+    scope: Option<ArticleScope>,
+    /// Shorthand for a feed scope, kept for older clients.
     feed_id: Option<i64>,
+    label_id: Option<i64>,
+    smart_folder_id: Option<i64>,
+}
+
+impl MarkAllRequest {
+    fn scope(&self) -> ArticleScope {
+        match (self.scope, self.feed_id) {
+            (Some(scope), _) => scope,
+            (None, Some(feed_id)) => ArticleScope::Feed(feed_id),
+            (None, None) => ArticleScope::All,
+        }
+    }
 }
 
 pub async fn mark_all_read(
-    State(state): State<AppState>,
+    UserState(state): UserState,
     Json(req): Json<MarkAllRequest>,
 ) -> Result<StatusCode, ApiError> {
-    state.article_service.mark_all_read(req.feed_id).await?;
+    if let Some(id) = req.smart_folder_id {
+        state.smart_folder_service.mark_read(id).await?;
+    } else if let Some(label_id) = req.label_id {
+        let query = ArticleQuery {
+            label_id: Some(label_id),
+            ..Default::default()
+        };
+        state.article_service.mark_articles_read(&query).await?;
+    } else {
+        state.article_service.mark_all_read(req.scope()).await?;
+    }
     Ok(StatusCode::NO_CONTENT)
 }
 
 pub async fn mark_all_unread(
-    State(state): State<AppState>,
+    UserState(state): UserState,
     Json(req): Json<MarkAllRequest>,
 ) -> Result<StatusCode, ApiError> {
-    state.article_service.mark_all_unread(req.feed_id).await?;
+    state.article_service.mark_all_unread(req.scope()).await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -100,21 +198,31 @@ pub async fn mark_all_unread(
 pub struct SearchQuery {
     q: String,
     limit: Option<i64>,
+    offset: Option<i64>,
 }
 
 pub async fn search_articles(
-    State(state): State<AppState>,
+    UserState(state): UserState,
     Query(params): Query<SearchQuery>,
 ) -> Result<impl IntoResponse, ApiError> {
     let articles = state
         .article_service
-        .search_articles(&params.q, params.limit.unwrap_or(50))
+        .search_articles(
+            &params.q,
+            params.limit.unwrap_or(50),
+            params.offset.unwrap_or(0),
+        )
         .await?;
     Ok(Json(articles))
 }
 
+pub async fn reindex_search(UserState(state): UserState) -> Result<StatusCode, ApiError> {
+    state.article_service.reindex_search().await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
 pub async fn get_favorites_count(
-    State(state): State<AppState>,
+    UserState(state): UserState,
 ) -> Result<Json<CountResponse>, ApiError> {
     let count = state.article_service.get_favorites_count().await?;
     Ok(Json(CountResponse { count }))
@@ -0,0 +1,134 @@
+use crate::AppState;
+use crate::auth::{self, CurrentUser, UserState};
+use crate::error::ApiError;
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Json},
+};
+use boke_core::models::{ApiToken, CreatedApiToken, User};
+use serde::Deserialize;
+
+// Login and session handlers
+
+#[derive(Deserialize)]
+pub struct LoginRequest {
+    pub username: String,
+    pub password: String,
+}
+
+pub async fn login(
+    State(state): State<AppState>,
+    Json(req): Json<LoginRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user = state
+        .user_service
+        .verify_login(&req.username, &req.password)
+        .await?
+        .ok_or_else(|| ApiError::Unauthorized("Invalid username or password".to_string()))?;
+    if user.disabled {
+        return Err(ApiError::Forbidden("User is disabled".to_string()));
+    }
+
+    let token = state.user_service.create_session(user.id).await?;
+    let cookie = auth::session_cookie(&token, state.secure_cookies);
+    Ok(([(header::SET_COOKIE, cookie)], Json(user)))
+}
+
+pub async fn logout(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, ApiError> {
+    if let Some(token) = auth::session_token(&headers) {
+        state.user_service.delete_session(token).await?;
+    }
+    let cookie = auth::expired_session_cookie(state.secure_cookies);
+    Ok((StatusCode::NO_CONTENT, [(header::SET_COOKIE, cookie)]))
+}
+
+pub async fn get_current_user(CurrentUser(user): CurrentUser) -> Json<User> {
+    Json(user)
+}
+
+#[derive(Deserialize)]
+pub struct ChangePasswordRequest {
+    /// Required unless the user has no password yet.
+    pub current_password: Option<String>,
+    pub new_password: String,
+}
+
+/// Change the user's own password. Other sessions are signed out; this one gets a new
+/// session cookie.
+pub async fn change_password(
+    State(state): State<AppState>,
+    CurrentUser(user): CurrentUser,
+    Json(req): Json<ChangePasswordRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    if user.has_password {
+        let current = req.current_password.as_deref().unwrap_or_default();
+        if state
+            .user_service
+            .verify_login(&user.username, current)
+            .await?
+            .is_none()
+        {
+            return Err(ApiError::Unauthorized(
+                "Current password is incorrect".to_string(),
+            ));
+        }
+    }
+
+    state
+        .user_service
+        .set_password(user.id, Some(&req.new_password))
+        .await?;
+    let token = state.user_service.create_session(user.id).await?;
+    let cookie = auth::session_cookie(&token, state.secure_cookies);
+    Ok((StatusCode::NO_CONTENT, [(header::SET_COOKIE, cookie)]))
+}
+
+// API token handlers
+
+pub async fn get_api_tokens(UserState(state): UserState) -> Result<Json<Vec<ApiToken>>, ApiError> {
+    let tokens = state.user_service.get_api_tokens().await?;
+    Ok(Json(tokens))
+}
+
+#[derive(Deserialize)]
+pub struct CreateApiTokenRequest {
+    pub name: String,
+}
+
+pub async fn create_api_token(
+    UserState(state): UserState,
+    Json(req): Json<CreateApiTokenRequest>,
+) -> Result<Json<CreatedApiToken>, ApiError> {
+    let token = state.user_service.create_api_token(&req.name).await?;
+    Ok(Json(token))
+}
+
+pub async fn delete_api_token(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    state.user_service.delete_api_token(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Deserialize)]
+pub struct SetFeverPasswordRequest {
+    /// `None` turns Fever access off.
+    pub password: Option<String>,
+}
+
+/// Set the password Fever clients use for the user's account.
+pub async fn set_fever_password(
+    UserState(state): UserState,
+    Json(req): Json<SetFeverPasswordRequest>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .user_service
+        .set_fever_password(req.password.as_deref())
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,43 @@
+use crate::auth::UserState;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use boke_core::{AppEvent, Audience, BusEvent, Database, DatabasePool};
+use futures_util::{Stream, stream};
+use std::convert::Infallible;
+use tokio::sync::broadcast::{Receiver, error::RecvError};
+
+/// Live changes for the request's user as server-sent events, one JSON [`AppEvent`] per
+/// message, so clients can update without polling.
+pub async fn events(
+    UserState(state): UserState,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let stream = stream::unfold(
+        (state.events.subscribe(), state.db),
+        |(mut events, db)| async move {
+            let event = next_event(&mut events, &db).await?;
+            let message = Event::default().json_data(&event).unwrap_or_default();
+            Some((Ok(message), (events, db)))
+        },
+    );
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// The next event meant for the user `db` acts as, or `None` once the bus is gone.
+async fn next_event(events: &mut Receiver<BusEvent>, db: &DatabasePool) -> Option<AppEvent> {
+    loop {
+        match events.recv().await {
+            Ok(BusEvent { audience, event }) => {
+                let for_user = match audience {
+                    Audience::User(user_id) => user_id == db.user_id(),
+                    Audience::FeedSubscribers(feed_id) => {
+                        matches!(db.get_feed(feed_id).await, Ok(Some(_)))
+                    }
+                };
+                if for_user {
+                    return Some(event);
+                }
+            }
+            Err(RecvError::Lagged(_)) => return Some(AppEvent::Resync),
+            Err(RecvError::Closed) => return None,
+        }
+    }
+}
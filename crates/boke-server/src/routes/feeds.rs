@@ -1,18 +1,24 @@
-use crate::AppState;
+use crate::auth::UserState;
 use crate::error::ApiError;
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::Json,
+    extract::Path,
+    http::{StatusCode, header},
+    response::{
+        IntoResponse, Json,
+        sse::{Event, KeepAlive, Sse},
+    },
 };
 use axum_extra::extract::Multipart;
-use boke_core::models::FeedWithMeta;
-use boke_core::parse_opml;
+use boke_core::models::{FeedStatus, FeedUpdate, FeedWithMeta};
+use boke_core::{Database, ImportEvent, ImportJob, parse_opml};
+use futures_util::{Stream, StreamExt, stream};
 use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use tokio::sync::broadcast::error::RecvError;
 
 // Feed handlers
 
-pub async fn get_feeds(State(state): State<AppState>) -> Result<Json<Vec<FeedWithMeta>>, ApiError> {
+pub async fn get_feeds(UserState(state): UserState) -> Result<Json<Vec<FeedWithMeta>>, ApiError> {
     let feeds = state.feed_service.get_feeds().await?;
     Ok(Json(feeds))
 }
@@ -23,7 +29,7 @@ pub struct AddFeedRequest {
 }
 
 pub async fn add_feed(
-    State(state): State<AppState>,
+    UserState(state): UserState,
     Json(req): Json<AddFeedRequest>,
 ) -> Result<Json<FeedWithMeta>, ApiError> {
     let feed = state.feed_service.add_feed(&req.url).await?;
@@ -31,13 +37,36 @@ pub async fn add_feed(
 }
 
 pub async fn remove_feed(
-    State(state): State<AppState>,
+    UserState(state): UserState,
     Path(id): Path<i64>,
 ) -> Result<StatusCode, ApiError> {
     state.feed_service.remove_feed(id).await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
+pub async fn update_feed(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+    Json(req): Json<FeedUpdate>,
+) -> Result<Json<FeedWithMeta>, ApiError> {
+    let feed = state.feed_service.update_feed(id, &req).await?;
+    Ok(Json(feed))
+}
+
+#[derive(Deserialize)]
+pub struct FeedStatusRequest {
+    status: FeedStatus,
+}
+
+pub async fn set_feed_status(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+    Json(req): Json<FeedStatusRequest>,
+) -> Result<Json<FeedWithMeta>, ApiError> {
+    let feed = state.feed_service.set_feed_status(id, req.status).await?;
+    Ok(Json(feed))
+}
+
 #[derive(Serialize)]
 pub struct RefreshResult {
     feed_id: i64,
@@ -45,7 +74,7 @@ pub struct RefreshResult {
 }
 
 pub async fn refresh_feed(
-    State(state): State<AppState>,
+    UserState(state): UserState,
     Path(id): Path<i64>,
 ) -> Result<Json<RefreshResult>, ApiError> {
     let result = state.feed_service.refresh_feed(id).await?;
@@ -56,7 +85,7 @@ pub async fn refresh_feed(
 }
 
 pub async fn refresh_all_feeds(
-    State(state): State<AppState>,
+    UserState(state): UserState,
 ) -> Result<Json<Vec<RefreshResult>>, ApiError> {
     let results = state.feed_service.refresh_all_feeds().await?;
     Ok(Json(
@@ -70,17 +99,26 @@ pub async fn refresh_all_feeds(
     ))
 }
 
-#[derive(Serialize)]
-pub struct ImportResult {
-    pub added: i32,
-    pub skipped: i32,
-    pub errors: Vec<String>,
+pub async fn export_opml(UserState(state): UserState) -> Result<impl IntoResponse, ApiError> {
+    let opml = state.folder_service.export_opml().await?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/x-opml; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"boke-subscriptions.opml\"",
+            ),
+        ],
+        opml,
+    ))
 }
 
+/// Start importing an uploaded OPML file in the background. Follow the returned job with
+/// `GET /feeds/import/{id}/events`.
 pub async fn import_opml(
-    State(state): State<AppState>,
+    UserState(state): UserState,
     mut multipart: Multipart,
-) -> Result<Json<ImportResult>, ApiError> {
+) -> Result<(StatusCode, Json<ImportJob>), ApiError> {
     // Extract the file content from the multipart form
     let mut file_content: Option<String> = None;
 
@@ -106,40 +144,79 @@ pub async fn import_opml(
     let content =
         file_content.ok_or_else(|| ApiError::BadRequest("No file provided".to_string()))?;
 
-    // Parse the OPML content
-    let urls = parse_opml(&content)
+    let outlines = parse_opml(&content)
         .map_err(|e| ApiError::BadRequest(format!("Failed to parse OPML: {}", e)))?;
+    let (job, _) = state
+        .import_jobs
+        .start(state.feed_service.clone(), &outlines)
+        .await?;
 
-    // Get existing feeds to check for duplicates
-    let existing_feeds = state.feed_service.get_feeds().await?;
-    let existing_urls: std::collections::HashSet<_> = existing_feeds
-        .iter()
-        .map(|f| f.feed_url.to_lowercase())
-        .collect();
-
-    let mut added = 0;
-    let mut skipped = 0;
-    let mut errors = Vec::new();
-
-    // Add each feed
-    for url in urls {
-        // Check if already subscribed (case-insensitive)
-        if existing_urls.contains(&url.to_lowercase()) {
-            skipped += 1;
-            continue;
-        }
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
 
-        match state.feed_service.add_feed(&url).await {
-            Ok(_) => added += 1,
-            Err(e) => {
-                errors.push(format!("{}: {}", url, e));
-            }
-        }
+pub async fn get_import_job(
+    UserState(state): UserState,
+    Path(id): Path<String>,
+) -> Result<Json<ImportJob>, ApiError> {
+    let job = state
+        .import_jobs
+        .get(state.db.user_id(), &id)
+        .ok_or(ApiError::NotFound)?;
+    Ok(Json(job))
+}
+
+/// Stop an import job; feeds already being fetched still finish.
+pub async fn cancel_import_job(
+    UserState(state): UserState,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    if !state.import_jobs.cancel(state.db.user_id(), &id) {
+        return Err(ApiError::NotFound);
     }
+    Ok(StatusCode::NO_CONTENT)
+}
 
-    Ok(Json(ImportResult {
-        added,
-        skipped,
-        errors,
-    }))
+/// Import progress as server-sent events: a `job` event with the job as it stands, then an
+/// `item` event per feed status change and a final `finished` event.
+pub async fn import_job_events(
+    UserState(state): UserState,
+    Path(id): Path<String>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let (job, events) = state
+        .import_jobs
+        .subscribe(state.db.user_id(), &id)
+        .ok_or(ApiError::NotFound)?;
+
+    let first = sse_event("job", &job);
+    let rest = stream::unfold(
+        (events, job.is_finished()),
+        |(mut events, done)| async move {
+            if done {
+                return None;
+            }
+            loop {
+                match events.recv().await {
+                    Ok(event) => {
+                        let (name, done) = match event {
+                            ImportEvent::Item { .. } => ("item", false),
+                            ImportEvent::Finished { .. } => ("finished", true),
+                        };
+                        return Some((sse_event(name, &event), (events, done)));
+                    }
+                    // The channel has room for every event of the job
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        },
+    );
+
+    Ok(Sse::new(stream::once(async move { first }).chain(rest)).keep_alive(KeepAlive::default()))
+}
+
+fn sse_event(name: &str, data: &impl Serialize) -> Result<Event, Infallible> {
+    Ok(Event::default()
+        .event(name)
+        .json_data(data)
+        .unwrap_or_default())
 }
@@ -0,0 +1,553 @@
+//! The Fever API, for clients such as Reeder and Unread.
+//!
+//! Everything goes through `/fever/`: the query string names what to return (`?api&items`)
+//! and the form body carries the `api_key` and any `mark` request. Fever groups are flat,
+//! so each folder is a group holding only the feeds directly in it.
+
+use crate::AppState;
+use crate::config::AuthMode;
+use crate::error::ApiError;
+use axum::{
+    body::Bytes,
+    extract::{RawQuery, State},
+    response::Json,
+};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use boke_core::DEFAULT_USER_ID;
+use boke_core::models::{Article, ArticleQuery, ArticleSort, FeedWithMeta, User};
+use chrono::{DateTime, Duration, Utc};
+use futures_util::StreamExt;
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use url::form_urlencoded;
+
+const API_VERSION: i64 = 3;
+
+/// Fever clients page through items 50 at a time.
+const ITEMS_PER_PAGE: i64 = 50;
+
+/// Favicons downloaded at the same time when a client asks for them.
+const FAVICON_CONCURRENCY: usize = 6;
+
+/// How long a downloaded favicon, or the failure to get one, is reused.
+const FAVICON_TTL_HOURS: i64 = 24;
+
+/// Icons larger than this are left out.
+const MAX_FAVICON_BYTES: usize = 256 * 1024;
+
+type Params = HashMap<String, String>;
+
+/// Favicons as Fever data URIs by URL, shared by every user, so that clients polling
+/// `favicons` don't download each feed's icon again every time.
+#[derive(Clone, Default)]
+pub struct FaviconCache(Arc<Mutex<HashMap<String, CachedFavicon>>>);
+
+struct CachedFavicon {
+    /// `None` when the icon could not be downloaded.
+    data: Option<String>,
+    fetched_at: DateTime<Utc>,
+}
+
+impl FaviconCache {
+    /// The icon for a URL, or `None` when it was never fetched or has gone stale.
+    fn get(&self, url: &str) -> Option<Option<String>> {
+        let cutoff = Utc::now() - Duration::hours(FAVICON_TTL_HOURS);
+        self.lock()
+            .get(url)
+            .filter(|icon| icon.fetched_at > cutoff)
+            .map(|icon| icon.data.clone())
+    }
+
+    fn insert(&self, url: &str, data: Option<String>) {
+        let icon = CachedFavicon {
+            data,
+            fetched_at: Utc::now(),
+        };
+        self.lock().insert(url.to_string(), icon);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, CachedFavicon>> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+pub async fn fever(
+    State(state): State<AppState>,
+    RawQuery(query): RawQuery,
+    body: Bytes,
+) -> Result<Json<Value>, ApiError> {
+    let mut params: Params = form_urlencoded::parse(query.unwrap_or_default().as_bytes())
+        .into_owned()
+        .collect();
+    params.extend(form_urlencoded::parse(&body).into_owned());
+
+    let mut response = Map::new();
+    response.insert("api_version".to_string(), json!(API_VERSION));
+    let Some(user) = authenticate(&state, &params).await? else {
+        response.insert("auth".to_string(), json!(0));
+        return Ok(Json(Value::Object(response)));
+    };
+    response.insert("auth".to_string(), json!(1));
+    let state = state.for_user(user.id);
+
+    let feeds = state.feed_service.get_feeds().await?;
+    let last_refreshed = feeds
+        .iter()
+        .filter_map(|f| f.last_fetched_at)
+        .max()
+        .map_or(0, |t| t.timestamp());
+    response.insert("last_refreshed_on_time".to_string(), json!(last_refreshed));
+
+    // Marking answers with the ids that changed, as if the client had asked for them
+    if params.contains_key("mark") {
+        let changed = mark(&state, &params, &feeds).await?;
+        params.insert(changed.to_string(), String::new());
+    }
+
+    if params.contains_key("groups") {
+        let folders = state.folder_service.get_folders().await?;
+        let groups: Vec<Value> = folders
+            .iter()
+            .map(|f| json!({ "id": f.id, "title": f.name }))
+            .collect();
+        response.insert("groups".to_string(), json!(groups));
+    }
+    if params.contains_key("feeds") {
+        let items: Vec<Value> = feeds.iter().map(feed_json).collect();
+        response.insert("feeds".to_string(), json!(items));
+    }
+    if params.contains_key("groups") || params.contains_key("feeds") {
+        response.insert("feeds_groups".to_string(), feeds_groups(&feeds));
+    }
+    if params.contains_key("favicons") {
+        response.insert("favicons".to_string(), favicons(&state, &feeds).await);
+    }
+    if params.contains_key("items") {
+        let items = get_items(&state, &params).await?;
+        let total = state
+            .article_service
+            .count_articles(&ArticleQuery::default())
+            .await?;
+        response.insert("items".to_string(), json!(items));
+        response.insert("total_items".to_string(), json!(total));
+    }
+    if params.contains_key("links") {
+        response.insert("links".to_string(), json!([]));
+    }
+    if params.contains_key("unread_item_ids") {
+        let query = ArticleQuery {
+            unread_only: true,
+            ..id_order()
+        };
+        let ids = state.article_service.get_article_ids(&query).await?;
+        response.insert("unread_item_ids".to_string(), json!(join_ids(&ids)));
+    }
+    if params.contains_key("saved_item_ids") {
+        let query = ArticleQuery {
+            favorites_only: true,
+            ..id_order()
+        };
+        let ids = state.article_service.get_article_ids(&query).await?;
+        response.insert("saved_item_ids".to_string(), json!(join_ids(&ids)));
+    }
+
+    Ok(Json(Value::Object(response)))
+}
+
+/// The user whose Fever key was given. Without authentication every client acts for the
+/// default user.
+async fn authenticate(state: &AppState, params: &Params) -> Result<Option<User>, ApiError> {
+    let mut user = match params.get("api_key") {
+        Some(key) => state.user_service.get_fever_user(key).await?,
+        None => None,
+    };
+    if user.is_none() && state.auth_mode == AuthMode::None {
+        user = state.user_service.get_user(DEFAULT_USER_ID).await?;
+    }
+    Ok(user.filter(|u| !u.disabled))
+}
+
+/// Apply a `mark` request, returning which id list it changed.
+async fn mark(
+    state: &AppState,
+    params: &Params,
+    feeds: &[FeedWithMeta],
+) -> Result<&'static str, ApiError> {
+    let kind = params.get("mark").map(String::as_str).unwrap_or_default();
+    let action = params.get("as").map(String::as_str).unwrap_or_default();
+    let id = parse_id(params, "id")?
+        .ok_or_else(|| ApiError::BadRequest("id is required".to_string()))?;
+    let articles = &state.article_service;
+
+    match (kind, action) {
+        ("item", "read") => articles.set_read(id, true).await?,
+        ("item", "unread") => articles.set_read(id, false).await?,
+        ("item", "saved") => {
+            articles.set_favorite(id, true).await?;
+            return Ok("saved_item_ids");
+        }
+        ("item", "unsaved") => {
+            articles.set_favorite(id, false).await?;
+            return Ok("saved_item_ids");
+        }
+        ("feed", "read") => {
+            articles
+                .mark_articles_read(&ArticleQuery {
+                    feed_id: Some(id),
+                    published_before: parse_time(params, "before")?,
+                    ..Default::default()
+                })
+                .await?
+        }
+        // Group 0 is every feed; negative ids are Fever's sparks, which we don't have
+        ("group", "read") if id == 0 => {
+            articles
+                .mark_articles_read(&ArticleQuery {
+                    published_before: parse_time(params, "before")?,
+                    ..Default::default()
+                })
+                .await?
+        }
+        ("group", "read") => {
+            let before = parse_time(params, "before")?;
+            for feed in feeds.iter().filter(|f| f.folder_id == Some(id)) {
+                articles
+                    .mark_articles_read(&ArticleQuery {
+                        feed_id: Some(feed.id),
+                        published_before: before,
+                        ..Default::default()
+                    })
+                    .await?;
+            }
+        }
+        _ => {
+            return Err(ApiError::BadRequest(format!(
+                "Cannot mark {} as {}",
+                kind, action
+            )));
+        }
+    }
+    Ok("unread_item_ids")
+}
+
+/// Up to a page of items: those listed in `with_ids`, those below `max_id` newest first,
+/// or those above `since_id` oldest first.
+async fn get_items(state: &AppState, params: &Params) -> Result<Vec<Value>, ApiError> {
+    let mut query = ArticleQuery {
+        limit: ITEMS_PER_PAGE,
+        ..id_order()
+    };
+    if let Some(ids) = params.get("with_ids") {
+        let ids = ids
+            .split(',')
+            .filter(|id| !id.trim().is_empty())
+            .map(|id| id.trim().parse::<i64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| ApiError::BadRequest("Invalid with_ids".to_string()))?;
+        query.ids = Some(ids);
+    } else if let Some(max_id) = parse_id(params, "max_id")? {
+        query.before_id = (max_id > 0).then_some(max_id);
+        query.oldest_first = false;
+    } else {
+        query.after_id = Some(parse_id(params, "since_id")?.unwrap_or(0));
+    }
+
+    let articles = state.article_service.get_articles(query).await?;
+    Ok(articles.iter().map(item_json).collect())
+}
+
+/// Articles in the order they were stored, which is what Fever's item ids follow.
+fn id_order() -> ArticleQuery {
+    ArticleQuery {
+        sort: ArticleSort::Id,
+        oldest_first: true,
+        ..Default::default()
+    }
+}
+
+fn feed_json(feed: &FeedWithMeta) -> Value {
+    json!({
+        "id": feed.id,
+        "favicon_id": if feed.favicon_url.is_some() { feed.id } else { 0 },
+        "title": feed.title,
+        "url": feed.feed_url,
+        "site_url": feed.site_url.as_deref().unwrap_or_default(),
+        "is_spark": 0,
+        "last_updated_on_time": feed.last_fetched_at.map_or(0, |t| t.timestamp()),
+    })
+}
+
+fn item_json(article: &Article) -> Value {
+    let html = article.content.as_ref().or(article.summary.as_ref());
+    json!({
+        "id": article.id,
+        "feed_id": article.feed_id,
+        "title": article.title,
+        "author": article.author.as_deref().unwrap_or_default(),
+        "html": html.map(String::as_str).unwrap_or_default(),
+        "url": article.link.as_deref().unwrap_or_default(),
+        "is_saved": article.is_favorite as i32,
+        "is_read": article.is_read as i32,
+        "created_on_time": article
+            .published_at
+            .or(article.created_at)
+            .map_or(0, |t| t.timestamp()),
+    })
+}
+
+/// Each folder's direct feeds, as a comma-separated id list.
+fn feeds_groups(feeds: &[FeedWithMeta]) -> Value {
+    let mut groups: Vec<(i64, Vec<i64>)> = Vec::new();
+    for feed in feeds {
+        let Some(folder_id) = feed.folder_id else {
+            continue;
+        };
+        match groups.iter_mut().find(|(id, _)| *id == folder_id) {
+            Some((_, ids)) => ids.push(feed.id),
+            None => groups.push((folder_id, vec![feed.id])),
+        }
+    }
+    groups
+        .iter()
+        .map(|(id, feed_ids)| json!({ "group_id": id, "feed_ids": join_ids(feed_ids) }))
+        .collect()
+}
+
+/// The feeds' favicons as data URIs without the `data:` prefix, keyed by feed id. Icons
+/// missing from the cache are downloaded a few at a time; those that fail to download
+/// are left out.
+async fn favicons(state: &AppState, feeds: &[FeedWithMeta]) -> Value {
+    let cache = &state.fever_favicons;
+    let mut missing: Vec<&str> = feeds
+        .iter()
+        .filter_map(|f| f.favicon_url.as_deref())
+        .filter(|url| cache.get(url).is_none())
+        .collect();
+    missing.sort_unstable();
+    missing.dedup();
+
+    futures_util::stream::iter(missing)
+        .for_each_concurrent(FAVICON_CONCURRENCY, |url| async move {
+            let data = match state.feed_service.fetch_favicon_image(url).await {
+                Ok((_, bytes)) if bytes.len() > MAX_FAVICON_BYTES => {
+                    tracing::debug!("Favicon {} is too large ({} bytes)", url, bytes.len());
+                    None
+                }
+                Ok((content_type, bytes)) => {
+                    Some(format!("{};base64,{}", content_type, BASE64.encode(bytes)))
+                }
+                Err(e) => {
+                    tracing::debug!("Failed to fetch favicon {}: {}", url, e);
+                    None
+                }
+            };
+            cache.insert(url, data);
+        })
+        .await;
+
+    let icons: Vec<Value> = feeds
+        .iter()
+        .filter_map(|feed| {
+            let data = cache.get(feed.favicon_url.as_deref()?)??;
+            Some(json!({ "id": feed.id, "data": data }))
+        })
+        .collect();
+    json!(icons)
+}
+
+fn parse_id(params: &Params, name: &str) -> Result<Option<i64>, ApiError> {
+    match params.get(name).map(|v| v.trim()) {
+        None | Some("") => Ok(None),
+        Some(value) => value
+            .parse()
+            .map(Some)
+            .map_err(|_| ApiError::BadRequest(format!("Invalid {}", name))),
+    }
+}
+
+/// A Unix timestamp parameter such as `before`.
+fn parse_time(params: &Params, name: &str) -> Result<Option<DateTime<Utc>>, ApiError> {
+    match parse_id(params, name)? {
+        None => Ok(None),
+        Some(secs) => DateTime::from_timestamp(secs, 0)
+            .map(Some)
+            .ok_or_else(|| ApiError::BadRequest(format!("Invalid {}", name))),
+    }
+}
+
+fn join_ids(ids: &[i64]) -> String {
+    ids.iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::{PASSWORD, add_article, add_feed, send, test_app};
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode, header};
+    use axum::{Router, routing::get};
+    use boke_core::auth::fever_api_key;
+    use boke_core::models::NewUser;
+    use serde_json::{Value, json};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn request(query: &str, form: &str) -> Request<Body> {
+        Request::post(format!("/fever/?api&{}", query))
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(form.to_string()))
+            .unwrap()
+    }
+
+    async fn fever(app: &Router, query: &str, form: &str) -> Value {
+        let (status, body) = send(app, request(query, form)).await;
+        assert_eq!(status, StatusCode::OK);
+        serde_json::from_str(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn api_keys_authenticate_enabled_users() {
+        let (app, state) = test_app().await;
+        state
+            .user_service
+            .set_fever_password(Some(PASSWORD))
+            .await
+            .unwrap();
+        let reader = state
+            .user_service
+            .create_user(&NewUser {
+                username: "reader".to_string(),
+                is_admin: false,
+                password: None,
+            })
+            .await
+            .unwrap();
+        let readers = state.user_service.for_user(reader.id);
+        readers.set_fever_password(Some(PASSWORD)).await.unwrap();
+
+        let key = format!("api_key={}", fever_api_key("admin", PASSWORD));
+        let response = fever(&app, "feeds", &key).await;
+        assert_eq!(response["auth"], 1);
+        assert_eq!(response["feeds"], json!([]));
+
+        let wrong = format!("api_key={}", fever_api_key("admin", "wrong"));
+        let response = fever(&app, "feeds", &wrong).await;
+        assert_eq!(response["auth"], 0);
+        assert!(response.get("feeds").is_none());
+        assert_eq!(fever(&app, "feeds", "").await["auth"], 0);
+
+        let key = format!("api_key={}", fever_api_key("reader", PASSWORD));
+        assert_eq!(fever(&app, "feeds", &key).await["auth"], 1);
+        state
+            .user_service
+            .set_user_disabled(reader.id, true)
+            .await
+            .unwrap();
+        assert_eq!(fever(&app, "feeds", &key).await["auth"], 0);
+    }
+
+    #[tokio::test]
+    async fn items_page_by_id_and_marks_update_the_id_lists() {
+        let (app, state) = test_app().await;
+        state
+            .user_service
+            .set_fever_password(Some(PASSWORD))
+            .await
+            .unwrap();
+        let key = format!("api_key={}", fever_api_key("admin", PASSWORD));
+        let loose = add_feed(&state, "http://loose.test/feed", None).await;
+        let filed = add_feed(&state, "http://filed.test/feed", None).await;
+        let group = state
+            .folder_service
+            .create_folder("News", None)
+            .await
+            .unwrap();
+        state
+            .folder_service
+            .move_feed_to_folder(filed, Some(group.id))
+            .await
+            .unwrap();
+        let first = add_article(&state, loose, "http://loose.test/1", "First").await;
+        let second = add_article(&state, filed, "http://filed.test/2", "Second").await;
+        let third = add_article(&state, filed, "http://filed.test/3", "Third").await;
+        let ids = |items: &Value| -> Vec<i64> {
+            let items = items["items"].as_array().unwrap();
+            items.iter().map(|i| i["id"].as_i64().unwrap()).collect()
+        };
+
+        let since = fever(&app, &format!("items&since_id={}", first), &key).await;
+        assert_eq!(ids(&since), vec![second, third]);
+        assert_eq!(since["total_items"], 3);
+        let below = fever(&app, &format!("items&max_id={}", third), &key).await;
+        assert_eq!(ids(&below), vec![second, first]);
+        let listed = fever(&app, &format!("items&with_ids={},{}", third, first), &key).await;
+        assert_eq!(ids(&listed), vec![first, third]);
+
+        let unread = |response: Value| response["unread_item_ids"].as_str().unwrap().to_string();
+        let response = fever(&app, "unread_item_ids", &key).await;
+        assert_eq!(unread(response), format!("{},{},{}", first, second, third));
+
+        // Marks answer with the list they changed
+        let mark = format!("{}&mark=item&as=read&id={}", key, second);
+        let response = fever(&app, "", &mark).await;
+        assert_eq!(unread(response), format!("{},{}", first, third));
+        let mark = format!("{}&mark=item&as=saved&id={}", key, third);
+        let response = fever(&app, "", &mark).await;
+        assert_eq!(response["saved_item_ids"], third.to_string());
+        let mark = format!("{}&mark=group&as=read&id={}", key, group.id);
+        let response = fever(&app, "", &mark).await;
+        assert_eq!(unread(response), first.to_string());
+        let mark = format!("{}&mark=feed&as=read&id={}", key, loose);
+        let response = fever(&app, "", &mark).await;
+        assert_eq!(unread(response), "");
+        let response = fever(&app, "saved_item_ids", &key).await;
+        assert_eq!(response["saved_item_ids"], third.to_string());
+
+        let bad = format!("{}&mark=item&as=shredded&id={}", key, first);
+        let (status, _) = send(&app, request("", &bad)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn favicons_are_downloaded_once_and_cached() {
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        let icons = Router::new().route(
+            "/icon.png",
+            get(move || async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                (
+                    [(header::CONTENT_TYPE, "image/png")],
+                    vec![0x89, b'P', b'N', b'G'],
+                )
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let icon = format!("http://{}/icon.png", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, icons).await.unwrap() });
+
+        let (app, state) = test_app().await;
+        state
+            .user_service
+            .set_fever_password(Some(PASSWORD))
+            .await
+            .unwrap();
+        let key = format!("api_key={}", fever_api_key("admin", PASSWORD));
+        let one = add_feed(&state, "http://one.test/feed", Some(&icon)).await;
+        let two = add_feed(&state, "http://two.test/feed", Some(&icon)).await;
+
+        for _ in 0..2 {
+            let response = fever(&app, "favicons", &key).await;
+            let mut favicons = response["favicons"].as_array().unwrap().clone();
+            favicons.sort_by_key(|f| f["id"].as_i64());
+            let expected = json!({ "id": one, "data": "image/png;base64,iVBORw==" });
+            assert_eq!(favicons[0], expected);
+            assert_eq!(favicons[1]["id"], two);
+        }
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,29 +1,38 @@
-use crate::AppState;
+use crate::auth::UserState;
 use crate::error::ApiError;
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query},
     http::StatusCode,
     response::Json,
 };
-use boke_core::models::Folder;
+use boke_core::models::{Folder, FolderDeleteMode, FolderTree};
 use serde::Deserialize;
 // Folder handlers
 
-pub async fn get_folders(State(state): State<AppState>) -> Result<Json<Vec<Folder>>, ApiError> {
+pub async fn get_folders(UserState(state): UserState) -> Result<Json<Vec<Folder>>, ApiError> {
     let folders = state.folder_service.get_folders().await?;
     Ok(Json(folders))
 }
 
+pub async fn get_folder_tree(UserState(state): UserState) -> Result<Json<FolderTree>, ApiError> {
+    let tree = state.folder_service.get_folder_tree().await?;
+    Ok(Json(tree))
+}
+
 #[derive(Deserialize)]
 pub struct CreateFolderRequest {
     name: String,
+    parent_id: Option<i64>,
 }
 
 pub async fn create_folder(
-    State(state): State<AppState>,
+    UserState(state): UserState,
     Json(req): Json<CreateFolderRequest>,
 ) -> Result<Json<Folder>, ApiError> {
-    let folder = state.folder_service.create_folder(&req.name).await?;
+    let folder = state
+        .folder_service
+        .create_folder(&req.name, req.parent_id)
+        .await?;
     Ok(Json(folder))
 }
 
@@ -33,7 +42,7 @@ pub struct RenameFolderRequest {
 }
 
 pub async fn rename_folder(
-    State(state): State<AppState>,
+    UserState(state): UserState,
     Path(id): Path<i64>,
     Json(req): Json<RenameFolderRequest>,
 ) -> Result<StatusCode, ApiError> {
@@ -41,16 +50,73 @@ pub async fn rename_folder(
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[derive(Deserialize)]
+pub struct DeleteFolderQuery {
+    mode: Option<FolderDeleteMode>,
+}
+
 pub async fn delete_folder(
-    State(state): State<AppState>,
+    UserState(state): UserState,
     Path(id): Path<i64>,
+    Query(params): Query<DeleteFolderQuery>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .folder_service
+        .delete_folder(id, params.mode.unwrap_or_default())
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Deserialize)]
+pub struct MoveFolderRequest {
+    parent_id: Option<i64>,
+}
+
+pub async fn move_folder(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+    Json(req): Json<MoveFolderRequest>,
+) -> Result<StatusCode, ApiError> {
+    state.folder_service.move_folder(id, req.parent_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Deserialize)]
+pub struct ReorderFoldersRequest {
+    parent_id: Option<i64>,
+    folder_ids: Vec<i64>,
+}
+
+pub async fn reorder_folders(
+    UserState(state): UserState,
+    Json(req): Json<ReorderFoldersRequest>,
 ) -> Result<StatusCode, ApiError> {
-    state.folder_service.delete_folder(id).await?;
+    state
+        .folder_service
+        .reorder_folders(req.parent_id, &req.folder_ids)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Deserialize)]
+pub struct ReorderFeedsRequest {
+    folder_id: Option<i64>,
+    feed_ids: Vec<i64>,
+}
+
+pub async fn reorder_feeds(
+    UserState(state): UserState,
+    Json(req): Json<ReorderFeedsRequest>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .folder_service
+        .reorder_feeds(req.folder_id, &req.feed_ids)
+        .await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
 pub async fn move_feed_to_folder(
-    State(state): State<AppState>,
+    UserState(state): UserState,
     Path((folder_id, feed_id)): Path<(i64, i64)>,
 ) -> Result<StatusCode, ApiError> {
     state
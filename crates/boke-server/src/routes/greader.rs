@@ -0,0 +1,770 @@
+//! The Google Reader API as implemented by FreshRSS and Miniflux, for clients such as
+//! Reeder, NetNewsWire, FeedMe and ReadYou.
+//!
+//! Clients log in with `/accounts/ClientLogin` and send the returned token as
+//! `Authorization: GoogleLogin auth=<token>` to everything under `/reader/api/0/`.
+//! Feeds are the streams `feed/<id>`, folders are the labels `user/-/label/<path>` with
+//! the folder's path from the top level, e.g. `Tech/Rust`, and
+//! item ids are article ids, either in decimal or as `tag:google.com,2005:reader/item/`
+//! followed by 16 hex digits.
+
+use crate::AppState;
+use crate::auth::{CurrentUser, UserState};
+use crate::config::AuthMode;
+use crate::error::ApiError;
+use axum::{
+    body::Bytes,
+    extract::{FromRequest, Path, Request, State},
+    http::{HeaderMap, header},
+    middleware::Next,
+    response::{Json, Response},
+};
+use boke_core::DEFAULT_USER_ID;
+use boke_core::models::{Article, ArticleQuery, FeedUpdate, Folder, FolderId, User};
+use chrono::{DateTime, Utc};
+use serde_json::{Value, json};
+use url::form_urlencoded;
+
+const READING_LIST: &str = "user/-/state/com.google/reading-list";
+const READ: &str = "user/-/state/com.google/read";
+const STARRED: &str = "user/-/state/com.google/starred";
+const KEPT_UNREAD: &str = "user/-/state/com.google/kept-unread";
+const LABEL_PREFIX: &str = "user/-/label/";
+const FEED_PREFIX: &str = "feed/";
+const ITEM_PREFIX: &str = "tag:google.com,2005:reader/item/";
+
+/// Items per page when the client doesn't ask for a number.
+const DEFAULT_PAGE_SIZE: i64 = 20;
+const MAX_CONTENTS_PAGE_SIZE: i64 = 1000;
+const MAX_IDS_PAGE_SIZE: i64 = 10000;
+
+/// Query string and form parameters together, in order. Keys such as `i` and `s` repeat.
+pub struct ReaderParams(Vec<(String, String)>);
+
+impl ReaderParams {
+    fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> {
+        self.0
+            .iter()
+            .filter(move |(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn get_i64(&self, name: &str) -> Result<Option<i64>, ApiError> {
+        match self.get(name).map(str::trim) {
+            None | Some("") => Ok(None),
+            Some(value) => value
+                .parse()
+                .map(Some)
+                .map_err(|_| ApiError::BadRequest(format!("Invalid {}", name))),
+        }
+    }
+}
+
+impl<S: Send + Sync> FromRequest<S> for ReaderParams {
+    type Rejection = ApiError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, ApiError> {
+        let mut params: Vec<(String, String)> = request
+            .uri()
+            .query()
+            .map(|query| {
+                form_urlencoded::parse(query.as_bytes())
+                    .into_owned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        let body = Bytes::from_request(request, state)
+            .await
+            .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+        params.extend(form_urlencoded::parse(&body).into_owned());
+        Ok(Self(params))
+    }
+}
+
+// Login
+
+/// Middleware that authenticates `GoogleLogin` tokens for the [`UserState`] extractor.
+/// Both login sessions and API tokens are accepted.
+pub async fn require_login(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let mut user = match google_login_token(request.headers()) {
+        Some(token) => match state.user_service.get_session_user(token).await? {
+            Some(user) => Some(user),
+            None => state.user_service.get_token_user(token).await?,
+        },
+        None => None,
+    };
+    if user.is_none() && state.auth_mode == AuthMode::None {
+        user = state.user_service.get_user(DEFAULT_USER_ID).await?;
+    }
+    let user = user.ok_or_else(|| ApiError::Unauthorized("Login required".to_string()))?;
+    if user.disabled {
+        return Err(ApiError::Forbidden("User is disabled".to_string()));
+    }
+    request.extensions_mut().insert(CurrentUser(user));
+    Ok(next.run(request).await)
+}
+
+/// The token from `Authorization: GoogleLogin auth=<token>`. Some clients prefix it with
+/// the username and a slash, as Miniflux issues them.
+fn google_login_token(headers: &HeaderMap) -> Option<&str> {
+    let value = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    let token = value.strip_prefix("GoogleLogin auth=")?.trim();
+    Some(token.rsplit('/').next().unwrap_or(token))
+}
+
+/// Log in with `Email` and `Passwd`, answering with a session token in the plain-text
+/// format Google used.
+pub async fn client_login(
+    State(state): State<AppState>,
+    params: ReaderParams,
+) -> Result<String, ApiError> {
+    let username = params.get("Email").unwrap_or_default();
+    let password = params.get("Passwd").unwrap_or_default();
+    let user = match state.auth_mode {
+        AuthMode::Required => state.user_service.verify_login(username, password).await?,
+        AuthMode::None => state.user_service.get_user_by_name(username).await?,
+    }
+    .ok_or_else(|| ApiError::Unauthorized("Invalid username or password".to_string()))?;
+    if user.disabled {
+        return Err(ApiError::Forbidden("User is disabled".to_string()));
+    }
+
+    let token = state.user_service.create_session(user.id).await?;
+    Ok(format!("SID={0}\nLSID={0}\nAuth={0}\n", token))
+}
+
+/// An edit token. Requests are authenticated by their header rather than a cookie, so
+/// the token clients send back with edits isn't checked.
+pub async fn token() -> String {
+    boke_core::auth::generate_token()
+}
+
+pub async fn user_info(CurrentUser(user): CurrentUser) -> Json<Value> {
+    Json(user_json(&user))
+}
+
+fn user_json(user: &User) -> Value {
+    json!({
+        "userId": user.id.to_string(),
+        "userName": user.username,
+        "userProfileId": user.id.to_string(),
+        "userEmail": "",
+    })
+}
+
+// Subscriptions and labels
+
+pub async fn subscription_list(UserState(state): UserState) -> Result<Json<Value>, ApiError> {
+    let feeds = state.feed_service.get_feeds().await?;
+    let folders = state.folder_service.get_folders().await?;
+    let subscriptions: Vec<Value> = feeds
+        .iter()
+        .map(|feed| {
+            let categories: Vec<Value> = folders
+                .iter()
+                .filter(|folder| feed.folder_id == Some(folder.id))
+                .map(|folder| {
+                    let path = folder_path(&folders, folder);
+                    json!({ "id": label_stream(&path), "label": path })
+                })
+                .collect();
+            json!({
+                "id": feed_stream(feed.id),
+                "title": feed.title,
+                "categories": categories,
+                "url": feed.feed_url,
+                "htmlUrl": feed.site_url.as_deref().unwrap_or_default(),
+                "iconUrl": feed.favicon_url.as_deref().unwrap_or_default(),
+            })
+        })
+        .collect();
+    Ok(Json(json!({ "subscriptions": subscriptions })))
+}
+
+/// Subscribe (`ac=subscribe`), unsubscribe, or rename and move (`ac=edit`) the feeds in
+/// `s`. A title in `t` renames; a label in `a` moves the feed to that folder, creating it
+/// if needed, and a label in `r` alone moves it out of its folder.
+pub async fn edit_subscription(
+    UserState(state): UserState,
+    params: ReaderParams,
+) -> Result<&'static str, ApiError> {
+    let action = params.get("ac").unwrap_or("edit");
+    let add_label = params.get("a").map(normalize_stream);
+    let remove_label = params.get("r").map(normalize_stream);
+    for stream in params.get_all("s") {
+        let feed_id = match action {
+            "subscribe" => {
+                let url = stream.strip_prefix(FEED_PREFIX).unwrap_or(stream);
+                state.feed_service.add_feed(url).await?.id
+            }
+            "unsubscribe" => {
+                let feed_id = find_feed(&state, stream).await?;
+                state.feed_service.remove_feed(feed_id).await?;
+                continue;
+            }
+            "edit" => find_feed(&state, stream).await?,
+            _ => {
+                return Err(ApiError::BadRequest(format!(
+                    "Unknown subscription action {}",
+                    action
+                )));
+            }
+        };
+
+        if let Some(title) = params.get("t") {
+            let update = FeedUpdate {
+                title: Some(title.to_string()),
+                ..Default::default()
+            };
+            state.feed_service.update_feed(feed_id, &update).await?;
+        }
+        if let Some(label) = add_label.as_deref().and_then(label_name) {
+            let folder_id = find_or_create_folder(&state, label).await?;
+            state
+                .folder_service
+                .move_feed_to_folder(feed_id, Some(folder_id))
+                .await?;
+        } else if remove_label.as_deref().and_then(label_name).is_some() {
+            state
+                .folder_service
+                .move_feed_to_folder(feed_id, None)
+                .await?;
+        }
+    }
+    Ok("OK")
+}
+
+pub async fn quick_add(
+    UserState(state): UserState,
+    params: ReaderParams,
+) -> Result<Json<Value>, ApiError> {
+    let url = params
+        .get("quickadd")
+        .ok_or_else(|| ApiError::BadRequest("quickadd is required".to_string()))?;
+    let url = url.strip_prefix(FEED_PREFIX).unwrap_or(url);
+    let feed = state.feed_service.add_feed(url).await?;
+    Ok(Json(json!({
+        "numResults": 1,
+        "query": url,
+        "streamId": feed_stream(feed.id),
+        "streamName": feed.title,
+    })))
+}
+
+pub async fn tag_list(UserState(state): UserState) -> Result<Json<Value>, ApiError> {
+    let folders = state.folder_service.get_folders().await?;
+    let mut tags = vec![json!({ "id": STARRED })];
+    tags.extend(folders.iter().map(
+        |folder| json!({ "id": label_stream(&folder_path(&folders, folder)), "type": "folder" }),
+    ));
+    Ok(Json(json!({ "tags": tags })))
+}
+
+pub async fn unread_count(UserState(state): UserState) -> Result<Json<Value>, ApiError> {
+    let feeds = state.feed_service.get_feeds().await?;
+    let folders = state.folder_service.get_folders().await?;
+    let total: i64 = feeds.iter().map(|f| f.unread_count).sum();
+
+    let mut counts = vec![unread_count_json(READING_LIST, total)];
+    counts.extend(
+        folders
+            .iter()
+            .map(|f| unread_count_json(&label_stream(&folder_path(&folders, f)), f.unread_count)),
+    );
+    counts.extend(
+        feeds
+            .iter()
+            .map(|f| unread_count_json(&feed_stream(f.id), f.unread_count)),
+    );
+    Ok(Json(json!({ "max": total, "unreadcounts": counts })))
+}
+
+fn unread_count_json(id: &str, count: i64) -> Value {
+    json!({ "id": id, "count": count, "newestItemTimestampUsec": "0" })
+}
+
+// Streams and items
+
+/// A page of a stream's items. The stream comes from the path or from `s`.
+pub async fn stream_contents(
+    UserState(state): UserState,
+    stream: Option<Path<String>>,
+    params: ReaderParams,
+) -> Result<Json<Value>, ApiError> {
+    let stream = match &stream {
+        Some(Path(stream)) => stream.as_str(),
+        None => params.get("s").unwrap_or(READING_LIST),
+    };
+    let mut query = stream_query(&state, stream, &params).await?;
+    query.limit = page_size(&params, MAX_CONTENTS_PAGE_SIZE)?;
+
+    let articles = state.article_service.get_articles(query.clone()).await?;
+    let mut response = json!({
+        "id": stream,
+        "updated": Utc::now().timestamp(),
+        "items": articles.iter().map(item_json).collect::<Vec<_>>(),
+    });
+    if articles.len() as i64 == query.limit {
+        response["continuation"] = json!((query.offset + query.limit).to_string());
+    }
+    Ok(Json(response))
+}
+
+/// A page of a stream's item ids, for clients that fetch contents separately.
+pub async fn stream_item_ids(
+    UserState(state): UserState,
+    params: ReaderParams,
+) -> Result<Json<Value>, ApiError> {
+    let stream = params.get("s").unwrap_or(READING_LIST);
+    let query = stream_query(&state, stream, &params).await?;
+    let limit = page_size(&params, MAX_IDS_PAGE_SIZE)?;
+
+    let ids = state.article_service.get_article_ids(&query).await?;
+    let page: Vec<Value> = ids
+        .iter()
+        .skip(query.offset as usize)
+        .take(limit as usize)
+        .map(|id| json!({ "id": id.to_string() }))
+        .collect();
+    let mut response = json!({ "itemRefs": page });
+    if (ids.len() as i64) > query.offset + limit {
+        response["continuation"] = json!((query.offset + limit).to_string());
+    }
+    Ok(Json(response))
+}
+
+/// The items listed in `i`.
+pub async fn item_contents(
+    UserState(state): UserState,
+    params: ReaderParams,
+) -> Result<Json<Value>, ApiError> {
+    let ids = item_ids(&params)?;
+    let query = ArticleQuery {
+        limit: ids.len() as i64,
+        ids: Some(ids),
+        ..Default::default()
+    };
+    let articles = state.article_service.get_articles(query).await?;
+    Ok(Json(json!({
+        "id": READING_LIST,
+        "updated": Utc::now().timestamp(),
+        "items": articles.iter().map(item_json).collect::<Vec<_>>(),
+    })))
+}
+
+/// Add (`a`) or remove (`r`) the read and starred states of the items in `i`.
+pub async fn edit_tag(
+    UserState(state): UserState,
+    params: ReaderParams,
+) -> Result<&'static str, ApiError> {
+    let ids = item_ids(&params)?;
+    let articles = &state.article_service;
+    for (tags, add) in [("a", true), ("r", false)] {
+        for tag in params.get_all(tags).map(normalize_stream) {
+            for &id in &ids {
+                match tag.as_str() {
+                    READ => articles.set_read(id, add).await?,
+                    KEPT_UNREAD => articles.set_read(id, !add).await?,
+                    STARRED => articles.set_favorite(id, add).await?,
+                    _ => {}
+                }
+            }
+        }
+    }
+    Ok("OK")
+}
+
+/// Mark a stream read, up to the `ts` timestamp in microseconds if given.
+pub async fn mark_all_as_read(
+    UserState(state): UserState,
+    params: ReaderParams,
+) -> Result<&'static str, ApiError> {
+    let stream = params
+        .get("s")
+        .ok_or_else(|| ApiError::BadRequest("s is required".to_string()))?;
+    let mut query = stream_query(&state, stream, &ReaderParams(Vec::new())).await?;
+    if let Some(usec) = params.get_i64("ts")? {
+        query.published_before = Some(timestamp(usec / 1_000_000)?);
+    }
+    state.article_service.mark_articles_read(&query).await?;
+    Ok("OK")
+}
+
+/// The articles of a stream, filtered by the common stream parameters: `xt` to exclude
+/// read items, `it` to include only starred ones, `ot` and `nt` for a time range in
+/// seconds, `r=o` for oldest first and `c` to continue from an earlier page.
+async fn stream_query(
+    state: &AppState,
+    stream: &str,
+    params: &ReaderParams,
+) -> Result<ArticleQuery, ApiError> {
+    let stream = normalize_stream(stream);
+    let mut query = ArticleQuery::default();
+    if stream == STARRED {
+        query.favorites_only = true;
+    } else if let Some(path) = label_name(&stream) {
+        let folders = state.folder_service.get_folders().await?;
+        let folder = find_folder(&folders, path).ok_or(ApiError::NotFound)?;
+        query.folder_id = Some(FolderId::Folder(folder.id));
+    } else if stream.starts_with(FEED_PREFIX) {
+        query.feed_id = Some(find_feed(state, &stream).await?);
+    } else if stream != READING_LIST {
+        return Err(ApiError::BadRequest(format!(
+            "Unsupported stream {}",
+            stream
+        )));
+    }
+
+    if params
+        .get_all("xt")
+        .map(normalize_stream)
+        .any(|s| s == READ)
+    {
+        query.unread_only = true;
+    }
+    if params
+        .get_all("it")
+        .map(normalize_stream)
+        .any(|s| s == STARRED)
+    {
+        query.favorites_only = true;
+    }
+    if let Some(secs) = params.get_i64("ot")? {
+        query.published_after = Some(timestamp(secs)?);
+    }
+    if let Some(secs) = params.get_i64("nt")? {
+        query.published_before = Some(timestamp(secs)?);
+    }
+    query.oldest_first = params.get("r") == Some("o");
+    query.offset = params.get_i64("c")?.unwrap_or(0).max(0);
+    Ok(query)
+}
+
+fn page_size(params: &ReaderParams, max: i64) -> Result<i64, ApiError> {
+    Ok(params
+        .get_i64("n")?
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, max))
+}
+
+fn item_json(article: &Article) -> Value {
+    let published = article.published_at.or(article.created_at);
+    let published_secs = published.map_or(0, |t| t.timestamp());
+    let crawled_msec = article
+        .created_at
+        .or(published)
+        .map_or(0, |t| t.timestamp_millis());
+    let link = article.link.as_deref().unwrap_or_default();
+    let html = article.content.as_ref().or(article.summary.as_ref());
+
+    let mut categories = vec![READING_LIST];
+    if article.is_read {
+        categories.push(READ);
+    }
+    if article.is_favorite {
+        categories.push(STARRED);
+    }
+
+    json!({
+        "id": format!("{}{:016x}", ITEM_PREFIX, article.id),
+        "crawlTimeMsec": crawled_msec.to_string(),
+        "timestampUsec": published.map_or(0, |t| t.timestamp_micros()).to_string(),
+        "published": published_secs,
+        "updated": published_secs,
+        "title": article.title,
+        "canonical": [{ "href": link }],
+        "alternate": [{ "href": link, "type": "text/html" }],
+        "summary": { "direction": "ltr", "content": html.map(String::as_str).unwrap_or_default() },
+        "author": article.author.as_deref().unwrap_or_default(),
+        "origin": {
+            "streamId": feed_stream(article.feed_id),
+            "title": article.feed_title.as_deref().unwrap_or_default(),
+        },
+        "categories": categories,
+    })
+}
+
+/// The article ids in `i`, in either the long or the short form.
+fn item_ids(params: &ReaderParams) -> Result<Vec<i64>, ApiError> {
+    params
+        .get_all("i")
+        .map(|id| {
+            let id = id.trim();
+            match id.strip_prefix(ITEM_PREFIX) {
+                Some(hex) => u64::from_str_radix(hex, 16).map(|id| id as i64).ok(),
+                None => id.parse().ok(),
+            }
+            .ok_or_else(|| ApiError::BadRequest(format!("Invalid item id {}", id)))
+        })
+        .collect()
+}
+
+/// The feed a `feed/` stream names, by id or by URL.
+async fn find_feed(state: &AppState, stream: &str) -> Result<i64, ApiError> {
+    let feed = stream.strip_prefix(FEED_PREFIX).unwrap_or(stream);
+    if let Ok(id) = feed.parse() {
+        return Ok(id);
+    }
+    let feeds = state.feed_service.get_feeds().await?;
+    feeds
+        .iter()
+        .find(|f| f.feed_url == feed)
+        .map(|f| f.id)
+        .ok_or(ApiError::NotFound)
+}
+
+/// The folder at a label's path, creating any missing folders along the way.
+async fn find_or_create_folder(state: &AppState, path: &str) -> Result<i64, ApiError> {
+    let mut folders = state.folder_service.get_folders().await?;
+    if let Some(folder) = find_folder(&folders, path) {
+        return Ok(folder.id);
+    }
+
+    let mut parent_id = None;
+    for name in path
+        .split('/')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+    {
+        let existing = folders
+            .iter()
+            .find(|f| f.parent_id == parent_id && f.name == name);
+        let id = match existing {
+            Some(folder) => folder.id,
+            None => {
+                let folder = state.folder_service.create_folder(name, parent_id).await?;
+                let id = folder.id;
+                folders.push(folder);
+                id
+            }
+        };
+        parent_id = Some(id);
+    }
+    parent_id.ok_or_else(|| ApiError::BadRequest(format!("Invalid label {}", path)))
+}
+
+/// A folder's names from the top level down, joined with `/`. Folder names are only
+/// unique among siblings, so labels use the whole path.
+fn folder_path(folders: &[Folder], folder: &Folder) -> String {
+    let mut names = vec![folder.name.as_str()];
+    let mut parent_id = folder.parent_id;
+    while let Some(parent) = parent_id.and_then(|id| folders.iter().find(|f| f.id == id)) {
+        names.push(&parent.name);
+        parent_id = parent.parent_id;
+    }
+    names.reverse();
+    names.join("/")
+}
+
+fn find_folder<'a>(folders: &'a [Folder], path: &str) -> Option<&'a Folder> {
+    folders.iter().find(|f| folder_path(folders, f) == path)
+}
+
+/// A stream id with the user written as `-`, e.g. `user/1/label/News` as
+/// `user/-/label/News`.
+fn normalize_stream(stream: &str) -> String {
+    match stream
+        .strip_prefix("user/")
+        .and_then(|rest| rest.split_once('/'))
+    {
+        Some((_, rest)) => format!("user/-/{}", rest),
+        None => stream.to_string(),
+    }
+}
+
+fn label_name(stream: &str) -> Option<&str> {
+    stream
+        .strip_prefix(LABEL_PREFIX)
+        .filter(|name| !name.is_empty())
+}
+
+fn label_stream(name: &str) -> String {
+    format!("{}{}", LABEL_PREFIX, name)
+}
+
+fn feed_stream(id: i64) -> String {
+    format!("{}{}", FEED_PREFIX, id)
+}
+
+fn timestamp(secs: i64) -> Result<DateTime<Utc>, ApiError> {
+    DateTime::from_timestamp(secs, 0)
+        .ok_or_else(|| ApiError::BadRequest(format!("Invalid timestamp {}", secs)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::{PASSWORD, seed, send, test_app};
+    use axum::Router;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode, header};
+    use serde_json::Value;
+
+    fn login(username: &str, password: &str) -> Request<Body> {
+        let form: String = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("Email", username)
+            .append_pair("Passwd", password)
+            .finish();
+        Request::post("/accounts/ClientLogin")
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(form))
+            .unwrap()
+    }
+
+    fn reader(method: &str, path: &str, token: &str, form: &str) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri(format!("/reader/api/0{}", path))
+            .header(header::AUTHORIZATION, format!("GoogleLogin auth={}", token))
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(form.to_string()))
+            .unwrap()
+    }
+
+    /// Log in as `admin`, returning the `Auth` token.
+    async fn log_in(app: &Router) -> String {
+        let (status, body) = send(app, login("admin", PASSWORD)).await;
+        assert_eq!(status, StatusCode::OK);
+        body.lines()
+            .find_map(|line| line.strip_prefix("Auth="))
+            .unwrap()
+            .to_string()
+    }
+
+    async fn get_json(app: &Router, path: &str, token: &str) -> Value {
+        let (status, body) = send(app, reader("GET", path, token, "")).await;
+        assert_eq!(status, StatusCode::OK);
+        serde_json::from_str(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn clients_log_in_read_and_mark_items() {
+        let (app, state) = test_app().await;
+        let (feed_id, article_id) = seed(&state).await;
+
+        let token = log_in(&app).await;
+
+        let (status, body) = send(&app, reader("GET", "/subscription/list", &token, "")).await;
+        assert_eq!(status, StatusCode::OK);
+        let list: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(
+            list["subscriptions"][0]["id"],
+            format!("feed/{}", feed_id).as_str()
+        );
+
+        let (status, body) = send(&app, reader("GET", "/stream/contents", &token, "")).await;
+        assert_eq!(status, StatusCode::OK);
+        let stream: Value = serde_json::from_str(&body).unwrap();
+        let item = &stream["items"][0];
+        assert_eq!(item["title"], "Hello");
+        let item_id = item["id"].as_str().unwrap();
+        assert_eq!(
+            item_id,
+            format!("tag:google.com,2005:reader/item/{:016x}", article_id)
+        );
+
+        let form = format!(
+            "i={}&a=user/-/state/com.google/read",
+            url::form_urlencoded::byte_serialize(item_id.as_bytes()).collect::<String>()
+        );
+        let (status, body) = send(&app, reader("POST", "/edit-tag", &token, &form)).await;
+        assert_eq!((status, body.as_str()), (StatusCode::OK, "OK"));
+        let article = state
+            .article_service
+            .get_article(article_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(article.is_read);
+    }
+
+    #[tokio::test]
+    async fn bad_logins_and_tokens_are_unauthorized() {
+        let (app, _state) = test_app().await;
+
+        let (status, _) = send(&app, login("admin", "wrong")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, _) = send(&app, login("nobody", PASSWORD)).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (status, _) = send(&app, reader("GET", "/user-info", "bogus", "")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let anonymous = Request::get("/reader/api/0/user-info")
+            .body(Body::empty())
+            .unwrap();
+        let (status, _) = send(&app, anonymous).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn labels_name_folders_by_their_path() {
+        let (app, state) = test_app().await;
+        let (feed_id, article_id) = seed(&state).await;
+        let folders = &state.folder_service;
+        let tech = folders.create_folder("Tech", None).await.unwrap();
+        let games = folders.create_folder("Games", None).await.unwrap();
+        let tech_rust = folders.create_folder("Rust", Some(tech.id)).await.unwrap();
+        let games_rust = folders.create_folder("Rust", Some(games.id)).await.unwrap();
+        folders
+            .move_feed_to_folder(feed_id, Some(games_rust.id))
+            .await
+            .unwrap();
+        let token = log_in(&app).await;
+
+        let list = get_json(&app, "/subscription/list", &token).await;
+        let category = &list["subscriptions"][0]["categories"][0];
+        assert_eq!(category["id"], "user/-/label/Games/Rust");
+        assert_eq!(category["label"], "Games/Rust");
+        let tags = get_json(&app, "/tag/list", &token).await;
+        let tags: Vec<&str> = tags["tags"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|t| t["id"].as_str())
+            .collect();
+        assert!(tags.contains(&"user/-/label/Tech/Rust"));
+        assert!(tags.contains(&"user/-/label/Games/Rust"));
+
+        let items = |stream: Value| stream["items"].as_array().unwrap().len();
+        let path = "/stream/contents/user/-/label/Games/Rust";
+        assert_eq!(items(get_json(&app, path, &token).await), 1);
+        let path = "/stream/contents/user/-/label/Tech/Rust";
+        assert_eq!(items(get_json(&app, path, &token).await), 0);
+        let path = "/stream/contents/user/-/label/Rust";
+        let (status, _) = send(&app, reader("GET", path, &token, "")).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        // Moving picks the folder at the path, and creates missing ones below their parent
+        let form = format!("ac=edit&s=feed/{}&a=user/-/label/Tech/Rust", feed_id);
+        let (status, _) = send(&app, reader("POST", "/subscription/edit", &token, &form)).await;
+        assert_eq!(status, StatusCode::OK);
+        let feeds = state.feed_service.get_feeds().await.unwrap();
+        assert_eq!(feeds[0].folder_id, Some(tech_rust.id));
+        let form = format!("ac=edit&s=feed/{}&a=user/-/label/Tech/Go", feed_id);
+        send(&app, reader("POST", "/subscription/edit", &token, &form)).await;
+        let go = folders
+            .get_folders()
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|f| f.name == "Go")
+            .unwrap();
+        assert_eq!(go.parent_id, Some(tech.id));
+        let path = "/stream/contents/user/-/label/Tech/Go";
+        let stream = get_json(&app, path, &token).await;
+        assert_eq!(
+            stream["items"][0]["id"],
+            format!("tag:google.com,2005:reader/item/{:016x}", article_id)
+        );
+    }
+}
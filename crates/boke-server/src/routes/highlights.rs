@@ -0,0 +1,110 @@
+use crate::auth::UserState;
+use crate::error::ApiError;
+use axum::{
+    extract::{Path, Query},
+    http::{StatusCode, header},
+    response::{IntoResponse, Json},
+};
+use boke_core::SearchQuery;
+use boke_core::models::{Highlight, HighlightQuery, NewHighlight, UpdateHighlight};
+use serde::Deserialize;
+
+// Highlight handlers
+
+#[derive(Deserialize)]
+pub struct GetHighlightsQuery {
+    q: Option<String>,
+    article_id: Option<i64>,
+    label_id: Option<i64>,
+    offset: Option<i64>,
+    limit: Option<i64>,
+}
+
+pub async fn get_highlights(
+    UserState(state): UserState,
+    Query(params): Query<GetHighlightsQuery>,
+) -> Result<Json<Vec<Highlight>>, ApiError> {
+    let query = HighlightQuery {
+        article_id: params.article_id,
+        label_id: params.label_id,
+        search: params
+            .q
+            .as_deref()
+            .map(SearchQuery::parse)
+            .filter(|q| !q.is_empty()),
+        offset: params.offset.unwrap_or(0),
+        limit: Some(params.limit.unwrap_or(50)),
+    };
+    let highlights = state.highlight_service.get_highlights(&query).await?;
+    Ok(Json(highlights))
+}
+
+pub async fn get_highlight(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+) -> Result<Json<Highlight>, ApiError> {
+    let highlight = state
+        .highlight_service
+        .get_highlight(id)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    Ok(Json(highlight))
+}
+
+pub async fn create_highlight(
+    UserState(state): UserState,
+    Json(req): Json<NewHighlight>,
+) -> Result<Json<Highlight>, ApiError> {
+    if req.quote.trim().is_empty() {
+        return Err(ApiError::BadRequest("Quote must not be empty".to_string()));
+    }
+    let highlight = state.highlight_service.create_highlight(&req).await?;
+    Ok(Json(highlight))
+}
+
+pub async fn update_highlight(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+    Json(req): Json<UpdateHighlight>,
+) -> Result<StatusCode, ApiError> {
+    state.highlight_service.update_highlight(id, &req).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn delete_highlight(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    state.highlight_service.delete_highlight(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn get_article_highlights(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<Highlight>>, ApiError> {
+    let highlights = state.highlight_service.get_article_highlights(id).await?;
+    Ok(Json(highlights))
+}
+
+pub async fn export_article_highlights(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, ApiError> {
+    let markdown = state.highlight_service.export_article(id).await?;
+    Ok((
+        [(header::CONTENT_TYPE, "text/markdown; charset=utf-8")],
+        markdown,
+    ))
+}
+
+pub async fn export_label_highlights(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, ApiError> {
+    let markdown = state.highlight_service.export_label(id).await?;
+    Ok((
+        [(header::CONTENT_TYPE, "text/markdown; charset=utf-8")],
+        markdown,
+    ))
+}
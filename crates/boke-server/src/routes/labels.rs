@@ -0,0 +1,91 @@
+use crate::auth::UserState;
+use crate::error::ApiError;
+use axum::{extract::Path, http::StatusCode, response::Json};
+use boke_core::models::{Label, NewLabel};
+use serde::Deserialize;
+
+// Label handlers
+
+pub async fn get_labels(UserState(state): UserState) -> Result<Json<Vec<Label>>, ApiError> {
+    let labels = state.label_service.get_labels().await?;
+    Ok(Json(labels))
+}
+
+pub async fn create_label(
+    UserState(state): UserState,
+    Json(req): Json<NewLabel>,
+) -> Result<Json<Label>, ApiError> {
+    if req.name.trim().is_empty() {
+        return Err(ApiError::BadRequest("Name must not be empty".to_string()));
+    }
+    let label = state.label_service.create_label(&req).await?;
+    Ok(Json(label))
+}
+
+pub async fn update_label(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+    Json(req): Json<NewLabel>,
+) -> Result<StatusCode, ApiError> {
+    if req.name.trim().is_empty() {
+        return Err(ApiError::BadRequest("Name must not be empty".to_string()));
+    }
+    state.label_service.update_label(id, &req).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn delete_label(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    state.label_service.delete_label(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn add_article_label(
+    UserState(state): UserState,
+    Path((article_id, label_id)): Path<(i64, i64)>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .label_service
+        .add_label(label_id, &[article_id])
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn remove_article_label(
+    UserState(state): UserState,
+    Path((article_id, label_id)): Path<(i64, i64)>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .label_service
+        .remove_label(label_id, &[article_id])
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Deserialize)]
+pub struct LabelArticlesRequest {
+    article_ids: Vec<i64>,
+}
+
+pub async fn add_label_to_articles(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+    Json(req): Json<LabelArticlesRequest>,
+) -> Result<StatusCode, ApiError> {
+    state.label_service.add_label(id, &req.article_ids).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn remove_label_from_articles(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+    Json(req): Json<LabelArticlesRequest>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .label_service
+        .remove_label(id, &req.article_ids)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
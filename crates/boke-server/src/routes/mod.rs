@@ -1,3 +1,14 @@
 pub mod articles;
+pub mod auth;
+pub mod events;
 pub mod feeds;
+pub mod fever;
 pub mod folders;
+pub mod greader;
+pub mod highlights;
+pub mod labels;
+pub mod nextcloud;
+pub mod smart_folders;
+pub mod stats;
+pub mod users;
+pub mod webhooks;
@@ -0,0 +1,685 @@
+//! The Nextcloud News API v1-3, under `/index.php/apps/news/api/v1-3/`.
+//!
+//! Clients authenticate every request with HTTP Basic auth using a Boke username and
+//! password. Nextcloud folders are flat, so a folder's items include those of its
+//! sub-folders. Item ids are article ids; `offset` and `newestItemId` compare against them.
+//! An item's `guidHash` is the MD5 hex of its guid, which API v1-2 stars items by.
+
+use crate::AppState;
+use crate::auth::{CurrentUser, UserState};
+use crate::config::AuthMode;
+use crate::error::ApiError;
+use axum::{
+    extract::{Path, Query, Request, State},
+    http::{HeaderMap, StatusCode, header},
+    middleware::Next,
+    response::{Json, Response},
+};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use boke_core::DEFAULT_USER_ID;
+use boke_core::models::{
+    Article, ArticleQuery, ArticleSort, FeedUpdate, FeedWithMeta, Folder, FolderDeleteMode,
+    FolderId, User, guid_hash,
+};
+use chrono::DateTime;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+/// The News app release whose API this matches; clients check it to pick features.
+const NEWS_VERSION: &str = "18.0.0";
+
+/// Middleware that checks Basic auth credentials for the [`UserState`] extractor.
+pub async fn require_basic_auth(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let mut user = match basic_credentials(request.headers()) {
+        Some((username, password)) => match state.auth_mode {
+            AuthMode::Required => {
+                state
+                    .user_service
+                    .verify_login(&username, &password)
+                    .await?
+            }
+            AuthMode::None => state.user_service.get_user_by_name(&username).await?,
+        },
+        None => None,
+    };
+    if user.is_none() && state.auth_mode == AuthMode::None {
+        user = state.user_service.get_user(DEFAULT_USER_ID).await?;
+    }
+    let user = user.ok_or_else(|| ApiError::Unauthorized("Login required".to_string()))?;
+    if user.disabled {
+        return Err(ApiError::Forbidden("User is disabled".to_string()));
+    }
+    request.extensions_mut().insert(CurrentUser(user));
+    Ok(next.run(request).await)
+}
+
+/// The username and password from `Authorization: Basic <base64>`.
+fn basic_credentials(headers: &HeaderMap) -> Option<(String, String)> {
+    let value = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    let encoded = value.strip_prefix("Basic ")?.trim();
+    let decoded = String::from_utf8(BASE64.decode(encoded).ok()?).ok()?;
+    let (username, password) = decoded.split_once(':')?;
+    Some((username.to_string(), password.to_string()))
+}
+
+pub async fn version() -> Json<Value> {
+    Json(json!({ "version": NEWS_VERSION }))
+}
+
+pub async fn status() -> Json<Value> {
+    Json(json!({
+        "version": NEWS_VERSION,
+        "warnings": {
+            "improperlyConfiguredCron": false,
+            "incorrectDbCharset": false,
+        },
+    }))
+}
+
+pub async fn user(CurrentUser(user): CurrentUser) -> Json<Value> {
+    Json(user_json(&user))
+}
+
+fn user_json(user: &User) -> Value {
+    json!({
+        "userId": user.username,
+        "displayName": user.username,
+        "lastLoginTimestamp": 0,
+        "avatar": null,
+    })
+}
+
+// Folders
+
+pub async fn get_folders(UserState(state): UserState) -> Result<Json<Value>, ApiError> {
+    let folders = state.folder_service.get_folders().await?;
+    let folders: Vec<Value> = folders.iter().map(folder_json).collect();
+    Ok(Json(json!({ "folders": folders })))
+}
+
+#[derive(Deserialize)]
+pub struct FolderRequest {
+    name: String,
+}
+
+pub async fn create_folder(
+    UserState(state): UserState,
+    Json(req): Json<FolderRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let folder = state.folder_service.create_folder(&req.name, None).await?;
+    Ok(Json(json!({ "folders": [folder_json(&folder)] })))
+}
+
+pub async fn rename_folder(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+    Json(req): Json<FolderRequest>,
+) -> Result<StatusCode, ApiError> {
+    state.folder_service.rename_folder(id, &req.name).await?;
+    Ok(StatusCode::OK)
+}
+
+/// Delete a folder along with its feeds, as Nextcloud does.
+pub async fn delete_folder(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .folder_service
+        .delete_folder(id, FolderDeleteMode::Cascade)
+        .await?;
+    Ok(StatusCode::OK)
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarkReadRequest {
+    newest_item_id: i64,
+}
+
+pub async fn mark_folder_read(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+    Json(req): Json<MarkReadRequest>,
+) -> Result<StatusCode, ApiError> {
+    let query = ArticleQuery {
+        folder_id: Some(FolderId::Folder(id)),
+        ..up_to(req.newest_item_id)
+    };
+    state.article_service.mark_articles_read(&query).await?;
+    Ok(StatusCode::OK)
+}
+
+fn folder_json(folder: &Folder) -> Value {
+    json!({ "id": folder.id, "name": folder.name })
+}
+
+// Feeds
+
+pub async fn get_feeds(UserState(state): UserState) -> Result<Json<Value>, ApiError> {
+    let feeds = state.feed_service.get_feeds().await?;
+    let starred = ArticleQuery {
+        favorites_only: true,
+        ..Default::default()
+    };
+    let starred_count = state.article_service.count_articles(&starred).await?;
+    Ok(Json(json!({
+        "feeds": feeds.iter().map(feed_json).collect::<Vec<_>>(),
+        "starredCount": starred_count,
+        "newestItemId": newest_item_id(&state).await?,
+    })))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddFeedRequest {
+    url: String,
+    folder_id: Option<i64>,
+}
+
+pub async fn add_feed(
+    UserState(state): UserState,
+    Json(req): Json<AddFeedRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let mut feed = state.feed_service.add_feed(&req.url).await?;
+    if let Some(folder_id) = req.folder_id.filter(|&id| id > 0) {
+        state
+            .folder_service
+            .move_feed_to_folder(feed.id, Some(folder_id))
+            .await?;
+        feed.folder_id = Some(folder_id);
+    }
+    Ok(Json(json!({
+        "feeds": [feed_json(&feed)],
+        "newestItemId": newest_item_id(&state).await?,
+    })))
+}
+
+pub async fn delete_feed(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    state.feed_service.remove_feed(id).await?;
+    Ok(StatusCode::OK)
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveFeedRequest {
+    /// `null` or 0 for the top level.
+    folder_id: Option<i64>,
+}
+
+pub async fn move_feed(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+    Json(req): Json<MoveFeedRequest>,
+) -> Result<StatusCode, ApiError> {
+    let folder_id = req.folder_id.filter(|&id| id > 0);
+    state
+        .folder_service
+        .move_feed_to_folder(id, folder_id)
+        .await?;
+    Ok(StatusCode::OK)
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameFeedRequest {
+    feed_title: String,
+}
+
+pub async fn rename_feed(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+    Json(req): Json<RenameFeedRequest>,
+) -> Result<StatusCode, ApiError> {
+    let update = FeedUpdate {
+        title: Some(req.feed_title),
+        ..Default::default()
+    };
+    state.feed_service.update_feed(id, &update).await?;
+    Ok(StatusCode::OK)
+}
+
+pub async fn mark_feed_read(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+    Json(req): Json<MarkReadRequest>,
+) -> Result<StatusCode, ApiError> {
+    let query = ArticleQuery {
+        feed_id: Some(id),
+        ..up_to(req.newest_item_id)
+    };
+    state.article_service.mark_articles_read(&query).await?;
+    Ok(StatusCode::OK)
+}
+
+fn feed_json(feed: &FeedWithMeta) -> Value {
+    json!({
+        "id": feed.id,
+        "url": feed.feed_url,
+        "title": feed.title,
+        "faviconLink": feed.favicon_url,
+        "added": feed.created_at.map_or(0, |t| t.timestamp()),
+        "folderId": feed.folder_id,
+        "unreadCount": feed.unread_count,
+        "ordering": 0,
+        "link": feed.site_url,
+        "pinned": false,
+        "updateErrorCount": 0,
+        "lastUpdateError": null,
+    })
+}
+
+/// The id of the newest article, or 0 if there are none.
+async fn newest_item_id(state: &AppState) -> Result<i64, ApiError> {
+    let query = ArticleQuery {
+        sort: ArticleSort::Id,
+        limit: 1,
+        ..Default::default()
+    };
+    let newest = state.article_service.get_articles(query).await?;
+    Ok(newest.first().map_or(0, |a| a.id))
+}
+
+// Items
+
+/// Which items a listing covers, from Nextcloud's `type` and `id` parameters.
+fn select_items(kind: i64, id: i64) -> Result<ArticleQuery, ApiError> {
+    let query = ArticleQuery {
+        sort: ArticleSort::Id,
+        ..Default::default()
+    };
+    match kind {
+        0 => Ok(ArticleQuery {
+            feed_id: Some(id),
+            ..query
+        }),
+        1 => Ok(ArticleQuery {
+            folder_id: Some(FolderId::Folder(id)),
+            ..query
+        }),
+        2 => Ok(ArticleQuery {
+            favorites_only: true,
+            ..query
+        }),
+        3 => Ok(query),
+        _ => Err(ApiError::BadRequest(format!("Invalid type {}", kind))),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemsQuery {
+    /// -1 for every item.
+    batch_size: Option<i64>,
+    /// Continue after this item id, or start from the newest (or oldest) with 0.
+    offset: Option<i64>,
+    #[serde(rename = "type")]
+    kind: Option<i64>,
+    id: Option<i64>,
+    get_read: Option<bool>,
+    oldest_first: Option<bool>,
+}
+
+pub async fn get_items(
+    UserState(state): UserState,
+    Query(params): Query<ItemsQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let mut query = select_items(params.kind.unwrap_or(3), params.id.unwrap_or(0))?;
+    query.oldest_first = params.oldest_first.unwrap_or(false);
+    query.unread_only = !params.get_read.unwrap_or(true);
+    query.limit = match params.batch_size {
+        Some(size) if size > 0 => size,
+        _ => i64::MAX,
+    };
+    if let Some(offset) = params.offset.filter(|&offset| offset > 0) {
+        if query.oldest_first {
+            query.after_id = Some(offset);
+        } else {
+            query.before_id = Some(offset);
+        }
+    }
+
+    let articles = state.article_service.get_articles(query).await?;
+    Ok(Json(
+        json!({ "items": articles.iter().map(item_json).collect::<Vec<_>>() }),
+    ))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatedItemsQuery {
+    /// Unix time in seconds, or microseconds as newer News releases send.
+    last_modified: i64,
+    #[serde(rename = "type")]
+    kind: Option<i64>,
+    id: Option<i64>,
+}
+
+/// Items added or read or starred since `lastModified`.
+pub async fn get_updated_items(
+    UserState(state): UserState,
+    Query(params): Query<UpdatedItemsQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let secs = if params.last_modified > 10_000_000_000 {
+        params.last_modified / 1_000_000
+    } else {
+        params.last_modified
+    };
+    let mut query = select_items(params.kind.unwrap_or(3), params.id.unwrap_or(0))?;
+    query.oldest_first = true;
+    query.limit = i64::MAX;
+    query.modified_after = Some(
+        DateTime::from_timestamp(secs, 0)
+            .ok_or_else(|| ApiError::BadRequest("Invalid lastModified".to_string()))?,
+    );
+
+    let articles = state.article_service.get_articles(query).await?;
+    Ok(Json(
+        json!({ "items": articles.iter().map(item_json).collect::<Vec<_>>() }),
+    ))
+}
+
+pub async fn mark_item_read(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    state.article_service.set_read(id, true).await?;
+    Ok(StatusCode::OK)
+}
+
+pub async fn mark_item_unread(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    state.article_service.set_read(id, false).await?;
+    Ok(StatusCode::OK)
+}
+
+pub async fn star_item(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    state.article_service.set_favorite(id, true).await?;
+    Ok(StatusCode::OK)
+}
+
+pub async fn unstar_item(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    state.article_service.set_favorite(id, false).await?;
+    Ok(StatusCode::OK)
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultipleItemsRequest {
+    /// `items` in API v1-2.
+    #[serde(alias = "items")]
+    item_ids: Vec<i64>,
+}
+
+pub async fn mark_items_read(
+    UserState(state): UserState,
+    Json(req): Json<MultipleItemsRequest>,
+) -> Result<StatusCode, ApiError> {
+    for id in req.item_ids {
+        state.article_service.set_read(id, true).await?;
+    }
+    Ok(StatusCode::OK)
+}
+
+pub async fn mark_items_unread(
+    UserState(state): UserState,
+    Json(req): Json<MultipleItemsRequest>,
+) -> Result<StatusCode, ApiError> {
+    for id in req.item_ids {
+        state.article_service.set_read(id, false).await?;
+    }
+    Ok(StatusCode::OK)
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StarItemsRequest {
+    #[serde(default)]
+    item_ids: Vec<i64>,
+    /// API v1-2 names the items by feed and guid hash instead.
+    #[serde(default)]
+    items: Vec<GuidHashItem>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GuidHashItem {
+    feed_id: i64,
+    guid_hash: String,
+}
+
+pub async fn star_items(
+    UserState(state): UserState,
+    Json(req): Json<StarItemsRequest>,
+) -> Result<StatusCode, ApiError> {
+    for id in star_request_ids(&state, req).await? {
+        state.article_service.set_favorite(id, true).await?;
+    }
+    Ok(StatusCode::OK)
+}
+
+pub async fn unstar_items(
+    UserState(state): UserState,
+    Json(req): Json<StarItemsRequest>,
+) -> Result<StatusCode, ApiError> {
+    for id in star_request_ids(&state, req).await? {
+        state.article_service.set_favorite(id, false).await?;
+    }
+    Ok(StatusCode::OK)
+}
+
+async fn star_request_ids(state: &AppState, req: StarItemsRequest) -> Result<Vec<i64>, ApiError> {
+    let mut ids = req.item_ids;
+    for item in &req.items {
+        ids.push(find_by_guid_hash(state, item.feed_id, &item.guid_hash).await?);
+    }
+    Ok(ids)
+}
+
+/// Star an item named by feed and guid hash, as API v1-2 does.
+pub async fn star_item_by_guid_hash(
+    UserState(state): UserState,
+    Path((feed_id, hash)): Path<(i64, String)>,
+) -> Result<StatusCode, ApiError> {
+    let id = find_by_guid_hash(&state, feed_id, &hash).await?;
+    state.article_service.set_favorite(id, true).await?;
+    Ok(StatusCode::OK)
+}
+
+pub async fn unstar_item_by_guid_hash(
+    UserState(state): UserState,
+    Path((feed_id, hash)): Path<(i64, String)>,
+) -> Result<StatusCode, ApiError> {
+    let id = find_by_guid_hash(&state, feed_id, &hash).await?;
+    state.article_service.set_favorite(id, false).await?;
+    Ok(StatusCode::OK)
+}
+
+async fn find_by_guid_hash(state: &AppState, feed_id: i64, hash: &str) -> Result<i64, ApiError> {
+    state
+        .article_service
+        .find_by_guid_hash(feed_id, hash)
+        .await?
+        .ok_or(ApiError::NotFound)
+}
+
+pub async fn mark_all_read(
+    UserState(state): UserState,
+    Json(req): Json<MarkReadRequest>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .article_service
+        .mark_articles_read(&up_to(req.newest_item_id))
+        .await?;
+    Ok(StatusCode::OK)
+}
+
+/// Articles up to and including `newest_item_id`, so that items that arrived after the
+/// client last synced stay unread.
+fn up_to(newest_item_id: i64) -> ArticleQuery {
+    ArticleQuery {
+        before_id: Some(newest_item_id.saturating_add(1)),
+        ..Default::default()
+    }
+}
+
+fn item_json(article: &Article) -> Value {
+    let last_modified = [article.created_at, article.read_at, article.favorited_at]
+        .into_iter()
+        .flatten()
+        .max();
+    let html = article.content.as_ref().or(article.summary.as_ref());
+    json!({
+        "id": article.id,
+        "guid": article.guid,
+        "guidHash": guid_hash(&article.guid),
+        "url": article.link,
+        "title": article.title,
+        "author": article.author,
+        "pubDate": article.published_at.or(article.created_at).map(|t| t.timestamp()),
+        "updatedDate": null,
+        "body": html.map(String::as_str).unwrap_or_default(),
+        "enclosureMime": article.enclosure_type,
+        "enclosureLink": article.enclosure_url,
+        "mediaThumbnail": article.image_url,
+        "mediaDescription": null,
+        "feedId": article.feed_id,
+        "unread": !article.is_read,
+        "starred": article.is_favorite,
+        "lastModified": last_modified.map_or(0, |t| t.timestamp()),
+        "rtl": false,
+        "fingerprint": null,
+        "contentHash": null,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BASE64;
+    use crate::testing::{PASSWORD, seed, send, test_app};
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode, header};
+    use base64::Engine;
+    use serde_json::{Value, json};
+
+    fn news(method: &str, path: &str, username: &str, password: &str) -> Request<Body> {
+        let credentials = BASE64.encode(format!("{}:{}", username, password));
+        Request::builder()
+            .method(method)
+            .uri(format!("/index.php/apps/news/api/v1-3{}", path))
+            .header(header::AUTHORIZATION, format!("Basic {}", credentials))
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    /// A request as `admin` with a JSON body.
+    fn news_json(method: &str, path: &str, body: Value) -> Request<Body> {
+        let (mut parts, _) = news(method, path, "admin", PASSWORD).into_parts();
+        parts.headers.insert(
+            header::CONTENT_TYPE,
+            header::HeaderValue::from_static("application/json"),
+        );
+        Request::from_parts(parts, Body::from(body.to_string()))
+    }
+
+    #[tokio::test]
+    async fn basic_auth_lists_feeds_and_marks_items() {
+        let (app, state) = test_app().await;
+        let (feed_id, article_id) = seed(&state).await;
+
+        let (status, body) = send(&app, news("GET", "/feeds", "admin", PASSWORD)).await;
+        assert_eq!(status, StatusCode::OK);
+        let feeds: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(feeds["feeds"][0]["id"], feed_id);
+        assert_eq!(feeds["feeds"][0]["unreadCount"], 1);
+        assert_eq!(feeds["newestItemId"], article_id);
+
+        let unread = "/items?type=3&id=0&getRead=false";
+        let (status, body) = send(&app, news("GET", unread, "admin", PASSWORD)).await;
+        assert_eq!(status, StatusCode::OK);
+        let items: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(items["items"][0]["id"], article_id);
+        assert_eq!(items["items"][0]["title"], "Hello");
+
+        let path = format!("/items/{}/read", article_id);
+        let (status, _) = send(&app, news("PUT", &path, "admin", PASSWORD)).await;
+        assert_eq!(status, StatusCode::OK);
+        let (_, body) = send(&app, news("GET", unread, "admin", PASSWORD)).await;
+        let items: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(items["items"].as_array().unwrap().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn bad_basic_credentials_are_unauthorized() {
+        let (app, state) = test_app().await;
+        seed(&state).await;
+
+        let (status, body) = send(&app, news("GET", "/feeds", "admin", "wrong")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert!(!body.contains("Example"));
+        let (status, _) = send(&app, news("GET", "/feeds", "nobody", PASSWORD)).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let anonymous = Request::get("/index.php/apps/news/api/v1-3/feeds")
+            .body(Body::empty())
+            .unwrap();
+        let (status, _) = send(&app, anonymous).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let garbled = Request::get("/index.php/apps/news/api/v1-3/feeds")
+            .header(header::AUTHORIZATION, "Basic not-base64")
+            .body(Body::empty())
+            .unwrap();
+        let (status, _) = send(&app, garbled).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn items_are_named_by_the_md5_of_their_guid() {
+        let (app, state) = test_app().await;
+        let (feed_id, article_id) = seed(&state).await;
+        let hash = "8ebe531b8406b6d8db8b041cfd9eb971";
+        let is_favorite = || async {
+            let article = state.article_service.get_article(article_id).await.unwrap();
+            article.unwrap().is_favorite
+        };
+
+        let (_, body) = send(&app, news("GET", "/items?type=3", "admin", PASSWORD)).await;
+        let items: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(items["items"][0]["guid"], "http://example.test/hello");
+        assert_eq!(items["items"][0]["guidHash"], hash);
+
+        let path = format!("/items/{}/{}/star", feed_id, hash);
+        let (status, _) = send(&app, news("PUT", &path, "admin", PASSWORD)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(is_favorite().await);
+        let unstar = json!({ "items": [{ "feedId": feed_id, "guidHash": hash }] });
+        let request = news_json("PUT", "/items/unstar/multiple", unstar);
+        assert_eq!(send(&app, request).await.0, StatusCode::OK);
+        assert!(!is_favorite().await);
+        let star = json!({ "itemIds": [article_id] });
+        let request = news_json("POST", "/items/star/multiple", star);
+        assert_eq!(send(&app, request).await.0, StatusCode::OK);
+        assert!(is_favorite().await);
+
+        let path = format!("/items/{}/{}/unstar", feed_id, "0".repeat(32));
+        let (status, _) = send(&app, news("PUT", &path, "admin", PASSWORD)).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(is_favorite().await);
+    }
+}
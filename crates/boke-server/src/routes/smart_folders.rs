@@ -0,0 +1,59 @@
+use crate::auth::UserState;
+use crate::error::ApiError;
+use axum::{extract::Path, http::StatusCode, response::Json};
+use boke_core::models::{NewSmartFolder, SmartFolder};
+
+// Smart folder handlers
+
+pub async fn get_smart_folders(
+    UserState(state): UserState,
+) -> Result<Json<Vec<SmartFolder>>, ApiError> {
+    let folders = state.smart_folder_service.get_smart_folders().await?;
+    Ok(Json(folders))
+}
+
+pub async fn get_smart_folder(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+) -> Result<Json<SmartFolder>, ApiError> {
+    let folder = state
+        .smart_folder_service
+        .get_smart_folder(id)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    Ok(Json(folder))
+}
+
+pub async fn create_smart_folder(
+    UserState(state): UserState,
+    Json(req): Json<NewSmartFolder>,
+) -> Result<Json<SmartFolder>, ApiError> {
+    if req.name.trim().is_empty() {
+        return Err(ApiError::BadRequest("Name must not be empty".to_string()));
+    }
+    let folder = state.smart_folder_service.create_smart_folder(&req).await?;
+    Ok(Json(folder))
+}
+
+pub async fn update_smart_folder(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+    Json(req): Json<NewSmartFolder>,
+) -> Result<StatusCode, ApiError> {
+    if req.name.trim().is_empty() {
+        return Err(ApiError::BadRequest("Name must not be empty".to_string()));
+    }
+    state
+        .smart_folder_service
+        .update_smart_folder(id, &req)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn delete_smart_folder(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    state.smart_folder_service.delete_smart_folder(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
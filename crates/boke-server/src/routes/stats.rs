@@ -0,0 +1,43 @@
+use crate::auth::UserState;
+use crate::error::ApiError;
+use axum::{
+    extract::{Path, Query},
+    response::Json,
+};
+use boke_core::models::{FeedHealth, ReadingStats};
+use serde::Deserialize;
+
+// Statistics handlers
+
+#[derive(Deserialize)]
+pub struct StatsQuery {
+    days: Option<i64>,
+}
+
+pub async fn get_reading_stats(
+    UserState(state): UserState,
+    Query(params): Query<StatsQuery>,
+) -> Result<Json<ReadingStats>, ApiError> {
+    let stats = state
+        .stats_service
+        .get_reading_stats(params.days.unwrap_or(30))
+        .await?;
+    Ok(Json(stats))
+}
+
+#[derive(Deserialize)]
+pub struct FeedStatsQuery {
+    dead_after_months: Option<u32>,
+}
+
+pub async fn get_feed_stats(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+    Query(params): Query<FeedStatsQuery>,
+) -> Result<Json<FeedHealth>, ApiError> {
+    let health = state
+        .stats_service
+        .get_feed_health(id, params.dead_after_months.unwrap_or(6))
+        .await?;
+    Ok(Json(health))
+}
@@ -0,0 +1,86 @@
+use crate::AppState;
+use crate::auth::AdminUser;
+use crate::error::ApiError;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use boke_core::models::{NewUser, User};
+use serde::Deserialize;
+
+// User administration handlers
+
+pub async fn get_users(
+    State(state): State<AppState>,
+    AdminUser(_): AdminUser,
+) -> Result<Json<Vec<User>>, ApiError> {
+    let users = state.user_service.get_users().await?;
+    Ok(Json(users))
+}
+
+pub async fn create_user(
+    State(state): State<AppState>,
+    AdminUser(_): AdminUser,
+    Json(req): Json<NewUser>,
+) -> Result<Json<User>, ApiError> {
+    let user = state.user_service.create_user(&req).await?;
+    Ok(Json(user))
+}
+
+#[derive(Deserialize)]
+pub struct SetDisabledRequest {
+    pub disabled: bool,
+}
+
+pub async fn set_user_disabled(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(id): Path<i64>,
+    Json(req): Json<SetDisabledRequest>,
+) -> Result<StatusCode, ApiError> {
+    if id == admin.id && req.disabled {
+        return Err(ApiError::BadRequest(
+            "You cannot disable your own account".to_string(),
+        ));
+    }
+    state
+        .user_service
+        .set_user_disabled(id, req.disabled)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Deserialize)]
+pub struct SetPasswordRequest {
+    /// A new password, or `None` to leave the user with API tokens only.
+    pub password: Option<String>,
+}
+
+/// Reset a user's password, signing them out everywhere.
+pub async fn set_user_password(
+    State(state): State<AppState>,
+    AdminUser(_): AdminUser,
+    Path(id): Path<i64>,
+    Json(req): Json<SetPasswordRequest>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .user_service
+        .set_password(id, req.password.as_deref())
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn delete_user(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    if id == admin.id {
+        return Err(ApiError::BadRequest(
+            "You cannot delete your own account".to_string(),
+        ));
+    }
+    state.user_service.delete_user(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,66 @@
+use crate::auth::UserState;
+use crate::error::ApiError;
+use axum::{extract::Path, http::StatusCode, response::Json};
+use boke_core::models::{CreatedWebhook, NewWebhook, Webhook, WebhookDelivery};
+
+// Webhook handlers
+
+pub async fn get_webhooks(UserState(state): UserState) -> Result<Json<Vec<Webhook>>, ApiError> {
+    let webhooks = state.webhook_service.get_webhooks().await?;
+    Ok(Json(webhooks))
+}
+
+pub async fn get_webhook(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+) -> Result<Json<Webhook>, ApiError> {
+    let webhook = state
+        .webhook_service
+        .get_webhook(id)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    Ok(Json(webhook))
+}
+
+/// Add a webhook; the response carries its signing secret, which is not shown again.
+pub async fn create_webhook(
+    UserState(state): UserState,
+    Json(req): Json<NewWebhook>,
+) -> Result<Json<CreatedWebhook>, ApiError> {
+    let webhook = state.webhook_service.create_webhook(&req).await?;
+    Ok(Json(webhook))
+}
+
+pub async fn update_webhook(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+    Json(req): Json<NewWebhook>,
+) -> Result<StatusCode, ApiError> {
+    state.webhook_service.update_webhook(id, &req).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn delete_webhook(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    state.webhook_service.delete_webhook(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn get_webhook_deliveries(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<WebhookDelivery>>, ApiError> {
+    let deliveries = state.webhook_service.get_deliveries(id).await?;
+    Ok(Json(deliveries))
+}
+
+/// Send a `ping` call now and report how it went.
+pub async fn test_webhook(
+    UserState(state): UserState,
+    Path(id): Path<i64>,
+) -> Result<Json<WebhookDelivery>, ApiError> {
+    let delivery = state.webhook_service.test_webhook(id).await?;
+    Ok(Json(delivery))
+}
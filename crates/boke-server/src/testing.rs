@@ -0,0 +1,101 @@
+//! Helpers for route tests, which run the full app against a fresh SQLite file.
+
+use crate::config::{AuthMode, Config};
+use crate::{AppState, app};
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode};
+use boke_core::DEFAULT_USER_ID;
+use boke_core::db::{Database, DatabasePool, InsertResult};
+use boke_core::models::{NewArticle, NewFeed};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tower::ServiceExt;
+
+/// Password given to the default `admin` user.
+pub const PASSWORD: &str = "correct horse";
+
+static NEXT_DB: AtomicUsize = AtomicUsize::new(0);
+
+/// The app and its state, with logins required and `admin` holding [`PASSWORD`].
+pub async fn test_app() -> (Router, AppState) {
+    let path = std::env::temp_dir().join(format!(
+        "boke_server_test_{}_{}.db",
+        std::process::id(),
+        NEXT_DB.fetch_add(1, Ordering::Relaxed)
+    ));
+    let _ = std::fs::remove_file(&path);
+    let database_url = format!("sqlite://{}", path.display());
+    let db = DatabasePool::from_url(&database_url).await.unwrap();
+    let state = AppState::new(db, AuthMode::Required, false);
+    state
+        .user_service
+        .set_password(DEFAULT_USER_ID, Some(PASSWORD))
+        .await
+        .unwrap();
+
+    let config = Config {
+        database_url,
+        bind_address: "127.0.0.1:0".to_string(),
+        static_dir: "./static".to_string(),
+        auth_mode: AuthMode::Required,
+        cors_origins: Vec::new(),
+        secure_cookies: false,
+    };
+    (app(state.clone(), &config).unwrap(), state)
+}
+
+/// Send a request through the app, returning the status and the body as text.
+pub async fn send(app: &Router, request: Request<Body>) -> (StatusCode, String) {
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+/// Subscribe the default user to a feed with one unread article, returning both ids.
+pub async fn seed(state: &AppState) -> (i64, i64) {
+    let feed_id = add_feed(state, "http://example.test/feed", None).await;
+    let article_id = add_article(state, feed_id, "http://example.test/hello", "Hello").await;
+    (feed_id, article_id)
+}
+
+/// Subscribe the default user to a feed titled "Example", with an optional favicon.
+pub async fn add_feed(state: &AppState, url: &str, favicon_url: Option<&str>) -> i64 {
+    state
+        .db
+        .insert_feed(&NewFeed {
+            title: "Example".to_string(),
+            folder_id: None,
+            feed_url: url.to_string(),
+            site_url: Some("http://example.test/".to_string()),
+            description: None,
+            language: None,
+            favicon_url: favicon_url.map(str::to_string),
+            last_build_date: None,
+        })
+        .await
+        .unwrap()
+}
+
+/// Store an unread article whose guid is its link.
+pub async fn add_article(state: &AppState, feed_id: i64, link: &str, title: &str) -> i64 {
+    let article = NewArticle {
+        feed_id,
+        guid: link.to_string(),
+        title: title.to_string(),
+        link: Some(link.to_string()),
+        author: None,
+        summary: None,
+        content: Some(format!("<p>{}</p>", title)),
+        image_url: None,
+        enclosure_url: None,
+        enclosure_type: None,
+        published_at: None,
+        normalized_link: boke_core::dedup::normalize_link(link),
+        categories: Vec::new(),
+    };
+    match state.db.insert_article(&article).await.unwrap() {
+        InsertResult::Inserted(id) => id,
+        InsertResult::Ignored => panic!("article was not inserted"),
+    }
+}
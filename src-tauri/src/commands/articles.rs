@@ -1,23 +1,64 @@
 //! Article-related Tauri commands.
 
-use boke_core::{Article, ArticleQuery, ArticleService, DatabasePool};
+use boke_core::{
+    Article, ArticleQuery, ArticleScope, ArticleService, ArticleSort, DatabasePool, FolderId,
+    HiddenArticle, SearchHit, SmartFolderService,
+};
+use chrono::{DateTime, Utc};
 use tauri::State;
 
 #[tauri::command]
 pub async fn get_articles(
     feed_id: Option<i64>,
+    folder_id: Option<FolderId>,
     offset: i64,
     limit: i64,
     unread_only: bool,
     favorites_only: bool,
+    collapse_duplicates: Option<bool>,
+    label_id: Option<i64>,
+    smart_folder_id: Option<i64>,
+    sort: Option<ArticleSort>,
+    oldest_first: Option<bool>,
+    published_after: Option<DateTime<Utc>>,
+    published_before: Option<DateTime<Utc>>,
+    created_after: Option<DateTime<Utc>>,
+    created_before: Option<DateTime<Utc>>,
+    author: Option<String>,
+    has_enclosure: Option<bool>,
+    min_content_length: Option<i64>,
     svc: State<'_, ArticleService<DatabasePool>>,
+    smart_folders: State<'_, SmartFolderService<DatabasePool>>,
 ) -> Result<Vec<Article>, String> {
+    let search = match smart_folder_id {
+        Some(id) => Some(
+            smart_folders
+                .search_query(id)
+                .await
+                .map_err(|e| e.to_string())?,
+        ),
+        None => None,
+    };
     let query = ArticleQuery {
         feed_id,
+        folder_id,
         offset,
         limit,
         unread_only,
         favorites_only,
+        collapse_duplicates: collapse_duplicates.unwrap_or(false),
+        label_id,
+        search,
+        sort: sort.unwrap_or_default(),
+        oldest_first: oldest_first.unwrap_or(false),
+        published_after,
+        published_before,
+        created_after,
+        created_before,
+        author: author.filter(|a| !a.trim().is_empty()),
+        has_enclosure: has_enclosure.unwrap_or(false),
+        min_content_length,
+        ..Default::default()
     };
     svc.get_articles(query).await.map_err(|e| e.to_string())
 }
@@ -43,18 +84,83 @@ pub async fn toggle_read(
 
 #[tauri::command]
 pub async fn mark_all_read(
+    scope: Option<ArticleScope>,
     feed_id: Option<i64>,
+    label_id: Option<i64>,
+    smart_folder_id: Option<i64>,
     svc: State<'_, ArticleService<DatabasePool>>,
+    smart_folders: State<'_, SmartFolderService<DatabasePool>>,
 ) -> Result<(), String> {
-    svc.mark_all_read(feed_id).await.map_err(|e| e.to_string())
+    let result = match (smart_folder_id, label_id) {
+        (Some(id), _) => smart_folders.mark_read(id).await,
+        (None, Some(label_id)) => {
+            let query = ArticleQuery {
+                label_id: Some(label_id),
+                ..Default::default()
+            };
+            svc.mark_articles_read(&query).await
+        }
+        (None, None) => svc.mark_all_read(scope_or_feed(scope, feed_id)).await,
+    };
+    result.map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub async fn mark_all_unread(
+    scope: Option<ArticleScope>,
+    feed_id: Option<i64>,
+    svc: State<'_, ArticleService<DatabasePool>>,
+) -> Result<(), String> {
+    svc.mark_all_unread(scope_or_feed(scope, feed_id))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Older callers pass a bare `feed_id` instead of a scope.
+fn scope_or_feed(scope: Option<ArticleScope>, feed_id: Option<i64>) -> ArticleScope {
+    match (scope, feed_id) {
+        (Some(scope), _) => scope,
+        (None, Some(feed_id)) => ArticleScope::Feed(feed_id),
+        (None, None) => ArticleScope::All,
+    }
+}
+
+#[tauri::command]
+pub async fn hide_article(
+    article_id: i64,
+    svc: State<'_, ArticleService<DatabasePool>>,
+) -> Result<HiddenArticle, String> {
+    svc.hide_article(article_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_article(
+    article_id: i64,
+    svc: State<'_, ArticleService<DatabasePool>>,
+) -> Result<HiddenArticle, String> {
+    svc.delete_article(article_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_hidden_articles(
     feed_id: Option<i64>,
     svc: State<'_, ArticleService<DatabasePool>>,
+) -> Result<Vec<HiddenArticle>, String> {
+    svc.get_hidden_articles(feed_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn unhide_article(
+    hidden_id: i64,
+    svc: State<'_, ArticleService<DatabasePool>>,
 ) -> Result<(), String> {
-    svc.mark_all_unread(feed_id)
+    svc.unhide_article(hidden_id)
         .await
         .map_err(|e| e.to_string())
 }
@@ -80,13 +186,19 @@ pub async fn get_favorites_count(
 pub async fn search_articles(
     query: String,
     limit: i64,
+    offset: Option<i64>,
     svc: State<'_, ArticleService<DatabasePool>>,
-) -> Result<Vec<Article>, String> {
-    svc.search_articles(&query, limit)
+) -> Result<Vec<SearchHit>, String> {
+    svc.search_articles(&query, limit, offset.unwrap_or(0))
         .await
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn reindex_search(svc: State<'_, ArticleService<DatabasePool>>) -> Result<(), String> {
+    svc.reindex_search().await.map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn fetch_article_content(
     article_id: i64,
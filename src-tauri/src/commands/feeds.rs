@@ -1,7 +1,12 @@
 //! Feed-related Tauri commands.
 
-use boke_core::{DatabasePool, FeedService, FeedWithMeta, OpmlError, RefreshResult, parse_opml};
+use boke_core::{
+    DEFAULT_USER_ID, DatabasePool, FeedService, FeedStatus, FeedUpdate, FeedWithMeta,
+    FolderService, ImportEvent, ImportJob, ImportJobs, OpmlError, RefreshResult, parse_opml,
+};
+use std::sync::Arc;
 use tauri::{AppHandle, Emitter, State};
+use tauri_plugin_dialog::DialogExt;
 
 #[tauri::command]
 pub async fn add_feed(
@@ -26,6 +31,32 @@ pub async fn get_feeds(
     svc.get_feeds().await.map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn update_feed(
+    feed_id: i64,
+    update: FeedUpdate,
+    svc: State<'_, FeedService<DatabasePool>>,
+) -> Result<FeedWithMeta, String> {
+    svc.update_feed(feed_id, &update).await.map_err(|e| {
+        if e.to_string().contains("UNIQUE") {
+            "Another feed already uses this URL".to_string()
+        } else {
+            e.to_string()
+        }
+    })
+}
+
+#[tauri::command]
+pub async fn set_feed_status(
+    feed_id: i64,
+    status: FeedStatus,
+    svc: State<'_, FeedService<DatabasePool>>,
+) -> Result<FeedWithMeta, String> {
+    svc.set_feed_status(feed_id, status)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn refresh_feed(
     feed_id: i64,
@@ -41,9 +72,10 @@ pub async fn refresh_all_feeds(
 ) -> Result<Vec<RefreshResult>, String> {
     let results = svc.refresh_all_feeds().await.map_err(|e| e.to_string())?;
 
-    // Emit event for notifications
-    let total_new: i64 = results.iter().map(|r| r.new_articles).sum();
-    let feeds_with_new = results.iter().filter(|r| r.new_articles > 0).count();
+    // Emit event for notifications, leaving out feeds that opted out
+    let notifying = || results.iter().filter(|r| r.notify);
+    let total_new: i64 = notifying().map(|r| r.new_articles).sum();
+    let feeds_with_new = notifying().filter(|r| r.new_articles > 0).count();
     if total_new > 0 {
         let _ = app.emit(
             "new-articles",
@@ -54,47 +86,82 @@ pub async fn refresh_all_feeds(
     Ok(results)
 }
 
-#[derive(Debug, Clone, serde::Serialize)]
-pub struct ImportResult {
-    pub added: i64,
-    pub skipped: i64,
-    pub errors: Vec<String>,
-}
-
+/// Start importing an OPML file in the background, returning the job as it starts.
+///
+/// Progress is emitted as `opml-import` events, one per feed status change and a final
+/// `finished` event.
 #[tauri::command]
 pub async fn import_opml(
     path: String,
+    app: AppHandle,
     svc: State<'_, FeedService<DatabasePool>>,
-) -> Result<ImportResult, String> {
+    jobs: State<'_, ImportJobs>,
+) -> Result<ImportJob, String> {
     let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
 
-    let feed_urls = parse_opml(&content).map_err(|e| match e {
+    let outlines = parse_opml(&content).map_err(|e| match e {
         OpmlError::ParseError(msg) => msg,
         OpmlError::NoFeeds => "No feeds found in OPML file".to_string(),
     })?;
 
-    let mut result = ImportResult {
-        added: 0,
-        skipped: 0,
-        errors: Vec::new(),
-    };
+    let (job, mut events) = jobs
+        .start(Arc::new(svc.inner().clone()), &outlines)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // The channel has room for every event of the job, so this never lags
+    tauri::async_runtime::spawn(async move {
+        while let Ok(event) = events.recv().await {
+            let finished = matches!(event, ImportEvent::Finished { .. });
+            let _ = app.emit("opml-import", &event);
+            if finished {
+                break;
+            }
+        }
+    });
 
-    // Get existing feeds to check for duplicates
-    let existing_feeds = svc.get_feeds().await.map_err(|e| e.to_string())?;
-    let existing_urls: std::collections::HashSet<_> =
-        existing_feeds.iter().map(|f| f.feed_url.as_str()).collect();
+    Ok(job)
+}
 
-    for feed_url in feed_urls {
-        if existing_urls.contains(feed_url.as_str()) {
-            result.skipped += 1;
-            continue;
-        }
+#[tauri::command]
+pub async fn get_import_job(
+    job_id: String,
+    jobs: State<'_, ImportJobs>,
+) -> Result<Option<ImportJob>, String> {
+    Ok(jobs.get(DEFAULT_USER_ID, &job_id))
+}
 
-        match svc.add_feed(&feed_url).await {
-            Ok(_) => result.added += 1,
-            Err(e) => result.errors.push(format!("{feed_url}: {e}")),
-        }
+/// Stop an import job; feeds already being fetched still finish.
+#[tauri::command]
+pub async fn cancel_import_job(job_id: String, jobs: State<'_, ImportJobs>) -> Result<(), String> {
+    if !jobs.cancel(DEFAULT_USER_ID, &job_id) {
+        return Err(format!("Import job {job_id} not found"));
     }
+    Ok(())
+}
+
+/// Save the subscription list as OPML where the user chooses. Returns the saved path, or
+/// `None` if the dialog was cancelled.
+#[tauri::command]
+pub async fn export_opml(
+    app: AppHandle,
+    folders: State<'_, FolderService<DatabasePool>>,
+) -> Result<Option<String>, String> {
+    let opml = folders.export_opml().await.map_err(|e| e.to_string())?;
+
+    let dialog = app
+        .dialog()
+        .file()
+        .add_filter("OPML", &["opml"])
+        .set_file_name("boke-subscriptions.opml");
+    let path = tauri::async_runtime::spawn_blocking(move || dialog.blocking_save_file())
+        .await
+        .map_err(|e| e.to_string())?;
+    let Some(path) = path else {
+        return Ok(None);
+    };
 
-    Ok(result)
+    let path = path.into_path().map_err(|e| e.to_string())?;
+    std::fs::write(&path, opml).map_err(|e| e.to_string())?;
+    Ok(Some(path.display().to_string()))
 }
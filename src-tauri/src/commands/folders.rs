@@ -1,6 +1,6 @@
 //! Folder-related Tauri commands.
 
-use boke_core::{DatabasePool, Folder, FolderService};
+use boke_core::{DatabasePool, Folder, FolderDeleteMode, FolderService, FolderTree};
 use tauri::State;
 
 #[tauri::command]
@@ -10,12 +10,20 @@ pub async fn get_folders(
     svc.get_folders().await.map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn get_folder_tree(
+    svc: State<'_, FolderService<DatabasePool>>,
+) -> Result<FolderTree, String> {
+    svc.get_folder_tree().await.map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn create_folder(
     name: String,
+    parent_id: Option<i64>,
     svc: State<'_, FolderService<DatabasePool>>,
 ) -> Result<Folder, String> {
-    svc.create_folder(&name).await.map_err(|e| {
+    svc.create_folder(&name, parent_id).await.map_err(|e| {
         if e.to_string().contains("UNIQUE") {
             format!("Folder \"{name}\" already exists")
         } else {
@@ -38,9 +46,32 @@ pub async fn rename_folder(
 #[tauri::command]
 pub async fn delete_folder(
     folder_id: i64,
+    mode: Option<FolderDeleteMode>,
+    svc: State<'_, FolderService<DatabasePool>>,
+) -> Result<(), String> {
+    svc.delete_folder(folder_id, mode.unwrap_or_default())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn move_folder(
+    folder_id: i64,
+    parent_id: Option<i64>,
     svc: State<'_, FolderService<DatabasePool>>,
 ) -> Result<(), String> {
-    svc.delete_folder(folder_id)
+    svc.move_folder(folder_id, parent_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn reorder_folders(
+    parent_id: Option<i64>,
+    folder_ids: Vec<i64>,
+    svc: State<'_, FolderService<DatabasePool>>,
+) -> Result<(), String> {
+    svc.reorder_folders(parent_id, &folder_ids)
         .await
         .map_err(|e| e.to_string())
 }
@@ -55,3 +86,14 @@ pub async fn move_feed_to_folder(
         .await
         .map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+pub async fn reorder_feeds(
+    folder_id: Option<i64>,
+    feed_ids: Vec<i64>,
+    svc: State<'_, FolderService<DatabasePool>>,
+) -> Result<(), String> {
+    svc.reorder_feeds(folder_id, &feed_ids)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,79 @@
+//! Highlight-related Tauri commands.
+
+use boke_core::{DatabasePool, Highlight, HighlightService, NewHighlight, UpdateHighlight};
+use tauri::State;
+
+#[tauri::command]
+pub async fn get_article_highlights(
+    article_id: i64,
+    svc: State<'_, HighlightService<DatabasePool>>,
+) -> Result<Vec<Highlight>, String> {
+    svc.get_article_highlights(article_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn search_highlights(
+    query: String,
+    limit: i64,
+    offset: Option<i64>,
+    svc: State<'_, HighlightService<DatabasePool>>,
+) -> Result<Vec<Highlight>, String> {
+    svc.search_highlights(&query, limit, offset.unwrap_or(0))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn create_highlight(
+    highlight: NewHighlight,
+    svc: State<'_, HighlightService<DatabasePool>>,
+) -> Result<Highlight, String> {
+    if highlight.quote.trim().is_empty() {
+        return Err("Quote must not be empty".to_string());
+    }
+    svc.create_highlight(&highlight)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_highlight(
+    highlight_id: i64,
+    note: Option<String>,
+    color: Option<String>,
+    svc: State<'_, HighlightService<DatabasePool>>,
+) -> Result<(), String> {
+    svc.update_highlight(highlight_id, &UpdateHighlight { note, color })
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_highlight(
+    highlight_id: i64,
+    svc: State<'_, HighlightService<DatabasePool>>,
+) -> Result<(), String> {
+    svc.delete_highlight(highlight_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn export_article_highlights(
+    article_id: i64,
+    svc: State<'_, HighlightService<DatabasePool>>,
+) -> Result<String, String> {
+    svc.export_article(article_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn export_label_highlights(
+    label_id: i64,
+    svc: State<'_, HighlightService<DatabasePool>>,
+) -> Result<String, String> {
+    svc.export_label(label_id).await.map_err(|e| e.to_string())
+}
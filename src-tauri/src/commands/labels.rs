@@ -0,0 +1,77 @@
+//! Label-related Tauri commands.
+
+use boke_core::{DatabasePool, Label, LabelService, NewLabel};
+use tauri::State;
+
+#[tauri::command]
+pub async fn get_labels(svc: State<'_, LabelService<DatabasePool>>) -> Result<Vec<Label>, String> {
+    svc.get_labels().await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn create_label(
+    name: String,
+    color: Option<String>,
+    svc: State<'_, LabelService<DatabasePool>>,
+) -> Result<Label, String> {
+    if name.trim().is_empty() {
+        return Err("Name must not be empty".to_string());
+    }
+    svc.create_label(&NewLabel {
+        name: name.clone(),
+        color,
+    })
+    .await
+    .map_err(|e| {
+        if e.to_string().contains("UNIQUE") {
+            format!("Label \"{name}\" already exists")
+        } else {
+            e.to_string()
+        }
+    })
+}
+
+#[tauri::command]
+pub async fn update_label(
+    label_id: i64,
+    name: String,
+    color: Option<String>,
+    svc: State<'_, LabelService<DatabasePool>>,
+) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("Name must not be empty".to_string());
+    }
+    svc.update_label(label_id, &NewLabel { name, color })
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_label(
+    label_id: i64,
+    svc: State<'_, LabelService<DatabasePool>>,
+) -> Result<(), String> {
+    svc.delete_label(label_id).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn add_label_to_articles(
+    label_id: i64,
+    article_ids: Vec<i64>,
+    svc: State<'_, LabelService<DatabasePool>>,
+) -> Result<(), String> {
+    svc.add_label(label_id, &article_ids)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn remove_label_from_articles(
+    label_id: i64,
+    article_ids: Vec<i64>,
+    svc: State<'_, LabelService<DatabasePool>>,
+) -> Result<(), String> {
+    svc.remove_label(label_id, &article_ids)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -1,3 +1,7 @@
 pub mod articles;
 pub mod feeds;
 pub mod folders;
+pub mod highlights;
+pub mod labels;
+pub mod smart_folders;
+pub mod stats;
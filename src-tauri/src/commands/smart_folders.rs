@@ -0,0 +1,50 @@
+//! Smart folder Tauri commands.
+
+use boke_core::{DatabasePool, NewSmartFolder, SmartFolder, SmartFolderService};
+use tauri::State;
+
+#[tauri::command]
+pub async fn get_smart_folders(
+    svc: State<'_, SmartFolderService<DatabasePool>>,
+) -> Result<Vec<SmartFolder>, String> {
+    svc.get_smart_folders().await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn create_smart_folder(
+    name: String,
+    query: String,
+    svc: State<'_, SmartFolderService<DatabasePool>>,
+) -> Result<SmartFolder, String> {
+    if name.trim().is_empty() {
+        return Err("Name must not be empty".to_string());
+    }
+    svc.create_smart_folder(&NewSmartFolder { name, query })
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_smart_folder(
+    smart_folder_id: i64,
+    name: String,
+    query: String,
+    svc: State<'_, SmartFolderService<DatabasePool>>,
+) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("Name must not be empty".to_string());
+    }
+    svc.update_smart_folder(smart_folder_id, &NewSmartFolder { name, query })
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_smart_folder(
+    smart_folder_id: i64,
+    svc: State<'_, SmartFolderService<DatabasePool>>,
+) -> Result<(), String> {
+    svc.delete_smart_folder(smart_folder_id)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,25 @@
+//! Statistics Tauri commands.
+
+use boke_core::{DatabasePool, FeedHealth, ReadingStats, StatsService};
+use tauri::State;
+
+#[tauri::command]
+pub async fn get_reading_stats(
+    days: Option<i64>,
+    svc: State<'_, StatsService<DatabasePool>>,
+) -> Result<ReadingStats, String> {
+    svc.get_reading_stats(days.unwrap_or(30))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_feed_stats(
+    feed_id: i64,
+    dead_after_months: Option<u32>,
+    svc: State<'_, StatsService<DatabasePool>>,
+) -> Result<FeedHealth, String> {
+    svc.get_feed_health(feed_id, dead_after_months.unwrap_or(6))
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -2,10 +2,14 @@ mod commands;
 
 use std::sync::Arc;
 
-use boke_core::{ArticleService, DatabasePool, FeedService, FolderService};
+use boke_core::{
+    AppEvent, ArticleService, BusEvent, DatabasePool, EventBus, FeedService, FolderService,
+    HighlightService, ImportJobs, LabelService, SmartFolderService, StatsService,
+};
 use tauri::menu::{MenuBuilder, MenuItemBuilder};
 use tauri::tray::TrayIconBuilder;
 use tauri::{Emitter, Manager};
+use tokio::sync::broadcast::error::RecvError;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -18,6 +22,8 @@ pub fn run() {
             commands::feeds::add_feed,
             commands::feeds::remove_feed,
             commands::feeds::get_feeds,
+            commands::feeds::update_feed,
+            commands::feeds::set_feed_status,
             commands::feeds::refresh_feed,
             commands::feeds::refresh_all_feeds,
             commands::articles::get_articles,
@@ -25,16 +31,47 @@ pub fn run() {
             commands::articles::toggle_read,
             commands::articles::mark_all_read,
             commands::articles::mark_all_unread,
+            commands::articles::hide_article,
+            commands::articles::delete_article,
+            commands::articles::get_hidden_articles,
+            commands::articles::unhide_article,
             commands::articles::toggle_favorite,
             commands::articles::get_favorites_count,
             commands::feeds::import_opml,
+            commands::feeds::get_import_job,
+            commands::feeds::cancel_import_job,
+            commands::feeds::export_opml,
             commands::articles::search_articles,
+            commands::articles::reindex_search,
             commands::articles::fetch_article_content,
             commands::folders::get_folders,
+            commands::folders::get_folder_tree,
             commands::folders::create_folder,
             commands::folders::rename_folder,
             commands::folders::delete_folder,
+            commands::folders::move_folder,
+            commands::folders::reorder_folders,
             commands::folders::move_feed_to_folder,
+            commands::folders::reorder_feeds,
+            commands::highlights::get_article_highlights,
+            commands::highlights::search_highlights,
+            commands::highlights::create_highlight,
+            commands::highlights::update_highlight,
+            commands::highlights::delete_highlight,
+            commands::highlights::export_article_highlights,
+            commands::highlights::export_label_highlights,
+            commands::labels::get_labels,
+            commands::labels::create_label,
+            commands::labels::update_label,
+            commands::labels::delete_label,
+            commands::labels::add_label_to_articles,
+            commands::labels::remove_label_from_articles,
+            commands::smart_folders::get_smart_folders,
+            commands::smart_folders::create_smart_folder,
+            commands::smart_folders::update_smart_folder,
+            commands::smart_folders::delete_smart_folder,
+            commands::stats::get_reading_stats,
+            commands::stats::get_feed_stats,
         ])
         .setup(|app| {
             // Database setup using boke-core
@@ -51,10 +88,30 @@ pub fn run() {
                 .expect("failed to initialize database");
             let db = Arc::new(db);
 
-            // Register services as managed state
-            app.manage(FeedService::new(db.clone()));
-            app.manage(ArticleService::new(db.clone()));
-            app.manage(FolderService::new(db));
+            // Register services as managed state, publishing changes to one event bus
+            let events = EventBus::new();
+            app.manage(FeedService::new(db.clone()).with_events(events.clone()));
+            app.manage(ArticleService::new(db.clone()).with_events(events.clone()));
+            app.manage(FolderService::new(db.clone()).with_events(events.clone()));
+            app.manage(HighlightService::new(db.clone()));
+            app.manage(LabelService::new(db.clone()).with_events(events.clone()));
+            app.manage(SmartFolderService::new(db.clone()));
+            app.manage(StatsService::new(db));
+            app.manage(ImportJobs::new());
+
+            // Forward the bus to the webview; the desktop app has a single user
+            let mut receiver = events.subscribe();
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                loop {
+                    let event = match receiver.recv().await {
+                        Ok(BusEvent { event, .. }) => event,
+                        Err(RecvError::Lagged(_)) => AppEvent::Resync,
+                        Err(RecvError::Closed) => break,
+                    };
+                    let _ = handle.emit("app-event", &event);
+                }
+            });
 
             // System tray
             let refresh_item = MenuItemBuilder::with_id("refresh", "Refresh All").build(app)?;
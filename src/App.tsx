@@ -8,6 +8,7 @@ import UpdateBanner from "./components/ui/UpdateBanner";
 import { SIDEBAR_WIDTH, ARTICLE_LIST_WIDTH } from "./lib/constants";
 import { useFeeds, useRefreshAllFeeds, useRefreshFeed } from "./api/feeds";
 import { useFolders } from "./api/folders";
+import { useLiveUpdates } from "./api/events";
 import {
   useArticles,
   useToggleRead,
@@ -48,6 +49,7 @@ export default function App() {
   } = useArticles(activeFeedId, activeFilter === "unread", activeFilter === "favourites");
   const refreshAll = useRefreshAllFeeds();
   const refreshFeed = useRefreshFeed();
+  useLiveUpdates();
   const toggleRead = useToggleRead();
   const toggleFavorite = useToggleFavorite();
   const markAllRead = useMarkAllRead();
@@ -0,0 +1,34 @@
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import * as api from "../lib/api";
+
+export function useCurrentUser(enabled: boolean) {
+  return useQuery({
+    queryKey: ["currentUser"],
+    queryFn: api.getCurrentUser,
+    enabled,
+    retry: false,
+  });
+}
+
+export function useLogin() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ username, password }: { username: string; password: string }) =>
+      api.login(username, password),
+    onSuccess: (user) => {
+      queryClient.setQueryData(["currentUser"], user);
+      queryClient.invalidateQueries({ predicate: (query) => query.queryKey[0] !== "currentUser" });
+    },
+  });
+}
+
+export function useLogout() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: api.logout,
+    onSuccess: () => {
+      queryClient.clear();
+      queryClient.setQueryData(["currentUser"], null);
+    },
+  });
+}
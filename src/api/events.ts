@@ -0,0 +1,56 @@
+import { useEffect } from "react";
+import { useQueryClient, type QueryKey } from "@tanstack/react-query";
+import * as api from "../lib/api";
+import type { AppEvent } from "../types";
+
+/** Query keys whose data an event makes stale; `null` for everything. */
+function staleKeys(event: AppEvent): QueryKey[] | null {
+  switch (event.type) {
+    case "refresh_started":
+    case "article_favorited":
+      return [];
+    case "refresh_finished":
+    case "refresh_failed":
+      return [["feeds"]];
+    case "new_articles":
+      return [["feeds"], ["folders"], ["articles"]];
+    case "article_changed":
+      return [
+        ["article", event.article_id],
+        ["articles"],
+        ["feeds"],
+        ["folders"],
+        ["favorites-count"],
+      ];
+    case "articles_labelled":
+      return [["articles"]];
+    case "articles_marked":
+      return [["articles"], ["feeds"], ["folders"]];
+    case "feeds_changed":
+    case "folders_changed":
+      return [["feeds"], ["folders"], ["articles"]];
+    case "resync":
+      return null;
+  }
+}
+
+/** Keep cached data current as the backend reports changes. */
+export function useLiveUpdates() {
+  const queryClient = useQueryClient();
+
+  useEffect(() => {
+    const unsubscribe = api.subscribeToEvents((event) => {
+      const keys = staleKeys(event);
+      if (keys === null) {
+        queryClient.invalidateQueries();
+        return;
+      }
+      for (const queryKey of keys) {
+        queryClient.invalidateQueries({ queryKey });
+      }
+    });
+    return () => {
+      unsubscribe.then((fn) => fn());
+    };
+  }, [queryClient]);
+}
@@ -1,5 +1,6 @@
 import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
 import * as api from "../lib/api";
+import type { ImportJob } from "../types";
 
 export function useFeeds() {
   return useQuery({
@@ -18,17 +19,31 @@ export function useAddFeed() {
   });
 }
 
-export function useImportOpml() {
+/** Import an OPML file, reporting the job's progress to `onProgress` as feeds are added. */
+export function useImportOpml(onProgress?: (job: ImportJob) => void) {
   const queryClient = useQueryClient();
   return useMutation({
-    mutationFn: (fileOrPath: string | File) => api.importOpml(fileOrPath),
+    mutationFn: (fileOrPath: string | File) => api.importOpml(fileOrPath, onProgress),
     onSuccess: () => {
       queryClient.invalidateQueries({ queryKey: ["feeds"] });
+      queryClient.invalidateQueries({ queryKey: ["folders"] });
       queryClient.invalidateQueries({ queryKey: ["articles"] });
     },
   });
 }
 
+export function useCancelImport() {
+  return useMutation({
+    mutationFn: (jobId: string) => api.cancelImport(jobId),
+  });
+}
+
+export function useExportOpml() {
+  return useMutation({
+    mutationFn: () => api.exportOpml(),
+  });
+}
+
 export function useRemoveFeed() {
   const queryClient = useQueryClient();
   return useMutation({
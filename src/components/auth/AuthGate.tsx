@@ -0,0 +1,28 @@
+import type { ReactNode } from "react";
+import { useCurrentUser } from "../../api/auth";
+import { isWeb } from "../../lib/platform";
+import LoginScreen from "./LoginScreen";
+
+/**
+ * Shows the login screen until the server knows who the user is.
+ * The desktop app has no login, so it renders its children straight away.
+ */
+export default function AuthGate({ children }: { children: ReactNode }) {
+  const web = isWeb();
+  const { data: user, isPending, error } = useCurrentUser(web);
+
+  if (!web) return <>{children}</>;
+  if (isPending) return null;
+  if (error) {
+    return (
+      <div
+        className="flex h-screen items-center justify-center text-sm"
+        style={{ color: "var(--color-text-secondary)" }}
+      >
+        {error instanceof Error ? error.message : String(error)}
+      </div>
+    );
+  }
+  if (!user) return <LoginScreen />;
+  return <>{children}</>;
+}
@@ -0,0 +1,72 @@
+import { useState } from "react";
+import { useLogin } from "../../api/auth";
+
+export default function LoginScreen() {
+  const [username, setUsername] = useState("");
+  const [password, setPassword] = useState("");
+  const login = useLogin();
+
+  const handleSubmit = (e: React.FormEvent) => {
+    e.preventDefault();
+    if (!username.trim() || !password) return;
+    login.mutate({ username: username.trim(), password });
+  };
+
+  const inputStyle = {
+    backgroundColor: "var(--color-bg-secondary)",
+    color: "var(--color-text-primary)",
+    border: "1px solid var(--color-border)",
+  };
+
+  return (
+    <div
+      className="flex h-screen items-center justify-center"
+      style={{ backgroundColor: "var(--color-bg-primary)" }}
+    >
+      <form
+        onSubmit={handleSubmit}
+        className="rounded-lg p-6 w-[360px] shadow-xl"
+        style={{
+          backgroundColor: "var(--color-bg-primary)",
+          border: "1px solid var(--color-border)",
+        }}
+      >
+        <h1 className="text-lg font-semibold mb-4" style={{ color: "var(--color-text-primary)" }}>
+          Boke
+        </h1>
+        <input
+          type="text"
+          value={username}
+          onChange={(e) => setUsername(e.target.value)}
+          placeholder="Username"
+          autoComplete="username"
+          autoFocus
+          className="w-full px-3 py-2 rounded-md text-sm outline-none mb-2"
+          style={inputStyle}
+        />
+        <input
+          type="password"
+          value={password}
+          onChange={(e) => setPassword(e.target.value)}
+          placeholder="Password"
+          autoComplete="current-password"
+          className="w-full px-3 py-2 rounded-md text-sm outline-none"
+          style={inputStyle}
+        />
+        {login.isError && (
+          <p className="text-red-500 text-xs mt-2">
+            {login.error instanceof Error ? login.error.message : String(login.error)}
+          </p>
+        )}
+        <button
+          type="submit"
+          disabled={login.isPending || !username.trim() || !password}
+          className="w-full mt-4 px-3 py-1.5 rounded-md text-sm font-medium text-white disabled:opacity-50"
+          style={{ backgroundColor: "var(--color-accent)" }}
+        >
+          {login.isPending ? "Logging in..." : "Log in"}
+        </button>
+      </form>
+    </div>
+  );
+}
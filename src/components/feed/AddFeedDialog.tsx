@@ -1,6 +1,7 @@
 import { useState } from "react";
 import { openFileDialog } from "../../lib/platform";
-import { useAddFeed, useImportOpml } from "../../api/feeds";
+import { useAddFeed, useCancelImport, useExportOpml, useImportOpml } from "../../api/feeds";
+import type { ImportJob } from "../../types";
 
 interface AddFeedDialogProps {
   open: boolean;
@@ -11,7 +12,10 @@ export default function AddFeedDialog({ open: isOpen, onClose }: AddFeedDialogPr
   const [url, setUrl] = useState("");
   const [importStatus, setImportStatus] = useState<string | null>(null);
   const addFeed = useAddFeed();
-  const importOpml = useImportOpml();
+  const [importJob, setImportJob] = useState<ImportJob | null>(null);
+  const importOpml = useImportOpml(setImportJob);
+  const cancelImport = useCancelImport();
+  const exportOpml = useExportOpml();
 
   if (!isOpen) return null;
 
@@ -33,18 +37,30 @@ export default function AddFeedDialog({ open: isOpen, onClose }: AddFeedDialogPr
     if (!fileOrPath) return;
 
     setImportStatus(null);
+    setImportJob(null);
     importOpml.mutate(fileOrPath, {
-      onSuccess: (result) => {
+      onSuccess: (job) => {
+        const count = (status: string) => job.items.filter((i) => i.status === status).length;
         const parts: string[] = [];
-        if (result.added > 0) parts.push(`${result.added} feeds added`);
-        if (result.skipped > 0) parts.push(`${result.skipped} already subscribed`);
-        if (result.errors.length > 0) parts.push(`${result.errors.length} failed`);
+        if (count("added") > 0) parts.push(`${count("added")} feeds added`);
+        if (count("duplicate") > 0) parts.push(`${count("duplicate")} already subscribed`);
+        if (count("failed") > 0) parts.push(`${count("failed")} failed`);
+        if (job.cancelled) parts.push("import cancelled");
         setImportStatus(parts.join(", "));
       },
     });
   };
 
-  const busy = addFeed.isPending || importOpml.isPending;
+  const handleExportOpml = () => {
+    setImportStatus(null);
+    exportOpml.mutate(undefined, {
+      onSuccess: (saved) => {
+        if (saved) setImportStatus(`Exported to ${saved}`);
+      },
+    });
+  };
+
+  const busy = addFeed.isPending || importOpml.isPending || exportOpml.isPending;
 
   return (
     <div
@@ -137,6 +153,40 @@ export default function AddFeedDialog({ open: isOpen, onClose }: AddFeedDialogPr
           {importOpml.isPending ? "Importing..." : "Import OPML file"}
         </button>
 
+        {importOpml.isPending && importJob && (
+          <ImportProgress
+            job={importJob}
+            onCancel={() => cancelImport.mutate(importJob.id)}
+            cancelling={cancelImport.isPending || cancelImport.isSuccess}
+          />
+        )}
+
+        {/* Export OPML */}
+        <button
+          type="button"
+          disabled={busy}
+          onClick={handleExportOpml}
+          className="w-full flex items-center justify-center gap-2 px-3 py-2 mt-2 rounded-md text-sm transition-opacity hover:opacity-80 disabled:opacity-50"
+          style={{
+            backgroundColor: "var(--color-bg-secondary)",
+            color: "var(--color-text-primary)",
+            border: "1px solid var(--color-border)",
+          }}
+        >
+          <svg
+            width="16"
+            height="16"
+            viewBox="0 0 16 16"
+            fill="none"
+            stroke="currentColor"
+            strokeWidth="1.5"
+          >
+            <path d="M8 2v8M8 10l-3-3M8 10l3-3" />
+            <path d="M2 10v3a1 1 0 001 1h10a1 1 0 001-1v-3" />
+          </svg>
+          {exportOpml.isPending ? "Exporting..." : "Export OPML file"}
+        </button>
+
         {importOpml.isError && (
           <p className="text-red-500 text-xs mt-2">
             {importOpml.error instanceof Error
@@ -144,12 +194,82 @@ export default function AddFeedDialog({ open: isOpen, onClose }: AddFeedDialogPr
               : String(importOpml.error)}
           </p>
         )}
+        {exportOpml.isError && (
+          <p className="text-red-500 text-xs mt-2">
+            {exportOpml.error instanceof Error
+              ? exportOpml.error.message
+              : String(exportOpml.error)}
+          </p>
+        )}
         {importStatus && (
           <p className="text-xs mt-2" style={{ color: "var(--color-text-secondary)" }}>
             {importStatus}
           </p>
         )}
+        {!importOpml.isPending && importJob && (
+          <ul className="text-xs mt-1 max-h-24 overflow-y-auto text-red-500">
+            {importJob.items
+              .filter((item) => item.status === "failed")
+              .map((item) => (
+                <li key={item.url} className="truncate" title={item.error ?? undefined}>
+                  {item.title || item.url}: {item.error}
+                </li>
+              ))}
+          </ul>
+        )}
+      </div>
+    </div>
+  );
+}
+
+function ImportProgress({
+  job,
+  onCancel,
+  cancelling,
+}: {
+  job: ImportJob;
+  onCancel: () => void;
+  cancelling: boolean;
+}) {
+  const total = job.items.length;
+  const done = job.items.filter(
+    (item) => item.status !== "queued" && item.status !== "fetching"
+  ).length;
+  const fetching = job.items.filter((item) => item.status === "fetching");
+
+  return (
+    <div className="mt-3">
+      <div className="flex items-center justify-between text-xs mb-1">
+        <span style={{ color: "var(--color-text-secondary)" }}>
+          {done} of {total} feeds
+        </span>
+        <button
+          type="button"
+          disabled={cancelling}
+          onClick={onCancel}
+          className="hover:underline disabled:opacity-50"
+          style={{ color: "var(--color-text-secondary)" }}
+        >
+          {cancelling ? "Cancelling..." : "Cancel"}
+        </button>
+      </div>
+      <div
+        className="h-1.5 rounded-full overflow-hidden"
+        style={{ backgroundColor: "var(--color-bg-secondary)" }}
+      >
+        <div
+          className="h-full transition-all"
+          style={{
+            width: `${total > 0 ? (done / total) * 100 : 100}%`,
+            backgroundColor: "var(--color-accent)",
+          }}
+        />
       </div>
+      {fetching.length > 0 && (
+        <p className="text-xs mt-1 truncate" style={{ color: "var(--color-text-secondary)" }}>
+          Fetching {fetching.map((item) => item.title || item.url).join(", ")}
+        </p>
+      )}
     </div>
   );
 }
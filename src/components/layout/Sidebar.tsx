@@ -7,6 +7,8 @@ import {
   useDeleteFolder,
   useMoveFeedToFolder,
 } from "../../api/folders";
+import { useLogout } from "../../api/auth";
+import { isWeb } from "../../lib/platform";
 import AddFeedDialog from "../feed/AddFeedDialog";
 import { FeedListSkeleton } from "../ui/Skeleton";
 import type { FeedWithMeta, SidebarItem } from "../../types";
@@ -106,6 +108,7 @@ export default function Sidebar({
   const { data: folders } = useFolders();
   const { data: favoritesCount } = useFavoritesCount();
   const refreshAll = useRefreshAllFeeds();
+  const logout = useLogout();
   const createFolder = useCreateFolder();
   const deleteFolder = useDeleteFolder();
   const moveFeed = useMoveFeedToFolder();
@@ -374,6 +377,29 @@ export default function Sidebar({
               </svg>
             )}
           </button>
+          {isWeb() && (
+            <button
+              type="button"
+              aria-label="Log out"
+              className="p-1.5 rounded-md hover:opacity-80 transition-opacity"
+              style={{ color: "var(--color-text-secondary)" }}
+              title="Log out"
+              onClick={() => logout.mutate()}
+              disabled={logout.isPending}
+            >
+              <svg
+                width="16"
+                height="16"
+                viewBox="0 0 16 16"
+                fill="none"
+                stroke="currentColor"
+                strokeWidth="1.5"
+              >
+                <path d="M6 14H3a1 1 0 01-1-1V3a1 1 0 011-1h3" />
+                <path d="M10.5 11.5L14 8l-3.5-3.5M14 8H6" />
+              </svg>
+            </button>
+          )}
           <button
             type="button"
             aria-label="Refresh all feeds"
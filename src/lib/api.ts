@@ -5,7 +5,17 @@
  * In web mode, uses HTTP REST API calls.
  */
 
-import type { FeedWithMeta, Article, Folder, RefreshResult, DiscoveredFeed } from "../types";
+import type {
+  FeedWithMeta,
+  Article,
+  Folder,
+  RefreshResult,
+  DiscoveredFeed,
+  AppEvent,
+  ImportEvent,
+  ImportJob,
+  User,
+} from "../types";
 
 // Runtime detection - check if we're running in Tauri (evaluated at call time, not module load)
 // Tauri v2 uses __TAURI_INTERNALS__, v1 used __TAURI__
@@ -36,6 +46,29 @@ async function getHttpApi(): Promise<HttpApi> {
   return httpApi;
 }
 
+// Authentication - only used in web mode, the desktop app has a single local user
+
+/** The logged-in user, or null when the server wants a login first. */
+export async function getCurrentUser(): Promise<User | null> {
+  const api = await getHttpApi();
+  try {
+    return await api.getCurrentUser();
+  } catch (error) {
+    if (error instanceof api.UnauthorizedError) return null;
+    throw error;
+  }
+}
+
+export async function login(username: string, password: string): Promise<User> {
+  const api = await getHttpApi();
+  return api.login(username, password);
+}
+
+export async function logout(): Promise<void> {
+  const api = await getHttpApi();
+  return api.logout();
+}
+
 // Feed operations
 
 export async function addFeed(url: string): Promise<FeedWithMeta> {
@@ -88,30 +121,106 @@ export async function refreshAllFeeds(): Promise<RefreshResult[]> {
   }
 }
 
-export type { ImportResult } from "./http";
+export type { ImportResult } from "../types";
+
+/** Apply an import progress event to the job it belongs to. */
+function applyImportEvent(job: ImportJob, event: ImportEvent): ImportJob {
+  switch (event.type) {
+    case "job":
+      return event.job;
+    case "item": {
+      const items = [...job.items];
+      items[event.index] = event.item;
+      return { ...job, items };
+    }
+    case "finished":
+      return { ...job, cancelled: event.cancelled, finished_at: new Date().toISOString() };
+  }
+}
 
 /**
- * Import feeds from an OPML file.
+ * Import feeds from an OPML file, in the background.
  * In Tauri mode, pass the file path as string.
  * In web mode, pass the File object.
+ *
+ * `onProgress` gets the job each time a feed changes status; the promise resolves with the
+ * job once every feed is done or the job is cancelled.
  */
-export async function importOpml(fileOrPath: string | File): Promise<{
-  added: number;
-  skipped: number;
-  errors: string[];
-}> {
+export async function importOpml(
+  fileOrPath: string | File,
+  onProgress?: (job: ImportJob) => void
+): Promise<ImportJob> {
+  let job: ImportJob;
+  let watch: TauriApi["watchImportJob"];
   if (isTauri()) {
     const api = await getTauriApi();
     if (typeof fileOrPath !== "string") {
       throw new Error("In desktop mode, importOpml expects a file path string");
     }
-    return api.importOpml(fileOrPath);
+    job = await api.importOpml(fileOrPath);
+    watch = api.watchImportJob;
   } else {
     const api = await getHttpApi();
     if (!(fileOrPath instanceof File)) {
       throw new Error("In web mode, importOpml expects a File object");
     }
-    return api.importOpml(fileOrPath);
+    job = await api.importOpml(fileOrPath);
+    watch = api.watchImportJob;
+  }
+  onProgress?.(job);
+  if (job.finished_at) return job;
+
+  return new Promise((resolve) => {
+    const stop = watch(job.id, (event) => {
+      job = applyImportEvent(job, event);
+      onProgress?.(job);
+      if (job.finished_at) {
+        stop.then((fn) => fn());
+        resolve(job);
+      }
+    });
+  });
+}
+
+/**
+ * Listen for changes made elsewhere: refreshes, other devices, sync clients.
+ * Returns a function that stops listening.
+ */
+export async function subscribeToEvents(
+  onEvent: (event: AppEvent) => void
+): Promise<() => void> {
+  if (isTauri()) {
+    const api = await getTauriApi();
+    return api.subscribeToEvents(onEvent);
+  } else {
+    const api = await getHttpApi();
+    return api.subscribeToEvents(onEvent);
+  }
+}
+
+/** Stop an import job; feeds already being fetched still finish. */
+export async function cancelImport(jobId: string): Promise<void> {
+  if (isTauri()) {
+    const api = await getTauriApi();
+    return api.cancelImportJob(jobId);
+  } else {
+    const api = await getHttpApi();
+    return api.cancelImportJob(jobId);
+  }
+}
+
+/**
+ * Export subscriptions as OPML.
+ * In desktop mode this opens a save dialog; in web mode the file is downloaded.
+ * Returns the saved path or filename, or null if the user cancelled.
+ */
+export async function exportOpml(): Promise<string | null> {
+  if (isTauri()) {
+    const api = await getTauriApi();
+    return api.exportOpml();
+  } else {
+    const api = await getHttpApi();
+    return api.exportOpml();
   }
 }
 
@@ -1,9 +1,25 @@
-import type { FeedWithMeta, Article, Folder, RefreshResult, DiscoveredFeed } from "../types";
+import type {
+  FeedWithMeta,
+  Article,
+  Folder,
+  RefreshResult,
+  DiscoveredFeed,
+  AppEvent,
+  ImportEvent,
+  ImportJob,
+  User,
+} from "../types";
 
 const API_BASE = import.meta.env.VITE_API_BASE_URL || "/api";
 
+/** Thrown when the server needs the user to log in first. */
+export class UnauthorizedError extends Error {
+  name = "UnauthorizedError";
+}
+
 async function request<T>(path: string, options?: RequestInit): Promise<T> {
   const response = await fetch(`${API_BASE}${path}`, {
+    credentials: "include",
     ...options,
     headers: {
       "Content-Type": "application/json",
@@ -13,6 +29,9 @@ async function request<T>(path: string, options?: RequestInit): Promise<T> {
 
   if (!response.ok) {
     const error = await response.json().catch(() => ({ error: response.statusText }));
+    if (response.status === 401) {
+      throw new UnauthorizedError(error.error || "Login required");
+    }
     throw new Error(error.error || "Request failed");
   }
 
@@ -24,6 +43,23 @@ async function request<T>(path: string, options?: RequestInit): Promise<T> {
   return response.json();
 }
 
+// Authentication
+
+export function getCurrentUser(): Promise<User> {
+  return request("/auth/me");
+}
+
+export function login(username: string, password: string): Promise<User> {
+  return request("/auth/login", {
+    method: "POST",
+    body: JSON.stringify({ username, password }),
+  });
+}
+
+export function logout(): Promise<void> {
+  return request("/auth/logout", { method: "POST" });
+}
+
 export function addFeed(url: string): Promise<FeedWithMeta> {
   return request("/feeds", {
     method: "POST",
@@ -141,30 +177,95 @@ export function moveFeedToFolder(feedId: number, folderId: number | null): Promi
   return request(`/folders/${folderId}/feeds/${feedId}`, { method: "PUT" });
 }
 
-// OPML import in web mode uses file upload
-export interface ImportResult {
-  added: number;
-  skipped: number;
-  errors: string[];
-}
-
-export async function importOpml(file: File): Promise<ImportResult> {
+// OPML import in web mode uses file upload; the server imports in the background
+export async function importOpml(file: File): Promise<ImportJob> {
   const formData = new FormData();
   formData.append("file", file);
 
   const response = await fetch(`${API_BASE}/feeds/import`, {
     method: "POST",
     body: formData,
+    credentials: "include",
   });
 
   if (!response.ok) {
     const error = await response.json().catch(() => ({ error: response.statusText }));
+    if (response.status === 401) {
+      throw new UnauthorizedError(error.error || "Login required");
+    }
     throw new Error(error.error || "Import failed");
   }
 
   return response.json();
 }
 
+/**
+ * Follow an import job's progress over server-sent events. The job as it stands is
+ * reported first. Returns a function that stops watching.
+ */
+export async function watchImportJob(
+  jobId: string,
+  onEvent: (event: ImportEvent) => void
+): Promise<() => void> {
+  const source = new EventSource(`${API_BASE}/feeds/import/${jobId}/events`, {
+    withCredentials: true,
+  });
+  source.addEventListener("job", (e) => {
+    const job: ImportJob = JSON.parse((e as MessageEvent).data);
+    // A finished job has nothing more to send; don't let the browser reconnect
+    if (job.finished_at) source.close();
+    onEvent({ type: "job", job });
+  });
+  source.addEventListener("item", (e) => onEvent(JSON.parse((e as MessageEvent).data)));
+  source.addEventListener("finished", (e) => {
+    source.close();
+    onEvent(JSON.parse((e as MessageEvent).data));
+  });
+  return () => source.close();
+}
+
+/** Follow live changes over server-sent events. Returns a function that stops listening. */
+export async function subscribeToEvents(
+  onEvent: (event: AppEvent) => void
+): Promise<() => void> {
+  const source = new EventSource(`${API_BASE}/events`, { withCredentials: true });
+  source.onmessage = (e) => onEvent(JSON.parse(e.data));
+  // The browser reconnects on its own; anything missed meanwhile needs a reload
+  let connected = false;
+  source.onopen = () => {
+    if (connected) onEvent({ type: "resync" });
+    connected = true;
+  };
+  return () => source.close();
+}
+
+export function cancelImportJob(jobId: string): Promise<void> {
+  return request(`/feeds/import/${jobId}`, { method: "DELETE" });
+}
+
+export async function exportOpml(): Promise<string | null> {
+  const response = await fetch(`${API_BASE}/feeds/export.opml`, {
+    credentials: "include",
+  });
+
+  if (!response.ok) {
+    const error = await response.json().catch(() => ({ error: response.statusText }));
+    if (response.status === 401) {
+      throw new UnauthorizedError(error.error || "Login required");
+    }
+    throw new Error(error.error || "Export failed");
+  }
+
+  const filename = "boke-subscriptions.opml";
+  const url = URL.createObjectURL(await response.blob());
+  const link = document.createElement("a");
+  link.href = url;
+  link.download = filename;
+  link.click();
+  URL.revokeObjectURL(url);
+  return filename;
+}
+
 // These are not available in web mode
 export function discoverFeed(_url: string): Promise<DiscoveredFeed[]> {
   throw new Error("discoverFeed is only available in desktop mode");
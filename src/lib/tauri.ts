@@ -1,5 +1,15 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { FeedWithMeta, Article, Folder, RefreshResult, DiscoveredFeed } from "../types";
+import { listen } from "@tauri-apps/api/event";
+import type {
+  FeedWithMeta,
+  Article,
+  Folder,
+  RefreshResult,
+  DiscoveredFeed,
+  AppEvent,
+  ImportEvent,
+  ImportJob,
+} from "../types";
 
 export function addFeed(url: string): Promise<FeedWithMeta> {
   return invoke<FeedWithMeta>("add_feed", { url });
@@ -83,14 +93,38 @@ export function moveFeedToFolder(feedId: number, folderId: number | null): Promi
   return invoke<void>("move_feed_to_folder", { feedId, folderId });
 }
 
-export interface ImportResult {
-  added: number;
-  skipped: number;
-  errors: string[];
+export function importOpml(path: string): Promise<ImportJob> {
+  return invoke<ImportJob>("import_opml", { path });
 }
 
-export function importOpml(path: string): Promise<ImportResult> {
-  return invoke<ImportResult>("import_opml", { path });
+/**
+ * Follow an import job's progress. The job as it stands is reported first, so events sent
+ * before the listener was attached are not lost. Returns a function that stops watching.
+ */
+export async function watchImportJob(
+  jobId: string,
+  onEvent: (event: ImportEvent) => void
+): Promise<() => void> {
+  const unlisten = await listen<ImportEvent>("opml-import", (event) => {
+    if (event.payload.type !== "job" && event.payload.job_id === jobId) {
+      onEvent(event.payload);
+    }
+  });
+  const job = await invoke<ImportJob | null>("get_import_job", { jobId });
+  if (job) onEvent({ type: "job", job });
+  return unlisten;
+}
+
+export function subscribeToEvents(onEvent: (event: AppEvent) => void): Promise<() => void> {
+  return listen<AppEvent>("app-event", (event) => onEvent(event.payload));
+}
+
+export function cancelImportJob(jobId: string): Promise<void> {
+  return invoke<void>("cancel_import_job", { jobId });
+}
+
+export function exportOpml(): Promise<string | null> {
+  return invoke<string | null>("export_opml");
 }
 
 export function discoverFeed(url: string): Promise<DiscoveredFeed[]> {
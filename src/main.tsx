@@ -1,10 +1,19 @@
 import React from "react";
 import ReactDOM from "react-dom/client";
-import { QueryClient, QueryClientProvider } from "@tanstack/react-query";
+import { QueryCache, QueryClient, QueryClientProvider } from "@tanstack/react-query";
 import App from "./App";
+import AuthGate from "./components/auth/AuthGate";
 import "./styles/index.css";
 
 const queryClient = new QueryClient({
+  // A session that expired while the app was open sends the user back to the login screen
+  queryCache: new QueryCache({
+    onError: (error) => {
+      if (error.name === "UnauthorizedError") {
+        queryClient.invalidateQueries({ queryKey: ["currentUser"] });
+      }
+    },
+  }),
   defaultOptions: {
     queries: {
       staleTime: 1000 * 60 * 5, // 5 minutes
@@ -16,7 +25,9 @@ const queryClient = new QueryClient({
 ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
   <React.StrictMode>
     <QueryClientProvider client={queryClient}>
-      <App />
+      <AuthGate>
+        <App />
+      </AuthGate>
     </QueryClientProvider>
   </React.StrictMode>
 );
@@ -68,12 +68,65 @@ export interface RefreshResult {
   error: string | null;
 }
 
+export interface ImportResult {
+  added: number;
+  skipped: number;
+  errors: string[];
+}
+
+export type ImportStatus = "queued" | "fetching" | "added" | "duplicate" | "failed" | "cancelled";
+
+export interface ImportItem {
+  url: string;
+  title: string | null;
+  status: ImportStatus;
+  error: string | null;
+  feed_id: number | null;
+}
+
+export interface ImportJob {
+  id: string;
+  items: ImportItem[];
+  started_at: string;
+  finished_at: string | null;
+  cancelled: boolean;
+}
+
+/** Progress of an OPML import job; `job` carries the whole job as it stands. */
+export type ImportEvent =
+  | { type: "job"; job: ImportJob }
+  | { type: "item"; job_id: string; index: number; item: ImportItem }
+  | { type: "finished"; job_id: string; cancelled: boolean; result: ImportResult };
+
+/** A change pushed by the backend, so views can update without polling. */
+export type AppEvent =
+  | { type: "refresh_started"; feed_id: number }
+  | { type: "refresh_finished"; feed_id: number; new_articles: number }
+  | { type: "refresh_failed"; feed_id: number; error: string }
+  | { type: "new_articles"; feed_id: number; count: number; article_ids: number[] }
+  | { type: "article_changed"; article_id: number; is_read: boolean; is_favorite: boolean }
+  | { type: "article_favorited"; article_id: number }
+  | { type: "articles_labelled"; label_id: number; article_ids: number[] }
+  | { type: "articles_marked"; read: boolean }
+  | { type: "feeds_changed"; feed_id: number | null }
+  | { type: "folders_changed" }
+  | { type: "resync" };
+
 export interface DiscoveredFeed {
   url: string;
   title: string | null;
   feed_type: string;
 }
 
+export interface User {
+  id: number;
+  username: string;
+  is_admin: boolean;
+  disabled: boolean;
+  has_password: boolean;
+  created_at: string | null;
+}
+
 export type SidebarItem =
   | { kind: "filter"; filter: "all" | "unread" | "favourites" }
   | { kind: "folder"; folderId: number }